@@ -9,6 +9,7 @@ use actix_web::{
 };
 use ports::storage::Storage;
 use services::{HealthReporter, StatusReporter};
+use validator::ChainContinuity;
 
 use crate::{
     config::Config,
@@ -21,11 +22,16 @@ pub async fn launch_api_server(
     metrics_registry: Registry,
     storage: impl Storage + 'static,
     fuel_health_check: HealthChecker,
-    eth_health_check: HealthChecker,
+    eth_health_checks: Vec<(String, HealthChecker)>,
+    chain_continuity: ChainContinuity,
 ) -> Result<()> {
     let metrics_registry = Arc::new(metrics_registry);
     let status_reporter = Arc::new(StatusReporter::new(storage));
-    let health_reporter = Arc::new(HealthReporter::new(fuel_health_check, eth_health_check));
+    let health_reporter = Arc::new(HealthReporter::new(
+        fuel_health_check,
+        eth_health_checks,
+        chain_continuity,
+    ));
     HttpServer::new(move || {
         App::new()
             .app_data(web::Data::new(Arc::clone(&metrics_registry)))
@@ -66,14 +72,10 @@ async fn status(data: web::Data<Arc<StatusReporter<Database>>>) -> impl Responde
 async fn metrics(registry: web::Data<Arc<Registry>>) -> impl Responder {
     let encoder = TextEncoder::new();
     let mut buf: Vec<u8> = vec![];
-    let mut encode = |metrics: &_| {
-        encoder
-            .encode(metrics, &mut buf)
-            .map_err(map_to_internal_err)
-    };
+    let mut encode = |metrics: &_| encoder.encode(metrics, &mut buf);
 
-    encode(&registry.gather())?;
-    encode(&prometheus::gather())?;
+    encode(&registry.gather()).map_err(map_to_internal_err)?;
+    encode(&prometheus::gather()).map_err(map_to_internal_err)?;
 
     let text = String::from_utf8(buf).map_err(map_to_internal_err)?;
 
@@ -1,7 +1,14 @@
-use std::{net::Ipv4Addr, path::PathBuf, str::FromStr, time::Duration};
+use std::{
+    net::Ipv4Addr,
+    num::{NonZeroU32, NonZeroUsize},
+    path::{Path, PathBuf},
+    str::FromStr,
+    time::Duration,
+};
 
-use clap::{command, Parser};
+use clap::{Args, Parser, Subcommand};
 use eth::{Address, Chain};
+use ports::types::Compression;
 use serde::Deserialize;
 use storage::DbConfig;
 use url::Url;
@@ -23,6 +30,29 @@ impl Config {
             }
         }
 
+        let mut activation_heights: Vec<_> = self
+            .fuel
+            .block_producer_key_rotations
+            .iter()
+            .map(|rotation| rotation.activation_height)
+            .collect();
+        activation_heights.sort_unstable();
+        if activation_heights.first() == Some(&0)
+            || activation_heights.windows(2).any(|pair| pair[0] == pair[1])
+        {
+            return Err(crate::errors::Error::Other(
+                "Block producer key rotations must have distinct activation heights above 0"
+                    .to_string(),
+            ));
+        }
+
+        if self.fuel.block_page_size.get() > fuel::MAX_BLOCK_PAGE_SIZE {
+            return Err(crate::errors::Error::Other(format!(
+                "Block page size must not exceed {}",
+                fuel::MAX_BLOCK_PAGE_SIZE
+            )));
+        }
+
         Ok(())
     }
 }
@@ -32,24 +62,175 @@ pub struct Fuel {
     /// URL to a fuel-core graphql endpoint.
     #[serde(deserialize_with = "parse_url")]
     pub graphql_endpoint: Url,
-    /// Block producer public key
+    /// Block producer public key, in effect from genesis until the first key rotation
     pub block_producer_public_key: ports::fuel::FuelPublicKey,
+    /// Keys the block producer switches to, each in effect from its activation height onwards
+    #[serde(default)]
+    pub block_producer_key_rotations: Vec<KeyRotation>,
+    /// Number of blocks fetched per request when catching up on a range of blocks, at most
+    /// `fuel::MAX_BLOCK_PAGE_SIZE`
+    #[serde(default = "defaults::block_page_size")]
+    pub block_page_size: NonZeroU32,
+    /// Number of block page requests in flight at once
+    #[serde(default = "defaults::block_page_concurrency")]
+    pub block_page_concurrency: NonZeroUsize,
+    /// How often the node is asked for the next block while following the chain
+    #[serde(
+        default = "defaults::block_poll_interval",
+        deserialize_with = "human_readable_duration"
+    )]
+    pub block_poll_interval: Duration,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct KeyRotation {
+    pub activation_height: u32,
+    pub public_key: ports::fuel::FuelPublicKey,
+}
+
+impl Fuel {
+    /// Number of blocks fetched by one round of `block_page_concurrency` page requests.
+    pub fn blocks_per_fetch_round(&self) -> usize {
+        (self.block_page_size.get() as usize).saturating_mul(self.block_page_concurrency.get())
+    }
+
+    /// `(activation height, public key)` pairs the produced blocks are validated against.
+    pub fn block_producer_key_schedule(&self) -> Vec<(u32, ports::fuel::FuelPublicKey)> {
+        std::iter::once((0, self.block_producer_public_key))
+            .chain(
+                self.block_producer_key_rotations
+                    .iter()
+                    .map(|rotation| (rotation.activation_height, rotation.public_key)),
+            )
+            .collect()
+    }
 }
 
 #[derive(Debug, Clone, Deserialize)]
 pub struct Eth {
     /// The secret key authorized by the L1 bridging contracts to post block commitments.
-    pub wallet_key: String,
+    pub wallet_key: WalletKey,
     /// The secret key for posting L2 state to L1.
-    pub blob_pool_wallet_key: Option<String>,
-    /// URL to a Ethereum RPC endpoint.
-    #[serde(deserialize_with = "parse_url")]
-    pub rpc: Url,
+    pub blob_pool_wallet_key: Option<WalletKey>,
+    /// Ethereum RPC endpoints, each either ws(s) or http(s). Commit events are polled for over
+    /// http. Either a single url or a list of `{ url, priority }`, lower priorities being tried
+    /// first.
+    #[serde(deserialize_with = "parse_rpc_endpoints")]
+    pub rpc: Vec<eth::RpcEndpoint>,
     /// Chain id of the ethereum network.
     #[serde(deserialize_with = "parse_chain_id")]
     pub chain_id: Chain,
     /// Ethereum address of the fuel chain state contract.
     pub state_contract_address: Address,
+    /// Address state is sent to as calldata whenever that is cheaper than blobs. Without it state
+    /// is only ever posted in blobs.
+    pub state_inbox_address: Option<Address>,
+}
+
+/// Either a hex encoded secret key, a V3 keystore file together with where to find its password
+/// or a remote signer speaking the web3signer JSON-RPC API:
+///
+/// ```toml
+/// [eth.wallet_key]
+/// keystore = "/keys/committer.json"
+/// password_file = "/run/secrets/committer_password" # or password_env = "WALLET_PASSWORD"
+///
+/// [eth.blob_pool_wallet_key]
+/// signer_url = "http://localhost:9000"
+/// address = "0x70997970C51812dc3A010C7d01b50e0d17dc79C8"
+/// ```
+#[derive(Clone, PartialEq, Eq, Deserialize)]
+#[serde(untagged)]
+pub enum WalletKey {
+    Raw(String),
+    Keystore {
+        keystore: PathBuf,
+        password_file: Option<PathBuf>,
+        password_env: Option<String>,
+    },
+    Remote {
+        #[serde(deserialize_with = "parse_url")]
+        signer_url: Url,
+        address: Address,
+    },
+}
+
+impl WalletKey {
+    /// Reads the keystore password, if any, from wherever it is kept.
+    pub fn resolve(&self) -> crate::errors::Result<eth::WalletKey> {
+        let (keystore, password_file, password_env) = match self {
+            Self::Raw(key) => return Ok(eth::WalletKey::Raw(key.clone())),
+            Self::Remote {
+                signer_url,
+                address,
+            } => {
+                return Ok(eth::WalletKey::Remote {
+                    url: signer_url.clone(),
+                    address: *address,
+                })
+            }
+            Self::Keystore {
+                keystore,
+                password_file,
+                password_env,
+            } => (keystore, password_file, password_env),
+        };
+
+        let password = match (password_file, password_env) {
+            (Some(file), None) => std::fs::read_to_string(file)
+                .map_err(|e| {
+                    crate::errors::Error::Other(format!(
+                        "failed to read keystore password from {}: {e}",
+                        file.display()
+                    ))
+                })?
+                .trim_end_matches(['\r', '\n'])
+                .to_string(),
+            (None, Some(var)) => std::env::var(var).map_err(|e| {
+                crate::errors::Error::Other(format!(
+                    "failed to read keystore password from env var {var}: {e}"
+                ))
+            })?,
+            _ => {
+                return Err(crate::errors::Error::Other(format!(
+                    "keystore {} needs exactly one of `password_file` and `password_env`",
+                    keystore.display()
+                )))
+            }
+        };
+
+        Ok(eth::WalletKey::Keystore {
+            path: keystore.clone(),
+            password,
+        })
+    }
+}
+
+// keeps the secret key out of logs
+impl std::fmt::Debug for WalletKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Raw(_) => f.write_str("Raw(<redacted>)"),
+            Self::Keystore {
+                keystore,
+                password_file,
+                password_env,
+            } => f
+                .debug_struct("Keystore")
+                .field("keystore", keystore)
+                .field("password_file", password_file)
+                .field("password_env", password_env)
+                .finish(),
+            Self::Remote {
+                signer_url,
+                address,
+            } => f
+                .debug_struct("Remote")
+                .field("signer_url", signer_url)
+                .field("address", address)
+                .finish(),
+        }
+    }
 }
 
 fn parse_chain_id<'de, D>(deserializer: D) -> Result<Chain, D::Error>
@@ -74,6 +255,48 @@ where
     })
 }
 
+fn parse_rpc_endpoints<'de, D>(deserializer: D) -> Result<Vec<eth::RpcEndpoint>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    struct Endpoint {
+        #[serde(deserialize_with = "parse_url")]
+        url: Url,
+        #[serde(default)]
+        priority: u32,
+    }
+
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Endpoints {
+        Single(String),
+        Many(Vec<Endpoint>),
+    }
+
+    let endpoints = match Endpoints::deserialize(deserializer)? {
+        Endpoints::Single(url) => {
+            let url = Url::from_str(&url).map_err(|e| {
+                let msg = format!("Failed to parse URL '{url}': {e};");
+                serde::de::Error::custom(msg)
+            })?;
+            vec![eth::RpcEndpoint::new(url, 0)]
+        }
+        Endpoints::Many(endpoints) => endpoints
+            .into_iter()
+            .map(|endpoint| eth::RpcEndpoint::new(endpoint.url, endpoint.priority))
+            .collect(),
+    };
+
+    if endpoints.is_empty() {
+        return Err(serde::de::Error::custom(
+            "at least one rpc endpoint is required",
+        ));
+    }
+
+    Ok(endpoints)
+}
+
 #[derive(Debug, Clone, Deserialize)]
 pub struct App {
     /// Port used by the started server
@@ -85,6 +308,107 @@ pub struct App {
     /// How often to check the latest fuel block
     #[serde(deserialize_with = "human_readable_duration")]
     pub block_check_interval: Duration,
+    /// Number of L1 blocks that need to pass to accept the tx as finalized
+    #[serde(default = "defaults::num_blocks_to_finalize_tx")]
+    pub num_blocks_to_finalize_tx: u64,
+    /// Number of L1 blocks after which an incomplete block commit is resent. Doubles with every
+    /// attempt.
+    #[serde(default = "defaults::num_blocks_before_commit_resubmission")]
+    pub num_blocks_before_commit_resubmission: u64,
+    /// Number of L1 blocks a block commit needs to be buried under to be considered completed
+    #[serde(default = "defaults::commit_confirmation_depth")]
+    pub commit_confirmation_depth: u64,
+    /// Number of L1 blocks below the confirmation depth in which completed commits are still
+    /// checked for having been reorged out
+    #[serde(default = "defaults::commit_reorg_lookback")]
+    pub commit_reorg_lookback: u64,
+    /// Number of L1 blocks a blob tx can stay pending before it is replaced with higher fees
+    #[serde(default = "defaults::num_blocks_before_tx_replacement")]
+    pub num_blocks_before_tx_replacement: u64,
+    /// Max fee (in wei) a blob tx is allowed to cost, replacements above it are skipped. Unbounded
+    /// by default.
+    #[serde(default = "defaults::no_ceiling")]
+    pub tx_max_fee: u64,
+    /// Codec the state is compressed with before it is posted as blobs, none by default
+    #[serde(default)]
+    pub state_compression: Compression,
+    /// Compression level passed to the codec
+    #[serde(default = "defaults::state_compression_level")]
+    pub state_compression_level: u32,
+    /// Fuel block height the state import starts at. Every block from it up to the tip gets its
+    /// state imported, including those produced while the committer was down. Without it the
+    /// import starts at the tip of the chain.
+    #[serde(default)]
+    pub state_import_start_height: Option<u32>,
+    /// Number of blobs the state has to fill before it is posted
+    #[serde(default = "defaults::state_batch_min_blobs")]
+    pub state_batch_min_blobs: usize,
+    /// How long state may wait for more state to fill the blobs before it is posted regardless.
+    /// State is posted right away by default.
+    #[serde(default, deserialize_with = "human_readable_duration")]
+    pub state_batch_max_latency: Duration,
+    /// Execution base fee (in wei) above which state submissions are deferred. Unbounded by
+    /// default.
+    #[serde(default = "defaults::no_ceiling")]
+    pub state_max_base_fee: u64,
+    /// Blob base fee (in wei) above which state submissions are deferred. Unbounded by default.
+    #[serde(default = "defaults::no_ceiling")]
+    pub state_max_blob_base_fee: u64,
+    /// How long state submissions may be deferred because of high base fees
+    #[serde(default, deserialize_with = "human_readable_duration")]
+    pub state_max_fee_deferral: Duration,
+}
+
+/// Defaults of the settings existing configurations predate.
+mod defaults {
+    use std::{
+        num::{NonZeroU32, NonZeroUsize},
+        time::Duration,
+    };
+
+    pub fn block_page_size() -> NonZeroU32 {
+        NonZeroU32::new(100).expect("not zero")
+    }
+
+    pub fn block_page_concurrency() -> NonZeroUsize {
+        NonZeroUsize::new(4).expect("not zero")
+    }
+
+    pub fn block_poll_interval() -> Duration {
+        Duration::from_secs(1)
+    }
+
+    pub fn num_blocks_to_finalize_tx() -> u64 {
+        12
+    }
+
+    pub fn num_blocks_before_commit_resubmission() -> u64 {
+        20
+    }
+
+    pub fn commit_confirmation_depth() -> u64 {
+        12
+    }
+
+    pub fn commit_reorg_lookback() -> u64 {
+        128
+    }
+
+    pub fn num_blocks_before_tx_replacement() -> u64 {
+        10
+    }
+
+    pub fn no_ceiling() -> u64 {
+        u64::MAX
+    }
+
+    pub fn state_compression_level() -> u32 {
+        3
+    }
+
+    pub fn state_batch_min_blobs() -> usize {
+        1
+    }
 }
 
 fn human_readable_duration<'de, D>(deserializer: D) -> Result<Duration, D::Error>
@@ -122,20 +446,96 @@ impl Default for Internal {
     version,
     about,
     propagate_version = true,
-    arg_required_else_help(true)
+    arg_required_else_help(true),
+    args_conflicts_with_subcommands = true,
+    subcommand_negates_reqs = true
 )]
 struct Cli {
-    #[arg(value_name = "FILE", help = "Path to the configuration file")]
-    config_path: PathBuf,
+    #[arg(
+        value_name = "FILE",
+        help = "Path to the configuration file",
+        required = true
+    )]
+    config_path: Option<PathBuf>,
+    #[command(subcommand)]
+    command: Option<Command>,
 }
 
-pub fn parse() -> crate::errors::Result<Config> {
-    let cli = Cli::parse();
+#[derive(Subcommand)]
+enum Command {
+    /// Decodes posted blobs and checks the recovered state against a fuel node
+    VerifyBlobs(VerifyBlobs),
+}
 
-    let config = config::Config::builder()
-        .add_source(config::File::from(cli.config_path))
+#[derive(Debug, Args)]
+pub struct VerifyBlobs {
+    #[arg(
+        long,
+        value_name = "URL",
+        help = "Fuel node to compare the recovered state against"
+    )]
+    pub fuel_graphql_endpoint: Url,
+    #[arg(
+        long,
+        help = "Files hold blob txs with their sidecar instead of the raw blobs of a single tx"
+    )]
+    pub blob_tx: bool,
+    #[arg(
+        long,
+        value_name = "HASH",
+        value_parser = parse_tx_hash,
+        requires = "committer_config",
+        help = "State tx the files were taken from, its recorded posting mode decides how they are decoded"
+    )]
+    pub tx: Option<[u8; 32]>,
+    #[arg(
+        long,
+        value_name = "FILE",
+        requires = "tx",
+        help = "Configuration of the committer that posted the tx, used to look it up in its database"
+    )]
+    pub committer_config: Option<PathBuf>,
+    #[arg(
+        value_name = "FILE",
+        required = true,
+        help = "Binary or hex encoded blobs (in posting order), blob txs or calldata"
+    )]
+    pub files: Vec<PathBuf>,
+}
+
+pub enum Invocation {
+    Run(Box<Config>),
+    VerifyBlobs(VerifyBlobs),
+}
+
+fn parse_tx_hash(hash: &str) -> Result<[u8; 32], String> {
+    let bytes = hex::decode(hash.strip_prefix("0x").unwrap_or(hash)).map_err(|e| e.to_string())?;
+    bytes
+        .try_into()
+        .map_err(|bytes: Vec<u8>| format!("expected 32 bytes, got {}", bytes.len()))
+}
+
+fn load(path: &Path) -> crate::errors::Result<config::Config> {
+    Ok(config::Config::builder()
+        .add_source(config::File::from(path))
         .add_source(config::Environment::with_prefix("COMMITTER").separator("__"))
-        .build()?;
+        .build()?)
+}
+
+/// Only the database settings of the committer configuration at `path`.
+pub fn load_db_config(path: &Path) -> crate::errors::Result<DbConfig> {
+    Ok(load(path)?.get("app.db")?)
+}
+
+pub fn parse() -> crate::errors::Result<Invocation> {
+    let cli = Cli::parse();
+
+    if let Some(Command::VerifyBlobs(args)) = cli.command {
+        return Ok(Invocation::VerifyBlobs(args));
+    }
+
+    let config_path = cli.config_path.expect("clap requires the config path");
+    let config = load(&config_path)?;
 
-    Ok(config.try_deserialize()?)
+    Ok(Invocation::Run(Box::new(config.try_deserialize()?)))
 }
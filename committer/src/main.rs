@@ -3,6 +3,7 @@ mod api;
 mod config;
 mod errors;
 mod setup;
+mod verify;
 
 use api::launch_api_server;
 use errors::Result;
@@ -11,6 +12,7 @@ use tokio_util::sync::CancellationToken;
 
 use crate::setup::shut_down;
 use ports::l1::Contract;
+use validator::ChainContinuity;
 
 pub type L1 = eth::WebsocketClient;
 pub type Database = storage::Postgres;
@@ -21,7 +23,10 @@ pub type Validator = validator::BlockValidator;
 async fn main() -> Result<()> {
     setup::logger();
 
-    let config = config::parse()?;
+    let config = match config::parse()? {
+        config::Invocation::Run(config) => *config,
+        config::Invocation::VerifyBlobs(args) => return verify::verify_blobs(args).await,
+    };
     config.validate()?;
 
     let storage = setup::storage(&config).await?;
@@ -34,8 +39,13 @@ async fn main() -> Result<()> {
     let (fuel_adapter, fuel_health_check) =
         setup::fuel_adapter(&config, &internal_config, &metrics_registry);
 
-    let (ethereum_rpc, eth_health_check) =
-        setup::l1_adapter(&config, &internal_config, &metrics_registry).await?;
+    let (ethereum_rpc, eth_health_checks) = setup::l1_adapter(
+        &config,
+        &internal_config,
+        storage.clone(),
+        &metrics_registry,
+    )
+    .await?;
 
     let commit_interval = ethereum_rpc.commit_interval();
 
@@ -46,11 +56,14 @@ async fn main() -> Result<()> {
         cancel_token.clone(),
     );
 
+    let chain_continuity = ChainContinuity::default();
+
     let committer_handle = setup::block_committer(
         commit_interval,
         ethereum_rpc.clone(),
         storage.clone(),
         fuel_adapter.clone(),
+        chain_continuity.clone(),
         &config,
         &metrics_registry,
         cancel_token.clone(),
@@ -64,16 +77,33 @@ async fn main() -> Result<()> {
         cancel_token.clone(),
     );
 
+    let receipt_listener_handle = setup::commit_receipt_listener(
+        ethereum_rpc.clone(),
+        storage.clone(),
+        cancel_token.clone(),
+        &config,
+    );
+
+    let confirmer_handle = setup::commit_confirmer(
+        ethereum_rpc.clone(),
+        storage.clone(),
+        &metrics_registry,
+        cancel_token.clone(),
+        &config,
+    );
+
     let mut handles = vec![
         wallet_balance_tracker_handle,
         committer_handle,
         listener_handle,
+        receipt_listener_handle,
+        confirmer_handle,
     ];
 
     // If the blob pool wallet key is set, we need to start the state committer and state importer
     if config.eth.blob_pool_wallet_key.is_some() {
         let state_committer_handle = setup::state_committer(
-            ethereum_rpc,
+            ethereum_rpc.clone(),
             storage.clone(),
             &metrics_registry,
             cancel_token.clone(),
@@ -82,14 +112,24 @@ async fn main() -> Result<()> {
 
         let state_importer_handle = setup::state_importer(
             fuel_adapter,
+            &ethereum_rpc,
             storage.clone(),
             &metrics_registry,
             cancel_token.clone(),
             &config,
         );
 
+        let state_listener_handle = setup::state_listener(
+            ethereum_rpc,
+            storage.clone(),
+            cancel_token.clone(),
+            &metrics_registry,
+            &config,
+        );
+
         handles.push(state_committer_handle);
         handles.push(state_importer_handle);
+        handles.push(state_listener_handle);
     }
 
     launch_api_server(
@@ -97,7 +137,8 @@ async fn main() -> Result<()> {
         metrics_registry,
         storage.clone(),
         fuel_health_check,
-        eth_health_check,
+        eth_health_checks,
+        chain_continuity,
     )
     .await?;
 
@@ -1,13 +1,17 @@
 use std::num::NonZeroU32;
+use std::sync::Arc;
 use std::time::Duration;
 
 use metrics::{prometheus::Registry, HealthChecker, RegistersMetrics};
 use ports::storage::Storage;
-use services::{BlockCommitter, CommitListener, Runner, WalletBalanceTracker};
+use services::{
+    BatchingPolicy, BlockCommitter, CommitConfirmer, CommitListener, CommitReceiptListener,
+    Compressor, FeeCeiling, Runner, WalletBalanceTracker,
+};
 use tokio::task::JoinHandle;
 use tokio_util::sync::CancellationToken;
 use tracing::{error, info};
-use validator::BlockValidator;
+use validator::{BlockValidator, ChainContinuity};
 
 use crate::{config, errors::Result, Database, FuelApi, L1};
 
@@ -47,18 +51,67 @@ pub fn l1_event_listener(
     )
 }
 
+pub fn commit_receipt_listener(
+    l1: L1,
+    storage: impl Storage + 'static,
+    cancel_token: CancellationToken,
+    config: &config::Config,
+) -> tokio::task::JoinHandle<()> {
+    let commit_receipt_listener = CommitReceiptListener::new(l1, storage);
+
+    schedule_polling(
+        config.app.block_check_interval,
+        commit_receipt_listener,
+        "Commit Receipt Listener",
+        cancel_token,
+    )
+}
+
+pub fn commit_confirmer(
+    l1: L1,
+    storage: impl Storage + 'static,
+    registry: &Registry,
+    cancel_token: CancellationToken,
+    config: &config::Config,
+) -> tokio::task::JoinHandle<()> {
+    let commit_confirmer = CommitConfirmer::new(
+        l1,
+        storage,
+        config.app.commit_confirmation_depth,
+        config.app.commit_reorg_lookback,
+    );
+    commit_confirmer.register_metrics(registry);
+
+    schedule_polling(
+        config.app.block_check_interval,
+        commit_confirmer,
+        "Commit Confirmer",
+        cancel_token,
+    )
+}
+
+#[allow(clippy::too_many_arguments)]
 pub fn block_committer(
     commit_interval: NonZeroU32,
     l1: L1,
     storage: impl Storage + 'static,
     fuel: FuelApi,
+    chain_continuity: ChainContinuity,
     config: &config::Config,
     registry: &Registry,
     cancel_token: CancellationToken,
 ) -> tokio::task::JoinHandle<()> {
-    let validator = BlockValidator::new(config.fuel.block_producer_public_key);
-
-    let block_committer = BlockCommitter::new(l1, storage, fuel, validator, commit_interval);
+    let validator = BlockValidator::with_key_schedule(config.fuel.block_producer_key_schedule());
+
+    let block_committer = BlockCommitter::new(
+        l1,
+        storage,
+        fuel,
+        validator,
+        commit_interval,
+        config.app.num_blocks_before_commit_resubmission,
+        chain_continuity,
+    );
 
     block_committer.register_metrics(registry);
 
@@ -71,13 +124,30 @@ pub fn block_committer(
 }
 
 pub fn state_committer(
-    l1: L1,
+    da: L1,
     storage: impl Storage + 'static,
-    _registry: &Registry,
+    registry: &Registry,
     cancel_token: CancellationToken,
     config: &config::Config,
 ) -> tokio::task::JoinHandle<()> {
-    let state_committer = services::StateCommitter::new(l1, storage);
+    let batching = BatchingPolicy::new(
+        config.app.state_batch_min_blobs,
+        config.app.state_batch_max_latency,
+    );
+    let fee_ceiling = FeeCeiling::new(
+        config.app.state_max_base_fee.into(),
+        config.app.state_max_blob_base_fee.into(),
+        config.app.state_max_fee_deferral,
+    );
+    let state_committer = services::StateCommitter::new(
+        da,
+        storage,
+        config.app.num_blocks_before_tx_replacement,
+        config.app.tx_max_fee.into(),
+        batching,
+        fee_ceiling,
+    );
+    state_committer.register_metrics(registry);
 
     schedule_polling(
         config.app.block_check_interval,
@@ -87,15 +157,49 @@ pub fn state_committer(
     )
 }
 
+pub fn state_listener(
+    da: L1,
+    storage: impl Storage + 'static,
+    cancel_token: CancellationToken,
+    registry: &Registry,
+    config: &config::Config,
+) -> tokio::task::JoinHandle<()> {
+    let state_listener =
+        services::StateListener::new(da, storage, config.app.num_blocks_to_finalize_tx);
+
+    state_listener.register_metrics(registry);
+
+    schedule_polling(
+        config.app.block_check_interval,
+        state_listener,
+        "State Listener",
+        cancel_token,
+    )
+}
+
 pub fn state_importer(
     fuel: FuelApi,
+    da: &L1,
     storage: impl Storage + 'static,
-    _registry: &Registry,
+    registry: &Registry,
     cancel_token: CancellationToken,
     config: &config::Config,
 ) -> tokio::task::JoinHandle<()> {
-    let validator = BlockValidator::new(config.fuel.block_producer_public_key);
-    let state_importer = services::StateImporter::new(storage, fuel, validator);
+    let validator = BlockValidator::with_key_schedule(config.fuel.block_producer_key_schedule());
+    let compressor = Compressor::new(
+        config.app.state_compression,
+        config.app.state_compression_level,
+    );
+    let state_importer = services::StateImporter::new(
+        storage,
+        fuel,
+        validator,
+        compressor,
+        ports::da::Api::max_fragment_size(da),
+        config.app.state_import_start_height,
+        config.fuel.blocks_per_fetch_round(),
+    );
+    state_importer.register_metrics(registry);
 
     schedule_polling(
         config.app.block_check_interval,
@@ -108,23 +212,32 @@ pub fn state_importer(
 pub async fn l1_adapter(
     config: &config::Config,
     internal_config: &config::Internal,
+    storage: Database,
     registry: &Registry,
-) -> Result<(L1, HealthChecker)> {
+) -> Result<(L1, Vec<(String, HealthChecker)>)> {
+    let blob_pool_wallet_key = config
+        .eth
+        .blob_pool_wallet_key
+        .as_ref()
+        .map(config::WalletKey::resolve)
+        .transpose()?;
     let l1 = L1::connect(
         &config.eth.rpc,
         config.eth.chain_id,
         config.eth.state_contract_address,
-        &config.eth.wallet_key,
-        config.eth.blob_pool_wallet_key.clone(),
+        &config.eth.wallet_key.resolve()?,
+        blob_pool_wallet_key.as_ref(),
+        config.eth.state_inbox_address,
+        Some(Arc::new(storage)),
         internal_config.eth_errors_before_unhealthy,
     )
     .await?;
 
     l1.register_metrics(registry);
 
-    let health_check = l1.connection_health_checker();
+    let health_checks = l1.connection_health_checkers();
 
-    Ok((l1, health_check))
+    Ok((l1, health_checks))
 }
 
 fn schedule_polling(
@@ -158,7 +271,10 @@ pub fn fuel_adapter(
     let fuel_adapter = FuelApi::new(
         &config.fuel.graphql_endpoint,
         internal_config.fuel_errors_before_unhealthy,
-    );
+        config.fuel.block_page_size,
+        config.fuel.block_page_concurrency,
+    )
+    .with_block_poll_interval(config.fuel.block_poll_interval);
     fuel_adapter.register_metrics(registry);
 
     let fuel_connection_health = fuel_adapter.connection_health_checker();
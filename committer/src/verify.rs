@@ -0,0 +1,129 @@
+use std::{
+    num::{NonZeroU32, NonZeroUsize},
+    path::Path,
+};
+
+use eth::PostingMode;
+use ports::{storage::Storage, types::StateFrame};
+use services::{StateVerification, StateVerifier};
+use tracing::{error, info};
+
+use crate::{
+    config::{self, VerifyBlobs},
+    errors::{Error, Result},
+    Database, FuelApi,
+};
+
+/// Decodes the given blobs or calldata and checks that the state they carry matches the fuel chain.
+pub async fn verify_blobs(args: VerifyBlobs) -> Result<()> {
+    let mode = posting_mode(&args).await?;
+    let frames = read_frames(&args, mode)?;
+    info!("decoded {} frames", frames.len());
+
+    // blocks are looked up one by one, no ranges are fetched
+    let fuel = FuelApi::new(
+        &args.fuel_graphql_endpoint,
+        1,
+        NonZeroU32::MIN,
+        NonZeroUsize::MIN,
+    );
+    let reports = StateVerifier::new(fuel).verify(frames).await?;
+
+    let mut failed = 0;
+    for report in &reports {
+        let block_hash = hex::encode(report.block_hash);
+        match &report.verification {
+            StateVerification::Verified => {
+                info!("block {} ({block_hash}): verified", report.block_height);
+            }
+            StateVerification::Incomplete { missing_fragments } => {
+                failed += 1;
+                error!(
+                    "block {} ({block_hash}): missing fragments {missing_fragments:?}",
+                    report.block_height
+                );
+            }
+            StateVerification::Mismatch(reason) => {
+                failed += 1;
+                error!("block {} ({block_hash}): {reason}", report.block_height);
+            }
+        }
+    }
+
+    if failed > 0 {
+        return Err(Error::Other(format!(
+            "{failed} of {} blocks failed verification",
+            reports.len()
+        )));
+    }
+
+    Ok(())
+}
+
+/// How the state tx was posted according to the database of the committer that posted it. Files
+/// not tied to a tx are taken to be blobs.
+async fn posting_mode(args: &VerifyBlobs) -> Result<PostingMode> {
+    let (Some(tx_hash), Some(config_path)) = (args.tx, &args.committer_config) else {
+        return Ok(PostingMode::Blob);
+    };
+
+    let storage = Database::connect(&config::load_db_config(config_path)?).await?;
+    let tx = storage.submission_tx(tx_hash).await?.ok_or_else(|| {
+        Error::Other(format!(
+            "state tx {} not found in the committer database",
+            hex::encode(tx_hash)
+        ))
+    })?;
+
+    Ok(eth::posting_mode(&tx.submission())?)
+}
+
+fn read_frames(args: &VerifyBlobs, mode: PostingMode) -> Result<Vec<StateFrame>> {
+    let contents = args
+        .files
+        .iter()
+        .map(|path| read_bytes(path))
+        .collect::<Result<Vec<_>>>()?;
+
+    let frames = match mode {
+        PostingMode::Calldata => {
+            let mut frames = vec![];
+            for calldata in contents {
+                frames.extend(
+                    eth::decoding::frames_from_calldata(&calldata)
+                        .map_err(ports::l1::Error::from)?,
+                );
+            }
+            frames
+        }
+        PostingMode::Blob if args.blob_tx => {
+            let mut frames = vec![];
+            for raw_tx in contents {
+                frames.extend(
+                    eth::decoding::frames_from_blob_tx(&raw_tx).map_err(ports::l1::Error::from)?,
+                );
+            }
+            frames
+        }
+        PostingMode::Blob => {
+            eth::decoding::frames_from_blobs(&contents).map_err(ports::l1::Error::from)?
+        }
+    };
+
+    Ok(frames)
+}
+
+/// Accepts both binary files and hex dumps, as returned by RPCs and explorers.
+fn read_bytes(path: &Path) -> Result<Vec<u8>> {
+    let contents = std::fs::read(path)?;
+
+    let Ok(text) = std::str::from_utf8(&contents) else {
+        return Ok(contents);
+    };
+
+    let text = text.trim();
+    match hex::decode(text.strip_prefix("0x").unwrap_or(text)) {
+        Ok(bytes) => Ok(bytes),
+        Err(_) => Ok(contents),
+    }
+}
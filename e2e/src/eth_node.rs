@@ -2,7 +2,7 @@ const FOUNDRY_PROJECT: &str = concat!(env!("OUT_DIR"), "/foundry");
 
 use std::time::Duration;
 
-use eth::WebsocketClient;
+use eth::{RpcEndpoint, WalletKey, WebsocketClient};
 use ethers::{
     abi::Address,
     signers::{
@@ -81,7 +81,7 @@ impl EthNodeProcess {
             .arg("script")
             .arg("script/deploy.sol:MyScript")
             .arg("--fork-url")
-            .arg(&format!("http://localhost:{}", self.port()))
+            .arg(format!("http://localhost:{}", self.port()))
             .arg("--broadcast")
             .stdin(std::process::Stdio::null())
             .env("PRIVATE_KEY", self.main_wallet_key())
@@ -183,13 +183,16 @@ impl DeployedContract {
         blocks_per_commit_interval: u32,
         commit_cooldown_seconds: u32,
     ) -> anyhow::Result<Self> {
+        let wallet_key = WalletKey::Raw(wallet_priv_key.to_string());
         let blob_wallet = None;
         let chain_state_contract = WebsocketClient::connect(
-            url,
+            &[RpcEndpoint::new(url.clone(), 0)],
             Chain::AnvilHardhat,
             address,
-            wallet_priv_key,
+            &wallet_key,
             blob_wallet,
+            None,
+            None,
             5,
         )
         .await?;
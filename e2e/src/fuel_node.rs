@@ -1,3 +1,5 @@
+use std::num::{NonZeroU32, NonZeroUsize};
+
 use fuel::HttpClient;
 use ports::fuel::FuelPublicKey;
 use secp256k1::{PublicKey, Secp256k1, SecretKey};
@@ -71,7 +73,12 @@ impl FuelNode {
 
 impl FuelNodeProcess {
     pub fn client(&self) -> HttpClient {
-        HttpClient::new(&self.url, 5)
+        HttpClient::new(
+            &self.url,
+            5,
+            NonZeroU32::new(100).unwrap(),
+            NonZeroUsize::MIN,
+        )
     }
 
     async fn wait_until_healthy(&self) {
@@ -1,3 +1,5 @@
+pub mod decoding;
+pub mod framing;
 mod types;
 mod utils;
 
@@ -0,0 +1,140 @@
+//! Recovers the state frames posted by the committer from blobs or calldata as they are found on
+//! L1.
+
+use ports::types::StateFrame;
+use rlp::Rlp;
+
+use super::{framing, BLOB_TX_TYPE};
+use crate::error::{Error, Result};
+
+/// Frames carried by the blobs of a single tx, in the order the blobs were posted.
+pub fn frames_from_blobs(blobs: &[Vec<u8>]) -> Result<Vec<StateFrame>> {
+    if let Some(blob) = blobs
+        .iter()
+        .find(|blob| blob.len() != c_kzg::BYTES_PER_BLOB)
+    {
+        return Err(Error::Other(format!(
+            "expected blobs of {} bytes, got {}",
+            c_kzg::BYTES_PER_BLOB,
+            blob.len()
+        )));
+    }
+
+    // reverses `BlobSidecar::generate_field_elements`, every field element starts with a zero byte
+    let payload: Vec<u8> = blobs
+        .iter()
+        .flat_map(|blob| blob.chunks(32))
+        .flat_map(|field_element| &field_element[1..])
+        .copied()
+        .collect();
+
+    framing::decode(&payload)
+}
+
+/// Frames carried by a blob tx in its network form, i.e. the signed tx wrapped together with its
+/// blobs, commitments and proofs.
+pub fn frames_from_blob_tx(raw_tx: &[u8]) -> Result<Vec<StateFrame>> {
+    let Some((&BLOB_TX_TYPE, wrapper)) = raw_tx.split_first() else {
+        return Err(Error::Other("not a blob tx".to_string()));
+    };
+
+    let blobs: Vec<Vec<u8>> = Rlp::new(wrapper)
+        .list_at(1)
+        .map_err(|e| Error::Other(format!("malformed blob tx sidecar: {e}")))?;
+
+    frames_from_blobs(&blobs)
+}
+
+/// Frames carried by the input of a tx sent to the state inbox.
+pub fn frames_from_calldata(calldata: &[u8]) -> Result<Vec<StateFrame>> {
+    framing::decode(calldata)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use ethers::{
+        signers::LocalWallet,
+        types::{Address, U256},
+    };
+    use ports::types::Compression;
+
+    use super::*;
+    use crate::eip_4844::{BlobSidecar, BlobTransaction, BlobTransactionEncoder};
+
+    fn given_frames() -> Vec<StateFrame> {
+        vec![
+            StateFrame {
+                block_hash: [1; 32],
+                block_height: 10,
+                fragment_index: 0,
+                total_fragments: 1,
+                compression: Compression::Zstd,
+                data: vec![7; 200_000],
+            },
+            StateFrame {
+                block_hash: [2; 32],
+                block_height: 11,
+                fragment_index: 0,
+                total_fragments: 2,
+                compression: Compression::None,
+                data: vec![8; 1000],
+            },
+        ]
+    }
+
+    async fn given_blob_tx(sidecar: BlobSidecar) -> Vec<u8> {
+        let wallet = LocalWallet::from_str(
+            "59c6995e998f97a5a0044966f0945389dc9e86dae88c7a8412f4603b6b78690d",
+        )
+        .unwrap();
+
+        let tx = BlobTransaction {
+            to: Address::zero(),
+            chain_id: U256::one(),
+            gas_limit: U256::from(21_000),
+            nonce: U256::zero(),
+            max_fee_per_gas: U256::one(),
+            max_priority_fee_per_gas: U256::one(),
+            max_fee_per_blob_gas: U256::one(),
+            blob_versioned_hashes: sidecar.versioned_hashes(),
+        };
+
+        let (_, raw_tx) = BlobTransactionEncoder::new(tx, sidecar)
+            .raw_signed_w_sidecar(&wallet)
+            .await
+            .unwrap();
+
+        raw_tx
+    }
+
+    #[tokio::test]
+    async fn frames_survive_the_blob_tx_encoding() {
+        // given
+        let frames = given_frames();
+        let sidecar = BlobSidecar::new(framing::encode(&frames).unwrap()).unwrap();
+        let raw_tx = given_blob_tx(sidecar).await;
+
+        // when
+        let decoded = frames_from_blob_tx(&raw_tx).unwrap();
+
+        // then
+        assert_eq!(decoded, frames);
+    }
+
+    #[test]
+    fn rejects_blobs_of_wrong_size() {
+        // given
+        let blobs = vec![vec![0; 100]];
+
+        // when
+        let result = frames_from_blobs(&blobs);
+
+        // then
+        let Err(Error::Other(msg)) = result else {
+            panic!("expected an error, got {result:?}");
+        };
+        assert!(msg.contains("expected blobs of"));
+    }
+}
@@ -0,0 +1,210 @@
+//! Framing of the state fragments carried by blobs.
+//!
+//! A payload starts with a header followed by one frame per fragment:
+//!
+//! ```text
+//! header: magic (4) | version (1) | frame count (u16)
+//! frame:  block hash (32) | block height (u32) | fragment index (u32) | total fragments (u32)
+//!         | compression (1) | data length (u32) | data
+//! ```
+//!
+//! All integers are big endian. Anything after the last frame must be zero padding.
+
+use ports::types::{Compression, StateFrame};
+
+use crate::error::{Error, Result};
+
+pub const MAGIC: [u8; 4] = *b"FBCB";
+pub const VERSION: u8 = 1;
+
+pub fn encoded_len(frames: &[StateFrame]) -> usize {
+    StateFrame::PAYLOAD_HEADER_SIZE + frames.iter().map(StateFrame::encoded_len).sum::<usize>()
+}
+
+pub fn encode(frames: &[StateFrame]) -> Result<Vec<u8>> {
+    let frame_count = u16::try_from(frames.len())
+        .map_err(|_| Error::Other(format!("cannot frame {} fragments", frames.len())))?;
+
+    let mut payload = Vec::with_capacity(encoded_len(frames));
+    payload.extend_from_slice(&MAGIC);
+    payload.push(VERSION);
+    payload.extend_from_slice(&frame_count.to_be_bytes());
+
+    for frame in frames {
+        let data_len = u32::try_from(frame.data.len()).map_err(|_| {
+            Error::Other(format!(
+                "fragment of {} bytes is too large",
+                frame.data.len()
+            ))
+        })?;
+
+        payload.extend_from_slice(&frame.block_hash);
+        payload.extend_from_slice(&frame.block_height.to_be_bytes());
+        payload.extend_from_slice(&frame.fragment_index.to_be_bytes());
+        payload.extend_from_slice(&frame.total_fragments.to_be_bytes());
+        payload.push(frame.compression.id());
+        payload.extend_from_slice(&data_len.to_be_bytes());
+        payload.extend_from_slice(&frame.data);
+    }
+
+    Ok(payload)
+}
+
+pub fn decode(payload: &[u8]) -> Result<Vec<StateFrame>> {
+    let mut reader = Reader { payload };
+
+    if reader.take::<4>()? != MAGIC {
+        return Err(Error::Other(
+            "payload is missing the framing magic".to_string(),
+        ));
+    }
+
+    let version = reader.take::<1>()?[0];
+    if version != VERSION {
+        return Err(Error::Other(format!(
+            "unsupported framing version {version}, expected {VERSION}"
+        )));
+    }
+
+    let frame_count = u16::from_be_bytes(reader.take()?);
+    let frames = (0..frame_count)
+        .map(|_| reader.frame())
+        .collect::<Result<Vec<_>>>()?;
+
+    if reader.payload.iter().any(|byte| *byte != 0) {
+        return Err(Error::Other(
+            "unexpected data after the last frame".to_string(),
+        ));
+    }
+
+    Ok(frames)
+}
+
+struct Reader<'a> {
+    payload: &'a [u8],
+}
+
+impl Reader<'_> {
+    fn frame(&mut self) -> Result<StateFrame> {
+        let block_hash = self.take()?;
+        let block_height = u32::from_be_bytes(self.take()?);
+        let fragment_index = u32::from_be_bytes(self.take()?);
+        let total_fragments = u32::from_be_bytes(self.take()?);
+        let compression =
+            Compression::try_from(self.take::<1>()?[0]).map_err(|e| Error::Other(e.to_string()))?;
+        let data_len = u32::from_be_bytes(self.take()?) as usize;
+        let data = self.take_slice(data_len)?.to_vec();
+
+        Ok(StateFrame {
+            block_hash,
+            block_height,
+            fragment_index,
+            total_fragments,
+            compression,
+            data,
+        })
+    }
+
+    fn take<const N: usize>(&mut self) -> Result<[u8; N]> {
+        let bytes = self.take_slice(N)?;
+        Ok(bytes.try_into().expect("slice has exactly N bytes"))
+    }
+
+    fn take_slice(&mut self, len: usize) -> Result<&[u8]> {
+        if self.payload.len() < len {
+            return Err(Error::Other(format!(
+                "payload truncated: expected {len} more bytes, got {}",
+                self.payload.len()
+            )));
+        }
+
+        let (taken, rest) = self.payload.split_at(len);
+        self.payload = rest;
+
+        Ok(taken)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn given_frames() -> Vec<StateFrame> {
+        vec![
+            StateFrame {
+                block_hash: [1; 32],
+                block_height: 10,
+                fragment_index: 1,
+                total_fragments: 2,
+                compression: Compression::Zstd,
+                data: vec![5; 100],
+            },
+            StateFrame {
+                block_hash: [2; 32],
+                block_height: 11,
+                fragment_index: 0,
+                total_fragments: 1,
+                compression: Compression::None,
+                data: vec![],
+            },
+        ]
+    }
+
+    #[test]
+    fn frames_round_trip() {
+        // given
+        let frames = given_frames();
+
+        // when
+        let payload = encode(&frames).unwrap();
+
+        // then
+        assert_eq!(payload.len(), encoded_len(&frames));
+        assert_eq!(decode(&payload).unwrap(), frames);
+    }
+
+    #[test]
+    fn zero_padding_is_stripped() {
+        // given
+        let frames = given_frames();
+        let mut payload = encode(&frames).unwrap();
+        payload.extend([0; 31]);
+
+        // when
+        let decoded = decode(&payload).unwrap();
+
+        // then
+        assert_eq!(decoded, frames);
+    }
+
+    #[test]
+    fn rejects_unknown_version() {
+        // given
+        let mut payload = encode(&given_frames()).unwrap();
+        payload[4] = VERSION + 1;
+
+        // when
+        let result = decode(&payload);
+
+        // then
+        let Err(Error::Other(msg)) = result else {
+            panic!("expected an error, got {result:?}");
+        };
+        assert!(msg.contains("unsupported framing version"));
+    }
+
+    #[test]
+    fn rejects_truncated_payload() {
+        // given
+        let payload = encode(&given_frames()).unwrap();
+
+        // when
+        let result = decode(&payload[..payload.len() - 1]);
+
+        // then
+        let Err(Error::Other(msg)) = result else {
+            panic!("expected an error, got {result:?}");
+        };
+        assert!(msg.contains("payload truncated"));
+    }
+}
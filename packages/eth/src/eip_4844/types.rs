@@ -31,18 +31,21 @@ fn kzg_settings() -> &'static c_kzg::KzgSettings {
     })
 }
 
-const BLOB_TX_TYPE: u8 = 0x03;
-const MAX_BLOBS_PER_BLOCK: usize = 6;
-pub const MAX_BYTES_PER_BLOB: usize = c_kzg::BYTES_PER_BLOB;
-
-pub trait BlobSigner {
-    fn sign_hash(&self, hash: H256) -> crate::error::Result<Signature>;
+pub(crate) const BLOB_TX_TYPE: u8 = 0x03;
+pub(crate) const MAX_BLOBS_PER_BLOCK: usize = 6;
+const FIELD_ELEMENTS_PER_BLOB: usize = c_kzg::BYTES_PER_BLOB / 32;
+/// Every field element carries 31 bytes of data, see `generate_field_elements`
+pub const USABLE_BYTES_PER_BLOB: usize = FIELD_ELEMENTS_PER_BLOB * 31;
+
+#[async_trait::async_trait]
+pub trait BlobSigner: Sync {
+    async fn sign_blob_tx(&self, tx: &BlobTransaction) -> crate::error::Result<Signature>;
 }
 
+#[async_trait::async_trait]
 impl BlobSigner for ethers::signers::LocalWallet {
-    fn sign_hash(&self, hash: H256) -> crate::error::Result<Signature> {
-        self.sign_hash(hash)
-            .map_err(|e| Error::Other(e.to_string()))
+    async fn sign_blob_tx(&self, tx: &BlobTransaction) -> crate::error::Result<Signature> {
+        Ok(self.sign_hash(tx.signing_hash())?)
     }
 }
 
@@ -59,7 +62,7 @@ pub struct BlobSidecar {
 
 impl BlobSidecar {
     pub fn new(data: Vec<u8>) -> std::result::Result<Self, Error> {
-        let num_blobs = data.len().div_ceil(MAX_BYTES_PER_BLOB);
+        let num_blobs = data.len().div_ceil(USABLE_BYTES_PER_BLOB);
 
         if num_blobs > MAX_BLOBS_PER_BLOCK {
             return Err(Error::Other(format!(
@@ -105,10 +108,9 @@ impl BlobSidecar {
     fn field_elements_to_blobs(field_elements: Vec<[u8; 32]>) -> Vec<c_kzg::Blob> {
         use itertools::Itertools;
 
-        const ELEMENTS_PER_BLOB: usize = c_kzg::BYTES_PER_BLOB / 32;
         field_elements
             .into_iter()
-            .chunks(ELEMENTS_PER_BLOB)
+            .chunks(FIELD_ELEMENTS_PER_BLOB)
             .into_iter()
             .map(|elements| {
                 let mut blob = [0u8; c_kzg::BYTES_PER_BLOB];
@@ -167,6 +169,66 @@ pub struct BlobTransaction {
     pub blob_versioned_hashes: Vec<H256>,
 }
 
+impl BlobTransaction {
+    /// Hash the sender signs, that of the tx without its signature.
+    pub fn signing_hash(&self) -> H256 {
+        H256(keccak256([&[BLOB_TX_TYPE], self.rlp().as_slice()].concat()))
+    }
+
+    fn rlp(&self) -> Vec<u8> {
+        let mut stream = RlpStream::new();
+        // 11 fields: common tx fields, unused fields, blob tx fields
+        stream.begin_list(11);
+
+        self.append_common_tx_fields(&mut stream);
+        Self::append_unused_fields(&mut stream);
+        self.append_blob_tx_fields(&mut stream);
+
+        stream.as_raw().to_vec()
+    }
+
+    pub(crate) fn rlp_signed(&self, signature: Signature) -> Vec<u8> {
+        let mut stream = RlpStream::new();
+        // 14 fields: common tx fields, unused fields, blob tx fields, signature
+        stream.begin_list(14);
+
+        self.append_common_tx_fields(&mut stream);
+        Self::append_unused_fields(&mut stream);
+        self.append_blob_tx_fields(&mut stream);
+
+        self.append_signature(&mut stream, signature);
+
+        stream.as_raw().to_vec()
+    }
+
+    fn append_common_tx_fields(&self, stream: &mut RlpStream) {
+        stream.append(&self.chain_id);
+        stream.append(&self.nonce);
+        stream.append(&self.max_priority_fee_per_gas);
+        stream.append(&self.max_fee_per_gas);
+        stream.append(&self.gas_limit);
+        stream.append(&self.to);
+    }
+
+    fn append_unused_fields(stream: &mut RlpStream) {
+        // value, data and access_list
+        stream.append_empty_data();
+        stream.append_empty_data();
+        stream.begin_list(0);
+    }
+
+    fn append_blob_tx_fields(&self, stream: &mut RlpStream) {
+        stream.append(&self.max_fee_per_blob_gas);
+        stream.append_list(&self.blob_versioned_hashes);
+    }
+
+    fn append_signature(&self, stream: &mut RlpStream, signature: Signature) {
+        stream.append(&signature.v);
+        stream.append(&signature.r);
+        stream.append(&signature.s);
+    }
+}
+
 pub struct BlobTransactionEncoder {
     tx: BlobTransaction,
     sidecar: BlobSidecar,
@@ -177,12 +239,16 @@ impl BlobTransactionEncoder {
         Self { tx, sidecar }
     }
 
-    pub fn raw_signed_w_sidecar(self, signer: &impl BlobSigner) -> (H256, Vec<u8>) {
-        let signed_tx_bytes = self.raw_signed(signer);
+    pub async fn raw_signed_w_sidecar(
+        self,
+        signer: &impl BlobSigner,
+    ) -> crate::error::Result<(H256, Vec<u8>)> {
+        let signature = signer.sign_blob_tx(&self.tx).await?;
+        let signed_tx_bytes = [&[BLOB_TX_TYPE], self.tx.rlp_signed(signature).as_slice()].concat();
         let tx_hash = H256(keccak256(&signed_tx_bytes));
         let final_bytes = self.encode_sidecar(signed_tx_bytes);
 
-        (tx_hash, final_bytes)
+        Ok((tx_hash, final_bytes))
     }
 
     fn encode_sidecar(self, payload: Vec<u8>) -> Vec<u8> {
@@ -213,82 +279,33 @@ impl BlobTransactionEncoder {
 
         tx
     }
+}
 
-    fn raw_signed(&self, signer: &impl BlobSigner) -> Vec<u8> {
-        let tx_bytes = self.encode(None);
-        let signature = self.compute_signature(tx_bytes, signer);
-
-        self.encode(Some(signature))
-    }
-
-    fn compute_signature(&self, tx_bytes: Vec<u8>, signer: &impl BlobSigner) -> Signature {
-        let message_hash = H256::from(keccak256(tx_bytes));
-
-        signer
-            .sign_hash(message_hash)
-            .expect("signing should not fail")
-    }
-
-    fn encode(&self, signature: Option<Signature>) -> Vec<u8> {
-        let tx_bytes = if let Some(signature) = signature {
-            self.rlp_signed(signature)
-        } else {
-            self.rlp()
-        };
-
-        [&[BLOB_TX_TYPE], tx_bytes.as_slice()].concat()
-    }
-
-    fn rlp(&self) -> Vec<u8> {
-        let mut stream = RlpStream::new();
-        // 11 fields: common tx fields, unused fields, blob tx fields
-        stream.begin_list(11);
-
-        self.append_common_tx_fields(&mut stream);
-        Self::append_unused_fields(&mut stream);
-        self.append_blob_tx_fields(&mut stream);
-
-        stream.as_raw().to_vec()
-    }
-
-    fn rlp_signed(&self, signature: Signature) -> Vec<u8> {
-        let mut stream = RlpStream::new();
-        // 14 fields: common tx fields, unused fields, blob tx fields, signature
-        stream.begin_list(14);
-
-        self.append_common_tx_fields(&mut stream);
-        Self::append_unused_fields(&mut stream);
-        self.append_blob_tx_fields(&mut stream);
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-        self.append_signature(&mut stream, signature);
+    #[test]
+    fn data_filling_the_usable_capacity_fits() {
+        // given
+        let data = vec![1; USABLE_BYTES_PER_BLOB * 2];
 
-        stream.as_raw().to_vec()
-    }
+        // when
+        let sidecar = BlobSidecar::new(data).unwrap();
 
-    fn append_common_tx_fields(&self, stream: &mut RlpStream) {
-        stream.append(&self.tx.chain_id);
-        stream.append(&self.tx.nonce);
-        stream.append(&self.tx.max_priority_fee_per_gas);
-        stream.append(&self.tx.max_fee_per_gas);
-        stream.append(&self.tx.gas_limit);
-        stream.append(&self.tx.to);
+        // then
+        assert_eq!(sidecar.num_blobs(), 2);
     }
 
-    fn append_unused_fields(stream: &mut RlpStream) {
-        // value, data and access_list
-        stream.append_empty_data();
-        stream.append_empty_data();
-        stream.begin_list(0);
-    }
+    #[test]
+    fn data_exceeding_the_usable_capacity_is_rejected() {
+        // given
+        let data = vec![1; USABLE_BYTES_PER_BLOB * MAX_BLOBS_PER_BLOCK + 1];
 
-    fn append_blob_tx_fields(&self, stream: &mut RlpStream) {
-        stream.append(&self.tx.max_fee_per_blob_gas);
-        stream.append_list(&self.tx.blob_versioned_hashes);
-    }
+        // when
+        let result = BlobSidecar::new(data);
 
-    fn append_signature(&self, stream: &mut RlpStream, signature: Signature) {
-        stream.append(&signature.v);
-        stream.append(&signature.r);
-        stream.append(&signature.s);
+        // then
+        assert!(result.is_err());
     }
 }
@@ -1,15 +1,19 @@
 use ethers::{
     prelude::{ContractError, SignerMiddleware},
-    providers::{Provider, Ws},
-    signers::LocalWallet,
+    providers::Provider,
 };
 
+use crate::{signer::EthSigner, websocket::Transport};
+
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
     #[error("wallet error: {0}")]
     Wallet(#[from] ethers::signers::WalletError),
     #[error("network error: {0}")]
     Network(String),
+    /// A network error hit while preparing a tx, before it was broadcast.
+    #[error("network error before the tx was sent: {0}")]
+    NetworkBeforeSend(String),
     #[error("other error: {0}")]
     Other(String),
 }
@@ -20,8 +24,14 @@ impl From<ethers::providers::ProviderError> for Error {
     }
 }
 
+impl From<ports::storage::Error> for Error {
+    fn from(err: ports::storage::Error) -> Self {
+        Self::Other(err.to_string())
+    }
+}
+
 pub type ContractErrorType =
-    ethers::contract::ContractError<SignerMiddleware<Provider<Ws>, LocalWallet>>;
+    ethers::contract::ContractError<SignerMiddleware<Provider<Transport>, EthSigner>>;
 
 impl From<ContractErrorType> for Error {
     fn from(value: ContractErrorType) -> Self {
@@ -33,12 +43,33 @@ impl From<ContractErrorType> for Error {
     }
 }
 
+impl Error {
+    /// Marks a network error as having happened before the tx was broadcast, so that the tx can
+    /// safely be sent through another endpoint.
+    pub(crate) fn before_send(self) -> Self {
+        match self {
+            Self::Network(err) => Self::NetworkBeforeSend(err),
+            err => err,
+        }
+    }
+}
+
 pub type Result<T> = std::result::Result<T, Error>;
 
 impl From<Error> for ports::l1::Error {
     fn from(err: Error) -> Self {
         match err {
-            Error::Network(err) => Self::Network(err),
+            Error::Network(err) | Error::NetworkBeforeSend(err) => Self::Network(err),
+            Error::Other(err) => Self::Other(err),
+            Error::Wallet(err) => Self::Other(err.to_string()),
+        }
+    }
+}
+
+impl From<Error> for ports::da::Error {
+    fn from(err: Error) -> Self {
+        match err {
+            Error::Network(err) | Error::NetworkBeforeSend(err) => Self::Network(err),
             Error::Other(err) => Self::Other(err),
             Error::Wallet(err) => Self::Other(err.to_string()),
         }
@@ -8,26 +8,45 @@ use ethers::types::U256;
 use futures::{stream::TryStreamExt, Stream};
 use ports::{
     l1::{Api, Contract, EventStreamer, Result},
-    types::{FuelBlockCommittedOnL1, L1Height, ValidatedFuelBlock},
+    types::{
+        BaseFees, CommitTx, DaSubmission, FuelBlockCommittedOnL1, L1Height, StateFrame,
+        SubmissionStatus, TransactionResponse, TxFees, ValidatedFuelBlock,
+    },
 };
+use state_tx::L1Tx;
 use websocket::EthEventStreamer;
 
 mod eip_4844;
 mod error;
 mod metrics;
+mod signer;
+mod state_tx;
+mod wallet_key;
 mod websocket;
 
+pub use eip_4844::{decoding, framing};
 pub use ethers::types::{Address, Chain};
-pub use websocket::WebsocketClient;
+pub use state_tx::{posting_mode, PostingMode};
+pub use wallet_key::WalletKey;
+pub use websocket::{RpcEndpoint, WebsocketClient};
 
 #[async_trait]
 impl Contract for WebsocketClient {
-    async fn submit(&self, block: ValidatedFuelBlock) -> Result<()> {
+    async fn submit(&self, block: ValidatedFuelBlock) -> Result<CommitTx> {
         self.submit(block).await
     }
 
-    fn event_streamer(&self, height: L1Height) -> Box<dyn EventStreamer + Send + Sync> {
-        Box::new(self.event_streamer(height.into()))
+    async fn replace_commit(
+        &self,
+        block: ValidatedFuelBlock,
+        nonce: u64,
+        min_fees: TxFees,
+    ) -> Result<CommitTx> {
+        self.replace_commit(block, nonce, min_fees).await
+    }
+
+    fn event_streamer(&self, height: L1Height) -> Result<Box<dyn EventStreamer + Send + Sync>> {
+        Ok(Box::new(self.event_streamer(height.into())?))
     }
 
     fn commit_interval(&self) -> NonZeroU32 {
@@ -37,10 +56,6 @@ impl Contract for WebsocketClient {
 
 #[async_trait]
 impl Api for WebsocketClient {
-    async fn submit_l2_state(&self, state_data: Vec<u8>) -> Result<[u8; 32]> {
-        Ok(self.submit_l2_state(state_data).await?)
-    }
-
     async fn balance(&self) -> Result<U256> {
         Ok(self.balance().await?)
     }
@@ -51,6 +66,124 @@ impl Api for WebsocketClient {
 
         Ok(height)
     }
+
+    async fn get_block_hash(&self, height: L1Height) -> Result<Option<[u8; 32]>> {
+        self.get_block_hash(height.into()).await
+    }
+
+    async fn get_transaction_response(
+        &self,
+        tx_hash: [u8; 32],
+    ) -> Result<Option<TransactionResponse>> {
+        self.get_transaction_response(tx_hash).await
+    }
+
+    async fn is_squeezed_out(&self, tx_hash: [u8; 32]) -> Result<bool> {
+        self.is_squeezed_out(tx_hash).await
+    }
+}
+
+/// Blob txs only take the place of their predecessor in the mempool if every one of their fees
+/// doubles.
+const STATE_TX_REPLACEMENT_BUMP_PERCENTAGE: u32 = 100;
+
+#[async_trait]
+impl ports::da::Api for WebsocketClient {
+    async fn height(&self) -> ports::da::Result<L1Height> {
+        Api::get_block_number(self).await.map_err(to_da_error)
+    }
+
+    async fn base_fees(&self) -> ports::da::Result<BaseFees> {
+        self.base_fees().await.map_err(to_da_error)
+    }
+
+    fn max_payload_size(&self) -> usize {
+        state_tx::MAX_PAYLOAD_SIZE
+    }
+
+    fn payload_unit(&self) -> usize {
+        eip_4844::USABLE_BYTES_PER_BLOB
+    }
+
+    fn max_fragment_size(&self) -> usize {
+        state_tx::MAX_FRAGMENT_SIZE
+    }
+
+    async fn submit(&self, frames: Vec<StateFrame>) -> ports::da::Result<(DaSubmission, usize)> {
+        let mode = if self.posts_calldata() {
+            let fees = self.base_fees().await.map_err(to_da_error)?;
+            PostingMode::choose(&fees, &frames, true)
+        } else {
+            PostingMode::Blob
+        };
+
+        let frames = mode.pack(frames);
+        let state_data = framing::encode(&frames)?;
+        let payload_size = state_data.len();
+        let tx = self
+            .submit_l2_state(state_data, mode)
+            .await
+            .map_err(to_da_error)?;
+
+        if mode == PostingMode::Blob {
+            self.observe_blob_fill(payload_size);
+        }
+
+        Ok((tx.into_submission(), frames.len()))
+    }
+
+    fn replacement_fee(&self, submission: &DaSubmission) -> ports::da::Result<u128> {
+        let tx = L1Tx::from_submission(submission)?;
+        let min_fees = tx.fees.bumped(STATE_TX_REPLACEMENT_BUMP_PERCENTAGE);
+
+        Ok(min_fees.max_total_fee(tx.gas_limit, tx.blob_gas))
+    }
+
+    async fn replace(
+        &self,
+        frames: Vec<StateFrame>,
+        submission: &DaSubmission,
+    ) -> ports::da::Result<DaSubmission> {
+        let tx = L1Tx::from_submission(submission)?;
+        let min_fees = tx.fees.bumped(STATE_TX_REPLACEMENT_BUMP_PERCENTAGE);
+
+        let state_data = framing::encode(&frames)?;
+        let replacement = self
+            .replace_l2_state(state_data, tx.nonce, min_fees, tx.mode)
+            .await
+            .map_err(to_da_error)?;
+
+        Ok(replacement.into_submission())
+    }
+
+    async fn confirm(&self, tx_hash: [u8; 32]) -> ports::da::Result<SubmissionStatus> {
+        if let Some(response) = self
+            .get_transaction_response(tx_hash)
+            .await
+            .map_err(to_da_error)?
+        {
+            return Ok(SubmissionStatus::Included(response));
+        }
+
+        if self.is_squeezed_out(tx_hash).await.map_err(to_da_error)? {
+            Ok(SubmissionStatus::Dropped)
+        } else {
+            Ok(SubmissionStatus::Pending)
+        }
+    }
+
+    /// Only state posted as calldata can be retrieved, blobs are pruned from execution nodes and
+    /// have to be fetched from a beacon node.
+    async fn retrieve(&self, tx_hash: [u8; 32]) -> ports::da::Result<Option<Vec<StateFrame>>> {
+        self.get_l2_state(tx_hash).await.map_err(to_da_error)
+    }
+}
+
+fn to_da_error(err: ports::l1::Error) -> ports::da::Error {
+    match err {
+        ports::l1::Error::Network(err) => ports::da::Error::Network(err),
+        ports::l1::Error::Other(err) => ports::da::Error::Other(err),
+    }
 }
 
 #[async_trait::async_trait]
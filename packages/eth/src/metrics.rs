@@ -14,12 +14,16 @@ impl RegistersMetrics for Metrics {
     }
 }
 
-impl Default for Metrics {
-    fn default() -> Self {
-        let eth_network_errors = IntCounter::with_opts(Opts::new(
-            "eth_network_errors",
-            "Number of network errors encountered while running Ethereum RPCs.",
-        ))
+impl Metrics {
+    /// The metrics of every endpoint share a name and are told apart by the `endpoint` label.
+    pub fn new(endpoint: &str) -> Self {
+        let eth_network_errors = IntCounter::with_opts(
+            Opts::new(
+                "eth_network_errors",
+                "Number of network errors encountered while running Ethereum RPCs.",
+            )
+            .const_label("endpoint", endpoint),
+        )
         .expect("eth_network_errors metric to be correctly configured");
 
         Self { eth_network_errors }
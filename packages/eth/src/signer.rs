@@ -0,0 +1,82 @@
+use ethers::{
+    signers::{LocalWallet, Signer},
+    types::{
+        transaction::eip2718::TypedTransaction, transaction::eip712::Eip712, Address, Signature,
+    },
+};
+
+use crate::{
+    eip_4844::{BlobSigner, BlobTransaction},
+    error::{Error, Result},
+};
+
+mod remote;
+
+pub use remote::RemoteSigner;
+
+/// Signs both the block commitments and the blob txs, with a key held either in memory or by a
+/// remote signer.
+#[derive(Debug, Clone)]
+pub enum EthSigner {
+    Local(LocalWallet),
+    Remote(RemoteSigner),
+}
+
+#[async_trait::async_trait]
+impl Signer for EthSigner {
+    type Error = Error;
+
+    async fn sign_message<S: Send + Sync + AsRef<[u8]>>(&self, message: S) -> Result<Signature> {
+        match self {
+            Self::Local(wallet) => Ok(wallet.sign_message(message).await?),
+            Self::Remote(signer) => signer.sign_message(message.as_ref()).await,
+        }
+    }
+
+    async fn sign_transaction(&self, tx: &TypedTransaction) -> Result<Signature> {
+        match self {
+            Self::Local(wallet) => Ok(wallet.sign_transaction(tx).await?),
+            Self::Remote(signer) => signer.sign_transaction(tx).await,
+        }
+    }
+
+    async fn sign_typed_data<T: Eip712 + Send + Sync>(&self, payload: &T) -> Result<Signature> {
+        match self {
+            Self::Local(wallet) => Ok(wallet.sign_typed_data(payload).await?),
+            Self::Remote(_) => Err(Error::Other(
+                "the remote signer doesn't support signing typed data".to_string(),
+            )),
+        }
+    }
+
+    fn address(&self) -> Address {
+        match self {
+            Self::Local(wallet) => wallet.address(),
+            Self::Remote(signer) => signer.address(),
+        }
+    }
+
+    fn chain_id(&self) -> u64 {
+        match self {
+            Self::Local(wallet) => wallet.chain_id(),
+            Self::Remote(signer) => signer.chain_id(),
+        }
+    }
+
+    fn with_chain_id<T: Into<u64>>(self, chain_id: T) -> Self {
+        match self {
+            Self::Local(wallet) => Self::Local(wallet.with_chain_id(chain_id)),
+            Self::Remote(signer) => Self::Remote(signer.with_chain_id(chain_id)),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl BlobSigner for EthSigner {
+    async fn sign_blob_tx(&self, tx: &BlobTransaction) -> Result<Signature> {
+        match self {
+            Self::Local(wallet) => wallet.sign_blob_tx(tx).await,
+            Self::Remote(signer) => signer.sign_blob_tx(tx).await,
+        }
+    }
+}
@@ -0,0 +1,324 @@
+use ethers::{
+    providers::{Http, Middleware, Provider},
+    types::{
+        transaction::eip2718::TypedTransaction, Address, Bytes, RecoveryMessage, Signature, U256,
+    },
+    utils::rlp as ethers_rlp,
+};
+use serde_json::json;
+use url::Url;
+
+use crate::{
+    eip_4844::{BlobTransaction, BLOB_TX_TYPE},
+    error::{Error, Result},
+};
+
+/// Fields of a signed blob tx: 11 tx fields followed by the signature.
+const SIGNED_BLOB_TX_FIELDS: usize = 14;
+
+/// Signs through a remote signer speaking the web3signer JSON-RPC API (`eth_sign`,
+/// `eth_signTransaction`). Every signature is checked against the expected address, so a
+/// misconfigured signer can't get a tx signed by the wrong key sent.
+#[derive(Debug, Clone)]
+pub struct RemoteSigner {
+    provider: Provider<Http>,
+    address: Address,
+    chain_id: u64,
+}
+
+impl RemoteSigner {
+    pub fn new(url: &Url, address: Address) -> Self {
+        Self {
+            provider: Provider::new(Http::new(url.clone())),
+            address,
+            chain_id: 1,
+        }
+    }
+
+    pub(crate) fn address(&self) -> Address {
+        self.address
+    }
+
+    pub(crate) fn chain_id(&self) -> u64 {
+        self.chain_id
+    }
+
+    pub(crate) fn with_chain_id(self, chain_id: impl Into<u64>) -> Self {
+        Self {
+            chain_id: chain_id.into(),
+            ..self
+        }
+    }
+
+    pub(crate) async fn sign_message(&self, message: &[u8]) -> Result<Signature> {
+        let signature = self
+            .provider
+            .sign(Bytes::from(message.to_vec()), &self.address)
+            .await?;
+
+        self.verified(signature, message)
+    }
+
+    pub(crate) async fn sign_transaction(&self, tx: &TypedTransaction) -> Result<Signature> {
+        let mut tx = tx.clone();
+        tx.set_from(self.address);
+        if tx.chain_id().is_none() {
+            tx.set_chain_id(self.chain_id);
+        }
+
+        let raw_tx: Bytes = self.provider.request("eth_signTransaction", [&tx]).await?;
+        let (_, signature) = TypedTransaction::decode_signed(&ethers_rlp::Rlp::new(&raw_tx))
+            .map_err(|e| Error::Other(format!("remote signer returned a malformed tx: {e}")))?;
+
+        self.verified(signature, tx.sighash())
+    }
+
+    pub(crate) async fn sign_blob_tx(&self, tx: &BlobTransaction) -> Result<Signature> {
+        let request = json!({
+            "type": "0x3",
+            "from": self.address,
+            "to": tx.to,
+            "chainId": tx.chain_id,
+            "nonce": tx.nonce,
+            "gas": tx.gas_limit,
+            "value": U256::zero(),
+            "input": Bytes::new(),
+            "maxFeePerGas": tx.max_fee_per_gas,
+            "maxPriorityFeePerGas": tx.max_priority_fee_per_gas,
+            "maxFeePerBlobGas": tx.max_fee_per_blob_gas,
+            "blobVersionedHashes": tx.blob_versioned_hashes,
+        });
+
+        let raw_tx: Bytes = self
+            .provider
+            .request("eth_signTransaction", [request])
+            .await?;
+        let signature = Self::blob_tx_signature(&raw_tx).map_err(|e| {
+            Error::Other(format!("remote signer returned a malformed blob tx: {e}"))
+        })?;
+
+        self.verified(signature, tx.signing_hash())
+    }
+
+    fn blob_tx_signature(raw_tx: &[u8]) -> std::result::Result<Signature, rlp::DecoderError> {
+        let Some((&BLOB_TX_TYPE, payload)) = raw_tx.split_first() else {
+            return Err(rlp::DecoderError::Custom("not a blob tx"));
+        };
+
+        let rlp = rlp::Rlp::new(payload);
+        if rlp.item_count()? != SIGNED_BLOB_TX_FIELDS {
+            return Err(rlp::DecoderError::RlpIncorrectListLen);
+        }
+
+        Ok(Signature {
+            v: rlp.val_at(11)?,
+            r: rlp.val_at(12)?,
+            s: rlp.val_at(13)?,
+        })
+    }
+
+    fn verified(
+        &self,
+        signature: Signature,
+        message: impl Into<RecoveryMessage>,
+    ) -> Result<Signature> {
+        signature.verify(message, self.address).map_err(|e| {
+            Error::Other(format!(
+                "remote signature doesn't match {:?}: {e}",
+                self.address
+            ))
+        })?;
+
+        Ok(signature)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use ethers::{
+        signers::{LocalWallet, Signer},
+        types::{Eip1559TransactionRequest, H256},
+    };
+    use serde_json::{from_value, Value};
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::{TcpListener, TcpStream},
+    };
+
+    use super::*;
+
+    fn given_wallet(key: &str) -> LocalWallet {
+        LocalWallet::from_str(key).unwrap()
+    }
+
+    fn given_signer_wallet() -> LocalWallet {
+        given_wallet("59c6995e998f97a5a0044966f0945389dc9e86dae88c7a8412f4603b6b78690d")
+    }
+
+    /// Serves the signing methods of the web3signer JSON-RPC API, signing with `wallet`.
+    async fn given_mock_signer_server(wallet: LocalWallet) -> Url {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = Url::parse(&format!("http://{}", listener.local_addr().unwrap())).unwrap();
+
+        tokio::spawn(async move {
+            loop {
+                let (stream, _) = listener.accept().await.unwrap();
+                serve_request(stream, &wallet).await;
+            }
+        });
+
+        url
+    }
+
+    async fn serve_request(mut stream: TcpStream, wallet: &LocalWallet) {
+        let mut request = Vec::new();
+        let body_start = loop {
+            let mut buf = [0; 4096];
+            let read = stream.read(&mut buf).await.unwrap();
+            request.extend_from_slice(&buf[..read]);
+
+            if let Some(pos) = request.windows(4).position(|w| w == b"\r\n\r\n") {
+                break pos + 4;
+            }
+        };
+
+        let headers = String::from_utf8_lossy(&request[..body_start]).to_lowercase();
+        let content_length: usize = headers
+            .lines()
+            .find_map(|line| line.strip_prefix("content-length:"))
+            .unwrap()
+            .trim()
+            .parse()
+            .unwrap();
+        while request.len() < body_start + content_length {
+            let mut buf = [0; 4096];
+            let read = stream.read(&mut buf).await.unwrap();
+            request.extend_from_slice(&buf[..read]);
+        }
+
+        let rpc: Value = serde_json::from_slice(&request[body_start..]).unwrap();
+        let result = sign(wallet, rpc["method"].as_str().unwrap(), &rpc["params"]);
+        let body = json!({ "jsonrpc": "2.0", "id": rpc["id"], "result": result }).to_string();
+
+        let response = format!(
+            "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{body}",
+            body.len()
+        );
+        stream.write_all(response.as_bytes()).await.unwrap();
+    }
+
+    fn sign(wallet: &LocalWallet, method: &str, params: &Value) -> Value {
+        match method {
+            "eth_sign" => {
+                let message: Bytes = serde_json::from_value(params[1].clone()).unwrap();
+                let signature = wallet
+                    .sign_hash(ethers::utils::hash_message(message))
+                    .unwrap();
+                json!(signature.to_string())
+            }
+            "eth_signTransaction" if params[0]["type"] == "0x3" => {
+                let tx = BlobTransaction {
+                    to: from_value(params[0]["to"].clone()).unwrap(),
+                    chain_id: from_value(params[0]["chainId"].clone()).unwrap(),
+                    gas_limit: from_value(params[0]["gas"].clone()).unwrap(),
+                    nonce: from_value(params[0]["nonce"].clone()).unwrap(),
+                    max_fee_per_gas: from_value(params[0]["maxFeePerGas"].clone()).unwrap(),
+                    max_priority_fee_per_gas: from_value(params[0]["maxPriorityFeePerGas"].clone())
+                        .unwrap(),
+                    max_fee_per_blob_gas: from_value(params[0]["maxFeePerBlobGas"].clone())
+                        .unwrap(),
+                    blob_versioned_hashes: from_value(params[0]["blobVersionedHashes"].clone())
+                        .unwrap(),
+                };
+                let signature = wallet.sign_hash(tx.signing_hash()).unwrap();
+                let raw_tx = [&[BLOB_TX_TYPE], tx.rlp_signed(signature).as_slice()].concat();
+                json!(Bytes::from(raw_tx))
+            }
+            "eth_signTransaction" => {
+                let tx: TypedTransaction = serde_json::from_value(params[0].clone()).unwrap();
+                let signature = wallet.sign_transaction_sync(&tx).unwrap();
+                json!(tx.rlp_signed(&signature))
+            }
+            _ => panic!("unexpected method {method}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn signs_txs_through_the_remote_signer() {
+        // given
+        let wallet = given_signer_wallet();
+        let url = given_mock_signer_server(wallet.clone()).await;
+        let signer = RemoteSigner::new(&url, wallet.address()).with_chain_id(1u64);
+
+        let tx: TypedTransaction = Eip1559TransactionRequest::new()
+            .to(Address::zero())
+            .nonce(3)
+            .gas(21_000)
+            .max_fee_per_gas(10)
+            .max_priority_fee_per_gas(1)
+            .into();
+
+        // when
+        let signature = signer.sign_transaction(&tx).await.unwrap();
+
+        // then
+        let mut expected_tx = tx.clone();
+        expected_tx.set_from(wallet.address());
+        expected_tx.set_chain_id(1);
+        assert_eq!(
+            signature.recover(expected_tx.sighash()).unwrap(),
+            wallet.address()
+        );
+    }
+
+    #[tokio::test]
+    async fn signs_blob_txs_and_messages_through_the_remote_signer() {
+        // given
+        let wallet = given_signer_wallet();
+        let url = given_mock_signer_server(wallet.clone()).await;
+        let signer = RemoteSigner::new(&url, wallet.address());
+
+        let tx = BlobTransaction {
+            to: wallet.address(),
+            chain_id: U256::one(),
+            gas_limit: U256::from(21_000),
+            nonce: U256::from(5),
+            max_fee_per_gas: U256::from(10),
+            max_priority_fee_per_gas: U256::one(),
+            max_fee_per_blob_gas: U256::from(2),
+            blob_versioned_hashes: vec![H256::repeat_byte(1)],
+        };
+
+        // when
+        let blob_signature = signer.sign_blob_tx(&tx).await.unwrap();
+        let message_signature = signer.sign_message(b"fuel").await.unwrap();
+
+        // then
+        assert_eq!(blob_signature, wallet.sign_hash(tx.signing_hash()).unwrap());
+        assert_eq!(
+            message_signature,
+            wallet.sign_message(b"fuel").await.unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn rejects_signatures_made_with_another_key() {
+        // given
+        let url = given_mock_signer_server(given_signer_wallet()).await;
+        let expected_address =
+            given_wallet("ac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80")
+                .address();
+        let signer = RemoteSigner::new(&url, expected_address);
+
+        // when
+        let result = signer.sign_message(b"fuel").await;
+
+        // then
+        let Err(Error::Other(msg)) = result else {
+            panic!("expected the signature to be rejected, got {result:?}");
+        };
+        assert!(msg.contains("doesn't match"));
+    }
+}
@@ -0,0 +1,274 @@
+use ports::types::{BaseFees, DaSubmission, StateFrame, TxFees};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    eip_4844::{framing, MAX_BLOBS_PER_BLOCK, USABLE_BYTES_PER_BLOB},
+    error::{Error, Result},
+};
+
+/// Largest state payload posted as calldata, leaving room for the rest of the tx below the 128 KiB
+/// nodes accept into their mempool.
+const MAX_CALLDATA_PAYLOAD_SIZE: usize = 120 * 1024;
+/// Bytes of framed state a single blob tx can carry.
+pub(crate) const MAX_PAYLOAD_SIZE: usize = MAX_BLOBS_PER_BLOCK * USABLE_BYTES_PER_BLOB;
+/// Largest fragment that, framed on its own, still fits into a single blob as well as into the
+/// calldata of a tx. Fragments are cut before it is known how they are going to be posted.
+pub(crate) const MAX_FRAGMENT_SIZE: usize = {
+    let max_payload_size = if USABLE_BYTES_PER_BLOB < MAX_CALLDATA_PAYLOAD_SIZE {
+        USABLE_BYTES_PER_BLOB
+    } else {
+        MAX_CALLDATA_PAYLOAD_SIZE
+    };
+    max_payload_size - StateFrame::PAYLOAD_HEADER_SIZE - StateFrame::HEADER_SIZE
+};
+
+/// Gas every tx pays, on top of what its input costs.
+const TX_BASE_GAS: u128 = 21_000;
+const BLOB_GAS_PER_BLOB: u128 = 131_072;
+/// Gas of a non-zero byte of calldata. State is mostly compressed, so zero bytes are rare.
+const CALLDATA_GAS_PER_BYTE: u128 = 16;
+
+/// How the state carried by a state tx was posted, and therefore how it has to be read back.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PostingMode {
+    /// Framed state in the blobs of an EIP-4844 tx.
+    #[default]
+    Blob,
+    /// Framed state as the input of a tx sent to the state inbox.
+    Calldata,
+}
+
+impl PostingMode {
+    /// Blobs, unless calldata is cheaper for `frames` at the current `fees`. Calldata can only be
+    /// used when there is a state inbox to send it to, and not for fragments stored before they
+    /// were sized to fit into a calldata tx.
+    pub(crate) fn choose(fees: &BaseFees, frames: &[StateFrame], calldata_available: bool) -> Self {
+        let fits_into_calldata = frames.first().is_some_and(|frame| {
+            framing::encoded_len(std::slice::from_ref(frame)) <= MAX_CALLDATA_PAYLOAD_SIZE
+        });
+        if !calldata_available || !fits_into_calldata {
+            return Self::Blob;
+        }
+
+        Self::cheapest(fees, framing::encoded_len(frames))
+    }
+
+    /// Blobs, unless the chain has none or calldata is cheaper per byte of state. Calldata
+    /// carries less state per tx, so the comparison is done per byte rather than per tx.
+    fn cheapest(fees: &BaseFees, payload_size: usize) -> Self {
+        let Some(base_fee_per_blob_gas) = fees.base_fee_per_blob_gas else {
+            return Self::Calldata;
+        };
+
+        let num_blobs = payload_size.div_ceil(USABLE_BYTES_PER_BLOB) as u128;
+        let blob_tx_cost = TX_BASE_GAS * fees.base_fee_per_gas
+            + num_blobs * BLOB_GAS_PER_BLOB * base_fee_per_blob_gas;
+
+        let calldata_size = payload_size.min(MAX_CALLDATA_PAYLOAD_SIZE) as u128;
+        let calldata_tx_cost =
+            (TX_BASE_GAS + calldata_size * CALLDATA_GAS_PER_BYTE) * fees.base_fee_per_gas;
+
+        if calldata_tx_cost * (payload_size as u128) < blob_tx_cost * calldata_size {
+            Self::Calldata
+        } else {
+            Self::Blob
+        }
+    }
+
+    /// The oldest frames that, framed together, fit into a tx of this mode.
+    pub(crate) fn pack(self, mut frames: Vec<StateFrame>) -> Vec<StateFrame> {
+        if self == Self::Calldata {
+            while framing::encoded_len(&frames) > MAX_CALLDATA_PAYLOAD_SIZE {
+                frames.pop();
+            }
+        }
+
+        frames
+    }
+}
+
+/// A state tx as sent to L1.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct L1Tx {
+    pub hash: [u8; 32],
+    pub nonce: u64,
+    pub fees: TxFees,
+    pub gas_limit: u64,
+    pub blob_gas: u64,
+    pub mode: PostingMode,
+}
+
+impl L1Tx {
+    pub fn max_total_fee(&self) -> u128 {
+        self.fees.max_total_fee(self.gas_limit, self.blob_gas)
+    }
+
+    pub(crate) fn from_submission(submission: &DaSubmission) -> Result<Self> {
+        let details: Details = serde_json::from_slice(&submission.details)
+            .map_err(|e| Error::Other(format!("invalid state tx details: {e}")))?;
+
+        Ok(Self {
+            hash: submission.hash,
+            nonce: details.nonce,
+            fees: TxFees {
+                max_fee_per_gas: details.max_fee_per_gas,
+                max_priority_fee_per_gas: details.max_priority_fee_per_gas,
+                max_fee_per_blob_gas: details.max_fee_per_blob_gas,
+            },
+            gas_limit: details.gas_limit,
+            blob_gas: details.blob_gas,
+            mode: details.mode,
+        })
+    }
+
+    pub(crate) fn into_submission(self) -> DaSubmission {
+        let details = Details {
+            nonce: self.nonce,
+            max_fee_per_gas: self.fees.max_fee_per_gas,
+            max_priority_fee_per_gas: self.fees.max_priority_fee_per_gas,
+            max_fee_per_blob_gas: self.fees.max_fee_per_blob_gas,
+            gas_limit: self.gas_limit,
+            blob_gas: self.blob_gas,
+            mode: self.mode,
+        };
+
+        DaSubmission {
+            hash: self.hash,
+            details: serde_json::to_vec(&details).expect("details to serialize"),
+        }
+    }
+}
+
+/// How the posting mode of a state tx is read back from what the committer stored about it.
+pub fn posting_mode(submission: &DaSubmission) -> ports::l1::Result<PostingMode> {
+    Ok(L1Tx::from_submission(submission)?.mode)
+}
+
+/// Layout of `DaSubmission::details` for state txs. Migrations of the committer database build
+/// it too, keep them in sync.
+#[derive(Serialize, Deserialize)]
+struct Details {
+    nonce: u64,
+    max_fee_per_gas: u128,
+    max_priority_fee_per_gas: u128,
+    max_fee_per_blob_gas: u128,
+    gas_limit: u64,
+    blob_gas: u64,
+    mode: PostingMode,
+}
+
+#[cfg(test)]
+mod tests {
+    use ports::types::Compression;
+
+    use super::*;
+
+    fn given_frames(frame_size: usize, num_frames: u32) -> Vec<StateFrame> {
+        (0..num_frames)
+            .map(|fragment_index| StateFrame {
+                block_hash: [0; 32],
+                block_height: 1,
+                fragment_index,
+                total_fragments: num_frames,
+                compression: Compression::None,
+                data: vec![1; frame_size],
+            })
+            .collect()
+    }
+
+    #[test]
+    fn posts_as_calldata_when_blob_gas_is_more_expensive() {
+        // given
+        let fees = BaseFees {
+            base_fee_per_gas: 1,
+            base_fee_per_blob_gas: Some(1_000),
+        };
+
+        // when
+        let mode = PostingMode::cheapest(&fees, 100);
+
+        // then
+        assert_eq!(mode, PostingMode::Calldata);
+    }
+
+    #[test]
+    fn posts_in_blobs_when_they_are_cheaper() {
+        // given
+        let fees = BaseFees {
+            base_fee_per_gas: 1_000,
+            base_fee_per_blob_gas: Some(1),
+        };
+
+        // when
+        let mode = PostingMode::cheapest(&fees, 100);
+
+        // then
+        assert_eq!(mode, PostingMode::Blob);
+    }
+
+    #[test]
+    fn posts_as_calldata_on_chains_without_blobs() {
+        // given
+        let fees = BaseFees {
+            base_fee_per_gas: 1_000,
+            base_fee_per_blob_gas: None,
+        };
+
+        // when
+        let mode = PostingMode::cheapest(&fees, 100);
+
+        // then
+        assert_eq!(mode, PostingMode::Calldata);
+    }
+
+    #[test]
+    fn posts_in_blobs_without_a_state_inbox() {
+        // given
+        let fees = BaseFees {
+            base_fee_per_gas: 1_000,
+            base_fee_per_blob_gas: None,
+        };
+
+        // when
+        let mode = PostingMode::choose(&fees, &given_frames(100, 1), false);
+
+        // then
+        assert_eq!(mode, PostingMode::Blob);
+    }
+
+    #[test]
+    fn calldata_carries_the_largest_fragments_one_at_a_time() {
+        // given
+        let frames = given_frames(MAX_FRAGMENT_SIZE, 2);
+
+        // when
+        let packed = PostingMode::Calldata.pack(frames.clone());
+
+        // then
+        assert_eq!(packed, frames[..1]);
+    }
+
+    #[test]
+    fn details_survive_the_round_trip() {
+        // given
+        let tx = L1Tx {
+            hash: [1; 32],
+            nonce: 7,
+            fees: TxFees {
+                max_fee_per_gas: u128::MAX,
+                max_priority_fee_per_gas: 2,
+                max_fee_per_blob_gas: 3,
+            },
+            gas_limit: 21_000,
+            blob_gas: 131_072,
+            mode: PostingMode::Calldata,
+        };
+
+        // when
+        let read_back = L1Tx::from_submission(&tx.into_submission()).unwrap();
+
+        // then
+        assert_eq!(read_back, tx);
+    }
+}
@@ -0,0 +1,115 @@
+use std::{path::PathBuf, str::FromStr};
+
+use ethers::{signers::LocalWallet, types::Address};
+use url::Url;
+
+use crate::{
+    error::{Error, Result},
+    signer::{EthSigner, RemoteSigner},
+};
+
+/// Secret key of a wallet, either given directly, stored in an Ethereum V3 keystore file or held
+/// by a remote signer.
+#[derive(Clone, PartialEq, Eq)]
+pub enum WalletKey {
+    /// Hex encoded secret key.
+    Raw(String),
+    Keystore {
+        path: PathBuf,
+        password: String,
+    },
+    /// Key of `address`, held by a signer speaking the web3signer JSON-RPC API at `url`.
+    Remote {
+        url: Url,
+        address: Address,
+    },
+}
+
+impl WalletKey {
+    pub(crate) fn signer(&self) -> Result<EthSigner> {
+        match self {
+            Self::Remote { url, address } => {
+                Ok(EthSigner::Remote(RemoteSigner::new(url, *address)))
+            }
+            _ => self.wallet().map(EthSigner::Local),
+        }
+    }
+
+    fn wallet(&self) -> Result<LocalWallet> {
+        match self {
+            Self::Raw(key) => Ok(LocalWallet::from_str(key)?),
+            Self::Keystore { path, password } => LocalWallet::decrypt_keystore(path, password)
+                .map_err(|e| {
+                    Error::Other(format!(
+                        "failed to decrypt keystore {}: {e}",
+                        path.display()
+                    ))
+                }),
+            Self::Remote { address, .. } => Err(Error::Other(format!(
+                "key of {address:?} is held by a remote signer"
+            ))),
+        }
+    }
+}
+
+// keeps the secrets out of logs
+impl std::fmt::Debug for WalletKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Raw(_) => f.write_str("Raw(<redacted>)"),
+            Self::Keystore { path, .. } => f
+                .debug_struct("Keystore")
+                .field("path", path)
+                .field("password", &"<redacted>")
+                .finish(),
+            Self::Remote { url, address } => f
+                .debug_struct("Remote")
+                .field("url", url)
+                .field("address", address)
+                .finish(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ethers::{core::rand::thread_rng, signers::Signer};
+
+    use super::*;
+
+    #[test]
+    fn loads_wallet_from_keystore() {
+        // given
+        let dir = tempfile::tempdir().unwrap();
+        let (wallet, file_name) =
+            LocalWallet::new_keystore(dir.path(), &mut thread_rng(), "secret", None).unwrap();
+        let key = WalletKey::Keystore {
+            path: dir.path().join(file_name),
+            password: "secret".to_string(),
+        };
+
+        // when
+        let loaded = key.wallet().unwrap();
+
+        // then
+        assert_eq!(loaded.address(), wallet.address());
+    }
+
+    #[test]
+    fn debug_output_hides_the_secrets() {
+        // given
+        let raw = WalletKey::Raw("0xdeadbeef".to_string());
+        let keystore = WalletKey::Keystore {
+            path: PathBuf::from("/keys/wallet.json"),
+            password: "secret".to_string(),
+        };
+
+        // when
+        let output = format!("{raw:?} {keystore:?}");
+
+        // then
+        assert!(!output.contains("deadbeef"));
+        assert!(!output.contains("secret"));
+        assert!(output.contains("/keys/wallet.json"));
+    }
+}
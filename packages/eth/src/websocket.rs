@@ -1,63 +1,167 @@
-use ::metrics::{prometheus::core::Collector, HealthChecker, RegistersMetrics};
+use ::metrics::{
+    prometheus::{core::Collector, Histogram, HistogramOpts},
+    HealthChecker, RegistersMetrics,
+};
 use ethers::types::{Address, Chain};
+use futures::FutureExt;
 use ports::{
     l1::Result,
-    types::{ValidatedFuelBlock, U256},
+    storage::Storage,
+    types::{
+        BaseFees, CommitTx, StateFrame, TransactionResponse, TxFees, ValidatedFuelBlock, U256,
+    },
+};
+use std::{num::NonZeroU32, sync::Arc};
+use tracing::warn;
+
+use crate::{
+    eip_4844::USABLE_BYTES_PER_BLOB,
+    state_tx::{L1Tx, PostingMode},
+    WalletKey,
 };
-use std::num::NonZeroU32;
-use url::Url;
 
-pub use self::event_streamer::EthEventStreamer;
 use self::{
     connection::WsConnection,
+    failover::{endpoint_names, Failover},
     health_tracking_middleware::{EthApi, HealthTrackingMiddleware},
+    lazy_connection::LazyConnection,
 };
+pub use self::{event_streamer::EthEventStreamer, failover::RpcEndpoint};
 
 mod connection;
 mod event_streamer;
+mod failover;
 mod health_tracking_middleware;
+mod lazy_connection;
+mod nonce_manager;
+mod transport;
+
+pub(crate) use transport::Transport;
 
 #[derive(Clone)]
 pub struct WebsocketClient {
-    inner: HealthTrackingMiddleware<WsConnection>,
+    inner: Failover<HealthTrackingMiddleware<LazyConnection<WsConnection>>>,
+    posts_calldata: bool,
+    blob_tx_fill_ratio: Histogram,
 }
 
 impl WebsocketClient {
+    /// Endpoints that can't be reached are connected to once they are needed, as long as at least
+    /// one of them connects right away.
+    #[allow(clippy::too_many_arguments)]
     pub async fn connect(
-        url: &Url,
+        endpoints: &[RpcEndpoint],
         chain_id: Chain,
         contract_address: Address,
-        wallet_key: &str,
-        blob_pool_wallet_key: Option<String>,
+        wallet_key: &WalletKey,
+        blob_pool_wallet_key: Option<&WalletKey>,
+        state_inbox: Option<Address>,
+        nonce_storage: Option<Arc<dyn Storage>>,
         unhealthy_after_n_errors: usize,
     ) -> ports::l1::Result<Self> {
-        let provider = WsConnection::connect(
-            url,
-            chain_id,
-            contract_address,
-            wallet_key,
-            blob_pool_wallet_key,
+        let mut connections = vec![];
+        let mut last_error = None;
+        for (endpoint, name) in endpoints.iter().zip(endpoint_names(endpoints)) {
+            let connection = LazyConnection::new({
+                let url = endpoint.url.clone();
+                let wallet_key = wallet_key.clone();
+                let blob_pool_wallet_key = blob_pool_wallet_key.cloned();
+                let nonce_storage = nonce_storage.clone();
+                move || {
+                    let url = url.clone();
+                    let wallet_key = wallet_key.clone();
+                    let blob_pool_wallet_key = blob_pool_wallet_key.clone();
+                    let nonce_storage = nonce_storage.clone();
+                    async move {
+                        WsConnection::connect(
+                            &url,
+                            chain_id,
+                            contract_address,
+                            &wallet_key,
+                            blob_pool_wallet_key.as_ref(),
+                            state_inbox,
+                            nonce_storage,
+                        )
+                        .await
+                    }
+                    .boxed()
+                }
+            });
+            let api = HealthTrackingMiddleware::new(connection, &name, unhealthy_after_n_errors);
+
+            if let Err(err) = api.connect().await {
+                warn!("failed to connect to l1 endpoint {name}, will retry once needed: {err}");
+                last_error = Some(err);
+            }
+            connections.push((name, endpoint.priority, api));
+        }
+
+        if let (false, Some(err)) = (
+            connections.iter().any(|(_, _, api)| api.is_connected()),
+            last_error,
+        ) {
+            return Err(err.into());
+        }
+
+        let blob_tx_fill_ratio = Histogram::with_opts(
+            HistogramOpts::new(
+                "blob_tx_fill_ratio",
+                "Share of the blob space of a state tx that is taken up by state.",
+            )
+            .buckets(vec![0.1, 0.2, 0.3, 0.4, 0.5, 0.6, 0.7, 0.8, 0.9, 1.0]),
         )
-        .await?;
+        .expect("blob_tx_fill_ratio metric to be correctly configured");
 
         Ok(Self {
-            inner: HealthTrackingMiddleware::new(provider, unhealthy_after_n_errors),
+            inner: Failover::new(connections)?,
+            posts_calldata: state_inbox.is_some(),
+            blob_tx_fill_ratio,
         })
     }
 
+    /// Whether state may be sent to the state inbox as calldata when that is cheaper than blobs.
+    pub(crate) fn posts_calldata(&self) -> bool {
+        self.posts_calldata
+    }
+
+    pub(crate) fn observe_blob_fill(&self, payload_size: usize) {
+        let num_blobs = payload_size.div_ceil(USABLE_BYTES_PER_BLOB);
+        self.blob_tx_fill_ratio
+            .observe(payload_size as f64 / (num_blobs * USABLE_BYTES_PER_BLOB) as f64);
+    }
+
+    /// Health of each endpoint, by name.
     #[must_use]
-    pub fn connection_health_checker(&self) -> HealthChecker {
-        self.inner.connection_health_checker()
+    pub fn connection_health_checkers(&self) -> Vec<(String, HealthChecker)> {
+        self.inner
+            .endpoints()
+            .iter()
+            .map(|endpoint| {
+                (
+                    endpoint.name.clone(),
+                    endpoint.api.connection_health_checker(),
+                )
+            })
+            .collect()
     }
 
-    pub(crate) fn event_streamer(&self, eth_block_height: u64) -> EthEventStreamer {
-        self.inner.event_streamer(eth_block_height)
+    pub(crate) fn event_streamer(&self, eth_block_height: u64) -> Result<EthEventStreamer> {
+        Ok(self.inner.event_streamer(eth_block_height)?)
     }
 
-    pub(crate) async fn submit(&self, block: ValidatedFuelBlock) -> Result<()> {
+    pub(crate) async fn submit(&self, block: ValidatedFuelBlock) -> Result<CommitTx> {
         Ok(self.inner.submit(block).await?)
     }
 
+    pub(crate) async fn replace_commit(
+        &self,
+        block: ValidatedFuelBlock,
+        nonce: u64,
+        min_fees: TxFees,
+    ) -> Result<CommitTx> {
+        Ok(self.inner.replace_commit(block, nonce, min_fees).await?)
+    }
+
     pub(crate) fn commit_interval(&self) -> NonZeroU32 {
         self.inner.commit_interval()
     }
@@ -66,12 +170,48 @@ impl WebsocketClient {
         Ok(self.inner.get_block_number().await?)
     }
 
+    pub(crate) async fn get_block_hash(&self, height: u64) -> Result<Option<[u8; 32]>> {
+        Ok(self.inner.get_block_hash(height).await?)
+    }
+
     pub(crate) async fn balance(&self) -> Result<U256> {
         Ok(self.inner.balance().await?)
     }
 
-    pub async fn submit_l2_state(&self, tx: Vec<u8>) -> Result<[u8; 32]> {
-        Ok(self.inner.submit_l2_state(tx).await?)
+    pub(crate) async fn base_fees(&self) -> Result<BaseFees> {
+        Ok(self.inner.base_fees().await?)
+    }
+
+    pub async fn submit_l2_state(&self, tx: Vec<u8>, mode: PostingMode) -> Result<L1Tx> {
+        Ok(self.inner.submit_l2_state(tx, mode).await?)
+    }
+
+    pub(crate) async fn replace_l2_state(
+        &self,
+        state_data: Vec<u8>,
+        nonce: u64,
+        min_fees: TxFees,
+        mode: PostingMode,
+    ) -> Result<L1Tx> {
+        Ok(self
+            .inner
+            .replace_l2_state(state_data, nonce, min_fees, mode)
+            .await?)
+    }
+
+    pub(crate) async fn get_transaction_response(
+        &self,
+        tx_hash: [u8; 32],
+    ) -> Result<Option<TransactionResponse>> {
+        Ok(self.inner.get_transaction_response(tx_hash).await?)
+    }
+
+    pub(crate) async fn is_squeezed_out(&self, tx_hash: [u8; 32]) -> Result<bool> {
+        Ok(self.inner.is_squeezed_out(tx_hash).await?)
+    }
+
+    pub(crate) async fn get_l2_state(&self, tx_hash: [u8; 32]) -> Result<Option<Vec<StateFrame>>> {
+        Ok(self.inner.get_l2_state(tx_hash).await?)
     }
 
     #[cfg(feature = "test-helpers")]
@@ -91,6 +231,14 @@ impl WebsocketClient {
 // User responsible for registering any metrics T might have
 impl RegistersMetrics for WebsocketClient {
     fn metrics(&self) -> Vec<Box<dyn Collector>> {
-        self.inner.metrics()
+        let mut metrics: Vec<Box<dyn Collector>> = self
+            .inner
+            .endpoints()
+            .iter()
+            .flat_map(|endpoint| endpoint.api.metrics())
+            .collect();
+        metrics.push(Box::new(self.blob_tx_fill_ratio.clone()));
+
+        metrics
     }
 }
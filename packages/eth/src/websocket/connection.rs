@@ -1,19 +1,35 @@
-use std::{num::NonZeroU32, str::FromStr, sync::Arc};
+use std::{num::NonZeroU32, ops::Range, sync::Arc};
 
 use ethers::{
-    prelude::{abigen, SignerMiddleware},
-    providers::{Middleware, Provider, Ws},
-    signers::{LocalWallet, Signer as _},
-    types::{Address, BlockNumber, Chain, H160, H256, U256, U64},
+    core::utils::keccak256,
+    prelude::{abigen, ContractCall, SignerMiddleware},
+    providers::{Middleware, Provider},
+    signers::Signer as _,
+    types::{
+        transaction::eip2718::TypedTransaction, Address, BlockNumber, Chain,
+        Eip1559TransactionRequest, TransactionReceipt, H160, H256, U256, U64,
+    },
+};
+use ports::{
+    storage::Storage,
+    types::{BaseFees, CommitTx, StateFrame, TransactionResponse, TxFees, ValidatedFuelBlock},
 };
-use ports::types::ValidatedFuelBlock;
 use serde_json::Value;
 use url::Url;
 
-use super::{event_streamer::EthEventStreamer, health_tracking_middleware::EthApi};
+use super::{
+    event_streamer::EthEventStreamer, health_tracking_middleware::EthApi,
+    nonce_manager::NonceManager, transport::Transport,
+};
 use crate::{
-    eip_4844::{calculate_blob_fee, BlobSidecar, BlobTransaction, BlobTransactionEncoder},
+    eip_4844::{
+        decoding, get_base_fee_per_blob_gas, get_total_blob_gas, BlobSidecar, BlobTransaction,
+        BlobTransactionEncoder, BLOB_TX_TYPE,
+    },
     error::{Error, Result},
+    signer::EthSigner,
+    state_tx::{L1Tx, PostingMode},
+    WalletKey,
 };
 
 const STANDARD_GAS_LIMIT: u64 = 21000;
@@ -29,25 +45,51 @@ abigen!(
     ]"#,
 );
 
+type Signer = SignerMiddleware<Provider<Transport>, EthSigner>;
+
 #[derive(Clone)]
 pub struct WsConnection {
-    provider: Provider<Ws>,
-    blob_pool_wallet: Option<LocalWallet>,
-    contract: FUEL_STATE_CONTRACT<SignerMiddleware<Provider<Ws>, LocalWallet>>,
+    provider: Provider<Transport>,
+    blob_pool_wallet: Option<EthSigner>,
+    state_inbox: Option<Address>,
+    contract: FUEL_STATE_CONTRACT<Signer>,
     commit_interval: NonZeroU32,
     address: H160,
+    commit_nonces: Option<NonceManager>,
+    blob_pool_nonces: Option<NonceManager>,
 }
 
 #[async_trait::async_trait]
 impl EthApi for WsConnection {
-    async fn submit(&self, block: ValidatedFuelBlock) -> Result<()> {
-        let commit_height = Self::calculate_commit_height(block.height(), self.commit_interval);
-        let contract_call = self.contract.commit(block.hash(), commit_height);
-        let tx = contract_call.send().await?;
+    async fn connect(&self) -> Result<()> {
+        Ok(())
+    }
 
-        tracing::info!("tx: {} submitted", tx.tx_hash());
+    fn is_connected(&self) -> bool {
+        true
+    }
 
-        Ok(())
+    async fn submit(&self, block: ValidatedFuelBlock) -> Result<CommitTx> {
+        let nonce = self
+            .next_nonce(self.commit_nonces.as_ref(), self.contract.client_ref())
+            .await
+            .map_err(Error::before_send)?;
+
+        let sent = self.send_commit(block, nonce, None).await;
+        if sent.is_err() && self.commit_nonces.is_some() {
+            Self::fill_nonce_gaps(self.contract.client_ref(), nonce..nonce + 1).await;
+        }
+
+        sent
+    }
+
+    async fn replace_commit(
+        &self,
+        block: ValidatedFuelBlock,
+        nonce: u64,
+        min_fees: TxFees,
+    ) -> Result<CommitTx> {
+        self.send_commit(block, nonce, Some(min_fees)).await
     }
 
     async fn get_block_number(&self) -> Result<u64> {
@@ -62,46 +104,97 @@ impl EthApi for WsConnection {
         Ok(response.as_u64())
     }
 
+    async fn get_block_hash(&self, height: u64) -> Result<Option<[u8; 32]>> {
+        Ok(self
+            .provider
+            .get_block(height)
+            .await?
+            .and_then(|block| block.hash)
+            .map(|hash| hash.to_fixed_bytes()))
+    }
+
+    async fn base_fees(&self) -> Result<BaseFees> {
+        let latest = self
+            .provider
+            .get_block(BlockNumber::Latest)
+            .await?
+            .ok_or_else(|| Error::Other("latest block not found".to_string()))?;
+
+        let base_fee_per_gas = latest
+            .base_fee_per_gas
+            .ok_or_else(|| Error::Other("latest block has no base fee".to_string()))?;
+        // blocks of chains without blobs carry no excess blob gas
+        let base_fee_per_blob_gas = latest
+            .excess_blob_gas
+            .map(|excess_blob_gas| to_u128(get_base_fee_per_blob_gas(excess_blob_gas)))
+            .transpose()?;
+
+        Ok(BaseFees {
+            base_fee_per_gas: to_u128(base_fee_per_gas)?,
+            base_fee_per_blob_gas,
+        })
+    }
+
     async fn balance(&self) -> Result<U256> {
         let address = self.address;
         Ok(self.provider.get_balance(address, None).await?)
     }
 
+    async fn get_transaction_response(
+        &self,
+        tx_hash: [u8; 32],
+    ) -> Result<Option<TransactionResponse>> {
+        let tx_receipt = self.provider.get_transaction_receipt(tx_hash).await?;
+
+        Self::convert_to_tx_response(tx_receipt)
+    }
+
+    async fn is_squeezed_out(&self, tx_hash: [u8; 32]) -> Result<bool> {
+        Ok(self.provider.get_transaction(tx_hash).await?.is_none())
+    }
+
+    async fn get_l2_state(&self, tx_hash: [u8; 32]) -> Result<Option<Vec<StateFrame>>> {
+        let Some(tx) = self.provider.get_transaction(tx_hash).await? else {
+            return Ok(None);
+        };
+
+        if tx.transaction_type == Some(U64::from(BLOB_TX_TYPE)) {
+            return Err(Error::Other(format!(
+                "blobs of tx {:?} can only be retrieved from a beacon node",
+                H256::from(tx_hash)
+            )));
+        }
+
+        decoding::frames_from_calldata(&tx.input).map(Some)
+    }
+
     fn commit_interval(&self) -> NonZeroU32 {
         self.commit_interval
     }
 
-    fn event_streamer(&self, eth_block_height: u64) -> EthEventStreamer {
-        let events = self
+    fn event_streamer(&self, eth_block_height: u64) -> Result<EthEventStreamer> {
+        let filter = self
             .contract
             .event::<CommitSubmittedFilter>()
-            .from_block(eth_block_height);
+            .from_block(eth_block_height)
+            .filter;
 
-        EthEventStreamer::new(events)
+        Ok(EthEventStreamer::new(filter, self.provider.clone()))
     }
 
-    async fn submit_l2_state(&self, state_data: Vec<u8>) -> Result<[u8; 32]> {
-        let blob_pool_wallet = if let Some(blob_pool_wallet) = &self.blob_pool_wallet {
-            blob_pool_wallet
-        } else {
-            return Err(Error::Other("blob pool wallet not configured".to_string()));
-        };
-
-        let sidecar = BlobSidecar::new(state_data).map_err(|e| Error::Other(e.to_string()))?;
-        let blob_tx = self
-            .prepare_blob_tx(
-                sidecar.versioned_hashes(),
-                blob_pool_wallet.address(),
-                blob_pool_wallet.chain_id(),
-            )
-            .await?;
-
-        let tx_encoder = BlobTransactionEncoder::new(blob_tx, sidecar);
-        let (tx_hash, raw_tx) = tx_encoder.raw_signed_w_sidecar(blob_pool_wallet);
-
-        self.provider.send_raw_transaction(raw_tx.into()).await?;
+    async fn submit_l2_state(&self, state_data: Vec<u8>, mode: PostingMode) -> Result<L1Tx> {
+        self.send_l2_state(state_data, mode, None).await
+    }
 
-        Ok(tx_hash.to_fixed_bytes())
+    async fn replace_l2_state(
+        &self,
+        state_data: Vec<u8>,
+        nonce: u64,
+        min_fees: TxFees,
+        mode: PostingMode,
+    ) -> Result<L1Tx> {
+        self.send_l2_state(state_data, mode, Some((nonce, min_fees)))
+            .await
     }
 
     #[cfg(feature = "test-helpers")]
@@ -128,16 +221,18 @@ impl WsConnection {
         url: &Url,
         chain_id: Chain,
         contract_address: Address,
-        wallet_key: &str,
-        blob_pool_wallet_key: Option<String>,
+        wallet_key: &WalletKey,
+        blob_pool_wallet_key: Option<&WalletKey>,
+        state_inbox: Option<Address>,
+        nonce_storage: Option<Arc<dyn Storage>>,
     ) -> Result<Self> {
-        let provider = Provider::<Ws>::connect(url.to_string()).await?;
+        let provider = Provider::new(Transport::connect(url).await?);
 
-        let wallet = LocalWallet::from_str(wallet_key)?.with_chain_id(chain_id);
+        let wallet = wallet_key.signer()?.with_chain_id(chain_id);
         let address = wallet.address();
 
         let blob_pool_wallet = blob_pool_wallet_key
-            .map(|key| LocalWallet::from_str(&key))
+            .map(WalletKey::signer)
             .transpose()?
             .map(|wallet| wallet.with_chain_id(chain_id));
 
@@ -156,15 +251,147 @@ impl WsConnection {
                 })
             })?;
 
-        Ok(Self {
+        let commit_nonces = nonce_storage
+            .clone()
+            .map(|storage| NonceManager::new(storage, address));
+        let blob_pool_nonces = nonce_storage
+            .zip(blob_pool_wallet.as_ref())
+            .map(|(storage, wallet)| NonceManager::new(storage, wallet.address()));
+
+        let connection = Self {
             provider,
             contract,
             commit_interval,
             address,
             blob_pool_wallet,
+            state_inbox,
+            commit_nonces,
+            blob_pool_nonces,
+        };
+        connection.reconcile_nonces().await?;
+
+        Ok(connection)
+    }
+
+    // Catches the stored nonces up with the node and fills any nonce that was reserved before a
+    // restart but never sent, otherwise every tx after it would be stuck.
+    async fn reconcile_nonces(&self) -> Result<()> {
+        if let Some(nonces) = &self.commit_nonces {
+            let gaps = self.reconcile(nonces, self.address).await?;
+            Self::fill_nonce_gaps(self.contract.client_ref(), gaps).await;
+        }
+
+        if let (Some(nonces), Some(wallet)) = (&self.blob_pool_nonces, &self.blob_pool_wallet) {
+            let gaps = self.reconcile(nonces, wallet.address()).await?;
+            Self::fill_nonce_gaps(&self.signer(wallet), gaps).await;
+        }
+
+        Ok(())
+    }
+
+    async fn reconcile(&self, nonces: &NonceManager, address: H160) -> Result<Range<u64>> {
+        let latest_count = self.provider.get_transaction_count(address, None).await?;
+        let pending_count = self.pending_tx_count(address).await?;
+
+        nonces
+            .reconcile(latest_count.as_u64(), pending_count.as_u64())
+            .await
+    }
+
+    // A no-op self transfer for every missing nonce. Failures are only logged, the gaps are
+    // detected again before the next tx of the wallet is sent.
+    async fn fill_nonce_gaps(signer: &Signer, gaps: Range<u64>) {
+        let address = signer.address();
+        for nonce in gaps {
+            let tx = Eip1559TransactionRequest::new()
+                .to(address)
+                .value(0)
+                .nonce(nonce);
+
+            match signer.send_transaction(tx, None).await {
+                Ok(tx) => tracing::info!(
+                    "filled nonce {nonce} of {address:?} with tx {}",
+                    tx.tx_hash()
+                ),
+                Err(err) => tracing::warn!("failed to fill nonce {nonce} of {address:?}: {err}"),
+            }
+        }
+    }
+
+    async fn pending_tx_count(&self, address: H160) -> Result<U256> {
+        Ok(self
+            .provider
+            .get_transaction_count(address, Some(BlockNumber::Pending.into()))
+            .await?)
+    }
+
+    // Gaps left by earlier sends that never reached the node are filled first, otherwise the new
+    // tx would be stuck behind them. The txs of a wallet are sent one at a time, so a reserved
+    // nonce the node doesn't know about can't belong to a send still in flight.
+    async fn next_nonce(&self, nonces: Option<&NonceManager>, signer: &Signer) -> Result<u64> {
+        let address = signer.address();
+        let Some(nonces) = nonces else {
+            return Ok(self.pending_tx_count(address).await?.as_u64());
+        };
+
+        let gaps = self.reconcile(nonces, address).await?;
+        Self::fill_nonce_gaps(signer, gaps).await;
+
+        let pending_count = self.pending_tx_count(address).await?.as_u64();
+        nonces.reserve(pending_count).await
+    }
+
+    fn signer(&self, wallet: &EthSigner) -> Signer {
+        SignerMiddleware::new(self.provider.clone(), wallet.clone())
+    }
+
+    async fn send_commit(
+        &self,
+        block: ValidatedFuelBlock,
+        nonce: u64,
+        min_fees: Option<TxFees>,
+    ) -> Result<CommitTx> {
+        let (contract_call, fees) = self
+            .prepare_commit(block, nonce, min_fees)
+            .await
+            .map_err(Error::before_send)?;
+
+        let tx = contract_call.send().await?;
+        tracing::info!("tx: {} submitted", tx.tx_hash());
+
+        Ok(CommitTx {
+            hash: tx.tx_hash().to_fixed_bytes(),
+            nonce,
+            fees,
         })
     }
 
+    /// The commit call with its nonce, fees and gas filled in, so that sending it only signs and
+    /// broadcasts it.
+    async fn prepare_commit(
+        &self,
+        block: ValidatedFuelBlock,
+        nonce: u64,
+        min_fees: Option<TxFees>,
+    ) -> Result<(ContractCall<Signer, ()>, TxFees)> {
+        let commit_height = Self::calculate_commit_height(block.height(), self.commit_interval);
+        let fees = self.fees(min_fees).await?;
+
+        let mut contract_call = self
+            .contract
+            .commit(block.hash(), commit_height)
+            .nonce(nonce);
+        let tx = contract_call.tx.as_eip1559_mut().ok_or_else(|| {
+            Error::Other("commit tx is expected to be an EIP-1559 tx".to_string())
+        })?;
+        tx.max_fee_per_gas = Some(fees.max_fee_per_gas.into());
+        tx.max_priority_fee_per_gas = Some(fees.max_priority_fee_per_gas.into());
+
+        let gas = self.provider.estimate_gas(&contract_call.tx, None).await?;
+
+        Ok((contract_call.gas(gas), fees))
+    }
+
     pub(crate) fn calculate_commit_height(block_height: u32, commit_interval: NonZeroU32) -> U256 {
         (block_height / commit_interval).into()
     }
@@ -173,49 +400,239 @@ impl WsConnection {
         Ok(self.provider.get_balance(address, None).await?)
     }
 
+    async fn send_l2_state(
+        &self,
+        state_data: Vec<u8>,
+        mode: PostingMode,
+        replacing: Option<(u64, TxFees)>,
+    ) -> Result<L1Tx> {
+        let blob_pool_wallet = if let Some(blob_pool_wallet) = &self.blob_pool_wallet {
+            blob_pool_wallet
+        } else {
+            return Err(Error::Other("blob pool wallet not configured".to_string()));
+        };
+
+        let (l1_tx, raw_tx) = match mode {
+            PostingMode::Blob => {
+                self.prepare_blob_tx(state_data, blob_pool_wallet, replacing)
+                    .await
+            }
+            PostingMode::Calldata => {
+                self.prepare_calldata_tx(state_data, blob_pool_wallet, replacing)
+                    .await
+            }
+        }
+        .map_err(Error::before_send)?;
+
+        if let Err(err) = self.provider.send_raw_transaction(raw_tx.into()).await {
+            // a fresh nonce that never made it to the node would block every later tx
+            if replacing.is_none() && self.blob_pool_nonces.is_some() {
+                Self::fill_nonce_gaps(&self.signer(blob_pool_wallet), l1_tx.nonce..l1_tx.nonce + 1)
+                    .await;
+            }
+            return Err(err.into());
+        }
+
+        Ok(l1_tx)
+    }
+
+    /// The signed blob tx, wrapped together with its sidecar.
     async fn prepare_blob_tx(
         &self,
-        blob_versioned_hashes: Vec<H256>,
-        address: H160,
-        chain_id: u64,
-    ) -> Result<BlobTransaction> {
-        let nonce = self.provider.get_transaction_count(address, None).await?;
+        state_data: Vec<u8>,
+        blob_pool_wallet: &EthSigner,
+        replacing: Option<(u64, TxFees)>,
+    ) -> Result<(L1Tx, Vec<u8>)> {
+        let sidecar = BlobSidecar::new(state_data).map_err(|e| Error::Other(e.to_string()))?;
+        let blob_tx = self
+            .build_blob_tx(sidecar.versioned_hashes(), blob_pool_wallet, replacing)
+            .await?;
+
+        let l1_tx = L1Tx {
+            hash: [0; 32],
+            nonce: blob_tx.nonce.as_u64(),
+            fees: TxFees {
+                max_fee_per_gas: to_u128(blob_tx.max_fee_per_gas)?,
+                max_priority_fee_per_gas: to_u128(blob_tx.max_priority_fee_per_gas)?,
+                max_fee_per_blob_gas: to_u128(blob_tx.max_fee_per_blob_gas)?,
+            },
+            gas_limit: blob_tx.gas_limit.as_u64(),
+            blob_gas: get_total_blob_gas(sidecar.num_blobs() as u64),
+            mode: PostingMode::Blob,
+        };
+
+        let tx_encoder = BlobTransactionEncoder::new(blob_tx, sidecar);
+        let (tx_hash, raw_tx) = tx_encoder.raw_signed_w_sidecar(blob_pool_wallet).await?;
+
+        let l1_tx = L1Tx {
+            hash: tx_hash.to_fixed_bytes(),
+            ..l1_tx
+        };
+
+        Ok((l1_tx, raw_tx))
+    }
 
+    /// The signed tx carrying `state_data` as its input to the state inbox.
+    async fn prepare_calldata_tx(
+        &self,
+        state_data: Vec<u8>,
+        blob_pool_wallet: &EthSigner,
+        replacing: Option<(u64, TxFees)>,
+    ) -> Result<(L1Tx, Vec<u8>)> {
+        let Some(state_inbox) = self.state_inbox else {
+            return Err(Error::Other("state inbox not configured".to_string()));
+        };
+        let address = blob_pool_wallet.address();
+
+        let (nonce, fees) = self.nonce_and_fees(blob_pool_wallet, replacing).await?;
+        let fees = TxFees {
+            max_fee_per_blob_gas: 0,
+            ..fees
+        };
+
+        let mut tx: TypedTransaction = Eip1559TransactionRequest::new()
+            .from(address)
+            .to(state_inbox)
+            .data(state_data)
+            .nonce(nonce)
+            .chain_id(blob_pool_wallet.chain_id())
+            .max_fee_per_gas(fees.max_fee_per_gas)
+            .max_priority_fee_per_gas(fees.max_priority_fee_per_gas)
+            .into();
+        let gas_limit = self.provider.estimate_gas(&tx, None).await?;
+        tx.set_gas(gas_limit);
+
+        let signature = blob_pool_wallet.sign_transaction(&tx).await?;
+        let raw_tx = tx.rlp_signed(&signature).to_vec();
+
+        let l1_tx = L1Tx {
+            hash: keccak256(&raw_tx),
+            nonce,
+            fees,
+            gas_limit: gas_limit.as_u64(),
+            blob_gas: 0,
+            mode: PostingMode::Calldata,
+        };
+
+        Ok((l1_tx, raw_tx))
+    }
+
+    // When `replacing` is given, the tx reuses its nonce and pays at least its fees so that it
+    // can replace the tx already in the mempool.
+    async fn nonce_and_fees(
+        &self,
+        blob_pool_wallet: &EthSigner,
+        replacing: Option<(u64, TxFees)>,
+    ) -> Result<(u64, TxFees)> {
+        let nonce = match replacing {
+            Some((nonce, _)) => nonce,
+            None => {
+                self.next_nonce(
+                    self.blob_pool_nonces.as_ref(),
+                    &self.signer(blob_pool_wallet),
+                )
+                .await?
+            }
+        };
+
+        let fees = self.fees(replacing.map(|(_, min_fees)| min_fees)).await?;
+
+        Ok((nonce, fees))
+    }
+
+    // The estimated fees, raised to `min_fees` if given so that a replacement outbids the tx it
+    // replaces.
+    async fn fees(&self, min_fees: Option<TxFees>) -> Result<TxFees> {
         let (max_fee_per_gas, max_priority_fee_per_gas) =
             self.provider.estimate_eip1559_fees(None).await?;
+        let estimated_fees = TxFees {
+            max_fee_per_gas: to_u128(max_fee_per_gas)?,
+            max_priority_fee_per_gas: to_u128(max_priority_fee_per_gas)?,
+            max_fee_per_blob_gas: 0,
+        };
+
+        Ok(match min_fees {
+            Some(min_fees) => estimated_fees.at_least(&min_fees),
+            None => estimated_fees,
+        })
+    }
+
+    async fn build_blob_tx(
+        &self,
+        blob_versioned_hashes: Vec<H256>,
+        blob_pool_wallet: &EthSigner,
+        replacing: Option<(u64, TxFees)>,
+    ) -> Result<BlobTransaction> {
+        let (nonce, fees) = self.nonce_and_fees(blob_pool_wallet, replacing).await?;
 
         let gas_limit = U256::from(STANDARD_GAS_LIMIT);
 
-        let max_fee_per_blob_gas = self.calculate_blob_fee(blob_versioned_hashes.len()).await?;
+        let max_fee_per_blob_gas = to_u128(self.calculate_blob_fee().await?)?;
+        let fees = TxFees {
+            max_fee_per_blob_gas: fees.max_fee_per_blob_gas.max(max_fee_per_blob_gas),
+            ..fees
+        };
 
         let blob_tx = BlobTransaction {
-            to: address,
-            chain_id: chain_id.into(),
+            to: blob_pool_wallet.address(),
+            chain_id: blob_pool_wallet.chain_id().into(),
             gas_limit,
-            nonce,
-            max_fee_per_gas,
-            max_priority_fee_per_gas,
-            max_fee_per_blob_gas,
+            nonce: nonce.into(),
+            max_fee_per_gas: fees.max_fee_per_gas.into(),
+            max_priority_fee_per_gas: fees.max_priority_fee_per_gas.into(),
+            max_fee_per_blob_gas: fees.max_fee_per_blob_gas.into(),
             blob_versioned_hashes,
         };
 
         Ok(blob_tx)
     }
 
-    async fn calculate_blob_fee(&self, num_blobs: usize) -> Result<U256> {
-        let latest = self
-            .provider
-            .get_block(BlockNumber::Latest)
-            .await?
-            .expect("block not found");
+    fn convert_to_tx_response(
+        tx_receipt: Option<TransactionReceipt>,
+    ) -> Result<Option<TransactionResponse>> {
+        let Some(tx_receipt) = tx_receipt else {
+            return Ok(None);
+        };
 
-        let excess_blob_gas = latest.excess_blob_gas.expect("excess blob gas not found");
-        let max_fee_per_blob_gas = calculate_blob_fee(excess_blob_gas, num_blobs as u64);
+        // a receipt without a block belongs to a pending tx
+        let (Some(block_number), Some(block_hash)) =
+            (tx_receipt.block_number, tx_receipt.block_hash)
+        else {
+            return Ok(None);
+        };
+
+        let succeeded = tx_receipt.status == Some(1.into());
+        let gas_used = tx_receipt.gas_used.unwrap_or_default().as_u64();
+        let effective_gas_price = to_u128(tx_receipt.effective_gas_price.unwrap_or_default())?;
+
+        Ok(Some(TransactionResponse::new(
+            block_number.as_u64(),
+            block_hash.to_fixed_bytes(),
+            succeeded,
+            gas_used,
+            effective_gas_price,
+        )))
+    }
+
+    // Max fee per blob gas. Double the current base fee so that the tx stays includable for a
+    // few blocks of rising blob gas prices, the same headroom ethers gives the execution base fee.
+    async fn calculate_blob_fee(&self) -> Result<U256> {
+        let base_fee_per_blob_gas = self
+            .base_fees()
+            .await?
+            .base_fee_per_blob_gas
+            .ok_or_else(|| Error::Other("chain doesn't support blobs".to_string()))?;
+        let max_fee_per_blob_gas = U256::from(base_fee_per_blob_gas) * 2;
 
         Ok(max_fee_per_blob_gas)
     }
 }
 
+fn to_u128(value: U256) -> Result<u128> {
+    u128::try_from(value)
+        .map_err(|_| Error::Other(format!("fee `{value}` does not fit into a u128")))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
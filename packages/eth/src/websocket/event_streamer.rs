@@ -1,45 +1,323 @@
-use std::sync::Arc;
-
 use ethers::{
-    prelude::{k256::ecdsa::SigningKey, Event, SignerMiddleware},
-    providers::{Provider, Ws},
-    signers::Wallet,
+    abi::RawLog,
+    contract::EthEvent,
+    providers::{interval, JsonRpcClient, Middleware, Provider},
+    types::{Filter, Log, U64},
 };
-use futures::{Stream, TryStreamExt};
-use ports::types::FuelBlockCommittedOnL1;
-
-use super::connection::CommitSubmittedFilter;
-use crate::error::Result;
+use futures::{stream::BoxStream, Future, Stream, StreamExt};
+use ports::types::{FuelBlockCommittedOnL1, L1Height};
+use tracing::warn;
 
-type EthStreamInitializer = Event<
-    Arc<SignerMiddleware<Provider<Ws>, Wallet<SigningKey>>>,
-    SignerMiddleware<Provider<Ws>, Wallet<SigningKey>>,
-    CommitSubmittedFilter,
->;
+use super::{connection::CommitSubmittedFilter, transport::Transport};
+use crate::error::{Error, Result};
 
+/// Streams the commit events from one endpoint after another, see [`failing_over`].
 pub struct EthEventStreamer {
-    events: EthStreamInitializer,
+    filter: Filter,
+    providers: Vec<Provider<Transport>>,
 }
 
 impl EthEventStreamer {
-    pub fn new(events: EthStreamInitializer) -> Self {
-        Self { events }
+    pub fn new(filter: Filter, provider: Provider<Transport>) -> Self {
+        Self {
+            filter,
+            providers: vec![provider],
+        }
+    }
+
+    /// Streams from the endpoints of `streamers` in the given order. They are expected to share
+    /// the filter.
+    pub fn chain(streamers: Vec<Self>) -> Option<Self> {
+        let mut streamers = streamers.into_iter();
+        let mut first = streamers.next()?;
+        first
+            .providers
+            .extend(streamers.flat_map(|streamer| streamer.providers));
+
+        Some(first)
     }
 
     pub(crate) async fn establish_stream(
         &self,
-    ) -> Result<impl Stream<Item = Result<FuelBlockCommittedOnL1>> + Send + '_> {
-        let events = self.events.subscribe().await?;
-        let stream = events
-            .map_ok(|event| {
-                let fuel_block_hash = event.block_hash;
-                let commit_height = event.commit_height;
-                FuelBlockCommittedOnL1 {
-                    fuel_block_hash,
-                    commit_height,
-                }
-            })
-            .map_err(Into::into);
-        Ok(stream)
+    ) -> Result<BoxStream<'_, Result<FuelBlockCommittedOnL1>>> {
+        let from_block = self.filter.get_from_block().unwrap_or_default();
+        let stream = failing_over(&self.providers, from_block, |provider, from_block| {
+            self.stream_from(provider, from_block)
+        })
+        .await?;
+
+        Ok(stream.boxed())
+    }
+
+    // Subscribes to the raw logs instead of the decoded events so that logs removed by a reorg
+    // can be told apart. Transports without subscriptions fall back to polling.
+    async fn stream_from<'a>(
+        &'a self,
+        provider: &'a Provider<Transport>,
+        from_block: U64,
+    ) -> Result<BoxStream<'a, Result<FuelBlockCommittedOnL1>>> {
+        let filter = self.filter.clone().from_block(from_block);
+
+        if !provider.as_ref().supports_subscriptions() {
+            let logs = poll_logs(provider, filter);
+            return Ok(logs.map(|log| log.and_then(Self::decode)).boxed());
+        }
+
+        let logs = provider.subscribe_logs(&filter).await?;
+        let stream = logs.map(Self::decode);
+
+        Ok(stream.boxed())
+    }
+
+    fn decode(log: Log) -> Result<FuelBlockCommittedOnL1> {
+        let removed = log.removed.unwrap_or(false);
+        let (Some(block_number), Some(block_hash)) = (log.block_number, log.block_hash) else {
+            return Err(Error::Other(
+                "received a commit log without a block".to_string(),
+            ));
+        };
+        let l1_height =
+            L1Height::try_from(block_number.as_u64()).map_err(|e| Error::Other(e.to_string()))?;
+
+        let event = CommitSubmittedFilter::decode_log(&RawLog::from(log))
+            .map_err(|e| Error::Other(e.to_string()))?;
+
+        Ok(FuelBlockCommittedOnL1 {
+            fuel_block_hash: event.block_hash,
+            commit_height: event.commit_height,
+            l1_height,
+            l1_block_hash: block_hash.to_fixed_bytes(),
+            removed,
+        })
+    }
+}
+
+type EventStream<'a> = BoxStream<'a, Result<FuelBlockCommittedOnL1>>;
+
+/// Streams the events of the first of `sources` a stream can be established with, moving on to
+/// the next source once the stream hits a network error or ends. The next source picks up at the
+/// L1 height of the last event seen, so the events of that block may be repeated. The stream ends
+/// with the last source, fails only if no stream can be established at all.
+async fn failing_over<'a, S, F, Fut>(
+    sources: &'a [S],
+    from_block: U64,
+    establish: F,
+) -> Result<impl Stream<Item = Result<FuelBlockCommittedOnL1>> + Send + 'a>
+where
+    S: Sync,
+    F: Fn(&'a S, U64) -> Fut + Send + Sync + 'a,
+    Fut: Future<Output = Result<EventStream<'a>>> + Send + 'a,
+{
+    let mut remaining = sources.iter();
+    let first = loop {
+        let Some(source) = remaining.next() else {
+            return Err(Error::Network(
+                "no l1 endpoint to stream events from".to_string(),
+            ));
+        };
+        match establish(source, from_block).await {
+            Ok(stream) => break stream,
+            Err(err) if remaining.len() > 0 => {
+                warn!("failed to stream l1 events, moving on to the next endpoint: {err}");
+            }
+            Err(err) => return Err(err),
+        }
+    };
+
+    let state = (Some(first), remaining, from_block, establish);
+    let stream = futures::stream::unfold(
+        state,
+        |(mut current, mut remaining, mut from_block, establish)| async move {
+            loop {
+                let Some(stream) = current.as_mut() else {
+                    let source = remaining.next()?;
+                    match establish(source, from_block).await {
+                        Ok(stream) => current = Some(stream),
+                        Err(err) => {
+                            return Some((Err(err), (None, remaining, from_block, establish)))
+                        }
+                    }
+                    continue;
+                };
+
+                let event = match stream.next().await {
+                    Some(Ok(event)) => {
+                        from_block = U64::from(u64::from(event.l1_height));
+                        Ok(event)
+                    }
+                    Some(Err(Error::Network(err))) => {
+                        if remaining.len() > 0 {
+                            warn!("l1 event stream failed, moving on to the next endpoint: {err}");
+                        }
+                        current = None;
+                        Err(Error::Network(err))
+                    }
+                    Some(Err(err)) => Err(err),
+                    None => {
+                        if remaining.len() > 0 {
+                            warn!("l1 event stream ended, moving on to the next endpoint");
+                        }
+                        current = None;
+                        continue;
+                    }
+                };
+
+                return Some((event, (current, remaining, from_block, establish)));
+            }
+        },
+    );
+
+    Ok(stream)
+}
+
+/// Polls `eth_getLogs` once every polling interval of the provider, each time for the blocks
+/// produced since the previous poll. Unlike a subscription this can't report logs that a reorg
+/// removed after they were polled.
+fn poll_logs<P: JsonRpcClient>(
+    provider: &Provider<P>,
+    filter: Filter,
+) -> impl Stream<Item = Result<Log>> + Send + '_ {
+    let next_block = filter.get_from_block().unwrap_or_default();
+    let ticks = interval(provider.get_interval());
+
+    futures::stream::unfold((ticks, next_block), move |(mut ticks, next_block)| {
+        let filter = filter.clone();
+        async move {
+            ticks.next().await;
+
+            let (logs, next_block) = match logs_since(provider, filter, next_block).await {
+                Ok((logs, next_block)) => (logs.into_iter().map(Ok).collect(), next_block),
+                Err(err) => (vec![Err(err)], next_block),
+            };
+
+            Some((futures::stream::iter(logs), (ticks, next_block)))
+        }
+    })
+    .flatten()
+}
+
+/// The logs from `from_block` up to the latest block, together with the block to continue from.
+async fn logs_since<P: JsonRpcClient>(
+    provider: &Provider<P>,
+    filter: Filter,
+    from_block: U64,
+) -> Result<(Vec<Log>, U64)> {
+    let latest_block = provider.get_block_number().await?;
+    if latest_block < from_block {
+        return Ok((vec![], from_block));
+    }
+
+    let filter = filter.from_block(from_block).to_block(latest_block);
+    let logs = provider.get_logs(&filter).await?;
+
+    Ok((logs, latest_block + 1))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{sync::Mutex, time::Duration};
+
+    use ethers::types::{Address, H256, U256};
+
+    use super::*;
+
+    fn given_log(block_number: u64) -> Log {
+        Log {
+            address: Address::zero(),
+            block_number: Some(block_number.into()),
+            block_hash: Some(H256::repeat_byte(1)),
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn polls_logs_of_new_blocks_only() {
+        // given
+        let (provider, mock) = Provider::mocked();
+        let provider = provider.interval(Duration::from_millis(1));
+
+        // responses are served last pushed first
+        mock.push::<Vec<Log>, _>(vec![given_log(12)]).unwrap();
+        mock.push::<U64, _>(U64::from(12)).unwrap();
+        mock.push::<Vec<Log>, _>(vec![given_log(10)]).unwrap();
+        mock.push::<U64, _>(U64::from(10)).unwrap();
+
+        let filter = Filter::new().from_block(8);
+
+        // when
+        let logs: Vec<_> = poll_logs(&provider, filter).take(2).collect().await;
+
+        // then
+        let block_numbers: Vec<_> = logs
+            .into_iter()
+            .map(|log| log.unwrap().block_number.unwrap().as_u64())
+            .collect();
+        assert_eq!(block_numbers, vec![10, 12]);
+
+        let requested_range = |from: u64, to: u64| {
+            let filter = Filter::new().from_block(from).to_block(to);
+            [serde_json::to_value(filter).unwrap()]
+        };
+        mock.assert_request("eth_blockNumber", ()).unwrap();
+        mock.assert_request("eth_getLogs", requested_range(8, 10))
+            .unwrap();
+        mock.assert_request("eth_blockNumber", ()).unwrap();
+        mock.assert_request("eth_getLogs", requested_range(11, 12))
+            .unwrap();
+    }
+
+    fn given_event(l1_height: u32) -> FuelBlockCommittedOnL1 {
+        FuelBlockCommittedOnL1 {
+            fuel_block_hash: [0; 32],
+            commit_height: U256::zero(),
+            l1_height: l1_height.into(),
+            l1_block_hash: [1; 32],
+            removed: false,
+        }
+    }
+
+    #[tokio::test]
+    async fn moves_to_the_next_endpoint_when_the_stream_fails_or_ends() {
+        // given
+        // the l1 heights of the events each endpoint streams, `None` being a network error
+        let endpoints = [vec![], vec![Some(5), None, Some(9)], vec![Some(5), Some(7)]];
+        let requested_from = Mutex::new(vec![]);
+
+        // when
+        let events: Vec<_> = failing_over(&endpoints, U64::from(3), |events, from_block| {
+            requested_from.lock().unwrap().push(from_block.as_u64());
+            let stream = futures::stream::iter(events.iter().map(|event| match event {
+                Some(height) => Ok(given_event(*height)),
+                None => Err(Error::Network("connection reset".to_string())),
+            }));
+            async move { Ok(stream.boxed()) }
+        })
+        .await
+        .unwrap()
+        .collect()
+        .await;
+
+        // then
+        let events: Vec<_> = events
+            .into_iter()
+            .map(|event| event.ok().map(|event| u64::from(event.l1_height)))
+            .collect();
+        assert_eq!(events, vec![Some(5), None, Some(5), Some(7)]);
+        assert_eq!(*requested_from.lock().unwrap(), vec![3, 3, 5]);
+    }
+
+    #[tokio::test]
+    async fn fails_if_no_endpoint_can_be_streamed_from() {
+        // given
+        let endpoints = ["first", "second"];
+
+        // when
+        let result = failing_over(&endpoints, U64::from(3), |_, _| async {
+            Err::<EventStream, _>(Error::Network("connection refused".to_string()))
+        })
+        .await;
+
+        // then
+        assert!(matches!(result, Err(Error::Network(_))));
     }
 }
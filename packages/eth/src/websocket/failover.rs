@@ -0,0 +1,586 @@
+use std::{
+    future::Future,
+    num::NonZeroU32,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+};
+
+use futures::future::join_all;
+use ports::types::{
+    BaseFees, CommitTx, StateFrame, TransactionResponse, TxFees, ValidatedFuelBlock, U256,
+};
+use tracing::warn;
+use url::Url;
+
+use crate::{
+    error::{Error, Result},
+    state_tx::{L1Tx, PostingMode},
+    websocket::{event_streamer::EthEventStreamer, health_tracking_middleware::EthApi},
+};
+
+/// An L1 RPC endpoint. Endpoints with a lower `priority` are tried first.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RpcEndpoint {
+    pub url: Url,
+    pub priority: u32,
+}
+
+impl RpcEndpoint {
+    pub fn new(url: Url, priority: u32) -> Self {
+        Self { url, priority }
+    }
+}
+
+/// Names the endpoints after their host and port. The path is left out since providers tend to
+/// put api keys in it. Endpoints sharing a host get their position appended.
+pub(crate) fn endpoint_names(endpoints: &[RpcEndpoint]) -> Vec<String> {
+    let hosts: Vec<String> = endpoints
+        .iter()
+        .map(|endpoint| {
+            let host = endpoint.url.host_str().unwrap_or("unknown");
+            match endpoint.url.port() {
+                Some(port) => format!("{host}:{port}"),
+                None => host.to_string(),
+            }
+        })
+        .collect();
+
+    hosts
+        .iter()
+        .enumerate()
+        .map(|(index, host)| {
+            if hosts.iter().filter(|other| *other == host).count() > 1 {
+                format!("{host}#{index}")
+            } else {
+                host.clone()
+            }
+        })
+        .collect()
+}
+
+pub(crate) struct Endpoint<T> {
+    pub(crate) name: String,
+    pub(crate) api: T,
+    /// Last block height the endpoint reported.
+    height: AtomicU64,
+}
+
+/// Spreads the calls over several endpoints. Txs are sent through the endpoints in order of
+/// priority, moving on to the next one if an endpoint can't be connected to or fails before the tx
+/// was broadcast. A tx that fails while being broadcast is not sent elsewhere, the node might have
+/// received it and it would be broadcast twice under different nonces. Reads are
+/// answered by the most advanced endpoint, moving on to the next one on network errors, or by all
+/// of them where a lagging node could mislead the committer.
+pub(crate) struct Failover<T> {
+    endpoints: Arc<Vec<Endpoint<T>>>,
+    commit_interval: NonZeroU32,
+}
+
+impl<T> Clone for Failover<T> {
+    fn clone(&self) -> Self {
+        Self {
+            endpoints: Arc::clone(&self.endpoints),
+            commit_interval: self.commit_interval,
+        }
+    }
+}
+
+impl<T: EthApi> Failover<T> {
+    /// `endpoints` are given as (name, priority, api). At least one of them has to be connected,
+    /// the commit interval of the contract is read from it.
+    pub fn new(endpoints: Vec<(String, u32, T)>) -> Result<Self> {
+        if endpoints.is_empty() {
+            return Err(Error::Other("no l1 endpoints given".to_string()));
+        }
+        let Some((_, _, connected)) = endpoints.iter().find(|(_, _, api)| api.is_connected())
+        else {
+            return Err(Error::Network(
+                "none of the l1 endpoints is connected".to_string(),
+            ));
+        };
+        let commit_interval = connected.commit_interval();
+
+        let mut endpoints = endpoints;
+        endpoints.sort_by_key(|(_, priority, _)| *priority);
+
+        let endpoints = endpoints
+            .into_iter()
+            .map(|(name, _, api)| Endpoint {
+                name,
+                api,
+                height: AtomicU64::new(0),
+            })
+            .collect();
+
+        Ok(Self {
+            endpoints: Arc::new(endpoints),
+            commit_interval,
+        })
+    }
+
+    pub fn endpoints(&self) -> &[Endpoint<T>] {
+        &self.endpoints
+    }
+
+    fn by_priority(&self) -> Vec<&Endpoint<T>> {
+        self.endpoints.iter().collect()
+    }
+
+    /// Highest known block height first, ties broken by priority.
+    fn by_height(&self) -> Vec<&Endpoint<T>> {
+        let mut endpoints = self.by_priority();
+        endpoints
+            .sort_by_key(|endpoint| std::cmp::Reverse(endpoint.height.load(Ordering::Relaxed)));
+        endpoints
+    }
+
+    /// Sends the tx through the endpoints in order of priority, moving on only while it certainly
+    /// hasn't reached a node: when connecting fails or a network error is hit before the tx is
+    /// broadcast.
+    async fn first_to_send<'a, R, Fut>(&'a self, send: impl Fn(&'a T) -> Fut) -> Result<R>
+    where
+        Fut: Future<Output = Result<R>>,
+    {
+        let mut last_error = None;
+        for endpoint in self.by_priority() {
+            if let Err(err) = endpoint.api.connect().await {
+                warn!(
+                    "l1 endpoint {} is unreachable, trying the next one: {err}",
+                    endpoint.name
+                );
+                last_error = Some(err);
+                continue;
+            }
+
+            match send(&endpoint.api).await {
+                Err(Error::NetworkBeforeSend(err)) => {
+                    warn!(
+                        "l1 endpoint {} failed before the tx was sent, trying the next one: {err}",
+                        endpoint.name
+                    );
+                    last_error = Some(Error::NetworkBeforeSend(err));
+                }
+                response => return response,
+            }
+        }
+
+        Err(last_error.expect("there is at least one endpoint"))
+    }
+
+    async fn first_available<'a, R, Fut>(
+        endpoints: Vec<&'a Endpoint<T>>,
+        request: impl Fn(&'a T) -> Fut,
+    ) -> Result<R>
+    where
+        Fut: Future<Output = Result<R>>,
+    {
+        let mut last_error = None;
+        for endpoint in endpoints {
+            match request(&endpoint.api).await {
+                Err(Error::Network(err)) => {
+                    warn!(
+                        "l1 endpoint {} failed, trying the next one: {err}",
+                        endpoint.name
+                    );
+                    last_error = Some(Error::Network(err));
+                }
+                response => return response,
+            }
+        }
+
+        Err(last_error.expect("there is at least one endpoint"))
+    }
+
+    /// Asks every endpoint, keeping the answers of those that responded. Fails only if none did.
+    async fn all_available<'a, R, Fut>(
+        &'a self,
+        request: impl Fn(&'a T) -> Fut,
+    ) -> Result<Vec<(&'a Endpoint<T>, R)>>
+    where
+        Fut: Future<Output = Result<R>>,
+    {
+        let request = &request;
+        let responses = join_all(
+            self.endpoints
+                .iter()
+                .map(|endpoint| async move { (endpoint, request(&endpoint.api).await) }),
+        )
+        .await;
+
+        let mut answers = vec![];
+        let mut last_error = None;
+        for (endpoint, response) in responses {
+            match response {
+                Ok(answer) => answers.push((endpoint, answer)),
+                Err(err) => {
+                    warn!("l1 endpoint {} failed: {err}", endpoint.name);
+                    last_error = Some(err);
+                }
+            }
+        }
+
+        match last_error {
+            Some(err) if answers.is_empty() => Err(err),
+            _ => Ok(answers),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl<T> EthApi for Failover<T>
+where
+    T: EthApi + Send + Sync,
+{
+    async fn connect(&self) -> Result<()> {
+        self.first_to_send(|_| async { Ok(()) }).await
+    }
+
+    fn is_connected(&self) -> bool {
+        self.endpoints
+            .iter()
+            .any(|endpoint| endpoint.api.is_connected())
+    }
+
+    async fn submit(&self, block: ValidatedFuelBlock) -> Result<CommitTx> {
+        self.first_to_send(|api| api.submit(block)).await
+    }
+
+    async fn replace_commit(
+        &self,
+        block: ValidatedFuelBlock,
+        nonce: u64,
+        min_fees: TxFees,
+    ) -> Result<CommitTx> {
+        self.first_to_send(|api| api.replace_commit(block, nonce, min_fees))
+            .await
+    }
+
+    async fn get_block_number(&self) -> Result<u64> {
+        let heights = self.all_available(|api| api.get_block_number()).await?;
+
+        for (endpoint, height) in &heights {
+            endpoint.height.store(*height, Ordering::Relaxed);
+        }
+
+        Ok(heights
+            .into_iter()
+            .map(|(_, height)| height)
+            .max()
+            .expect("at least one endpoint responded"))
+    }
+
+    async fn get_block_hash(&self, height: u64) -> Result<Option<[u8; 32]>> {
+        Self::first_available(self.by_height(), |api| api.get_block_hash(height)).await
+    }
+
+    async fn balance(&self) -> Result<U256> {
+        Self::first_available(self.by_height(), |api| api.balance()).await
+    }
+
+    async fn base_fees(&self) -> Result<BaseFees> {
+        Self::first_available(self.by_height(), |api| api.base_fees()).await
+    }
+
+    fn commit_interval(&self) -> NonZeroU32 {
+        self.commit_interval
+    }
+
+    /// Streams from the most advanced endpoint, moving on to the next one whenever the stream
+    /// fails or ends. Endpoints that aren't connected yet are left out.
+    fn event_streamer(&self, eth_block_height: u64) -> Result<EthEventStreamer> {
+        let mut streamers = vec![];
+        let mut last_error = None;
+        for endpoint in self.by_height() {
+            match endpoint.api.event_streamer(eth_block_height) {
+                Ok(streamer) => streamers.push(streamer),
+                Err(err) => last_error = Some(err),
+            }
+        }
+
+        EthEventStreamer::chain(streamers)
+            .ok_or_else(|| last_error.expect("there is at least one endpoint"))
+    }
+
+    async fn submit_l2_state(&self, state_data: Vec<u8>, mode: PostingMode) -> Result<L1Tx> {
+        self.first_to_send(|api| api.submit_l2_state(state_data.clone(), mode))
+            .await
+    }
+
+    async fn replace_l2_state(
+        &self,
+        state_data: Vec<u8>,
+        nonce: u64,
+        min_fees: TxFees,
+        mode: PostingMode,
+    ) -> Result<L1Tx> {
+        self.first_to_send(|api| api.replace_l2_state(state_data.clone(), nonce, min_fees, mode))
+            .await
+    }
+
+    async fn get_transaction_response(
+        &self,
+        tx_hash: [u8; 32],
+    ) -> Result<Option<TransactionResponse>> {
+        Self::first_available(self.by_height(), |api| {
+            api.get_transaction_response(tx_hash)
+        })
+        .await
+    }
+
+    // a node that hasn't seen the tx yet would have it squeezed out, so all have to agree
+    async fn is_squeezed_out(&self, tx_hash: [u8; 32]) -> Result<bool> {
+        let answers = self
+            .all_available(|api| api.is_squeezed_out(tx_hash))
+            .await?;
+
+        Ok(answers.into_iter().all(|(_, squeezed_out)| squeezed_out))
+    }
+
+    async fn get_l2_state(&self, tx_hash: [u8; 32]) -> Result<Option<Vec<StateFrame>>> {
+        Self::first_available(self.by_height(), |api| api.get_l2_state(tx_hash)).await
+    }
+
+    #[cfg(feature = "test-helpers")]
+    async fn finalized(&self, block: ValidatedFuelBlock) -> Result<bool> {
+        Self::first_available(self.by_height(), |api| api.finalized(block)).await
+    }
+
+    #[cfg(feature = "test-helpers")]
+    async fn block_hash_at_commit_height(&self, commit_height: u32) -> Result<[u8; 32]> {
+        Self::first_available(self.by_height(), |api| {
+            api.block_hash_at_commit_height(commit_height)
+        })
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::websocket::health_tracking_middleware::MockEthApi;
+
+    use super::*;
+
+    fn given_connected_endpoint() -> MockEthApi {
+        let mut api = MockEthApi::new();
+        api.expect_is_connected().returning(|| true);
+        api.expect_connect().returning(|| Ok(()));
+        api.expect_commit_interval()
+            .returning(|| NonZeroU32::new(10).unwrap());
+        api
+    }
+
+    fn given_unreachable_endpoint() -> MockEthApi {
+        let mut api = MockEthApi::new();
+        api.expect_is_connected().returning(|| false);
+        api.expect_connect()
+            .returning(|| Err(Error::Network("connection refused".into())));
+        api
+    }
+
+    fn given_endpoint_at_height(height: u64) -> MockEthApi {
+        let mut api = given_connected_endpoint();
+        api.expect_get_block_number().returning(move || Ok(height));
+        api
+    }
+
+    #[tokio::test]
+    async fn submissions_go_to_the_next_endpoint_if_the_first_cannot_be_reached() {
+        // given
+        let mut primary = given_unreachable_endpoint();
+        primary.expect_submit().never();
+        let mut backup = given_connected_endpoint();
+        backup.expect_submit().once().returning(|_| {
+            Ok(CommitTx {
+                hash: [1; 32],
+                nonce: 0,
+                fees: TxFees::default(),
+            })
+        });
+
+        let failover = Failover::new(vec![
+            ("backup".to_string(), 1, backup),
+            ("primary".to_string(), 0, primary),
+        ])
+        .unwrap();
+
+        // when
+        let tx = failover
+            .submit(ValidatedFuelBlock::new([0; 32], 1))
+            .await
+            .unwrap();
+
+        // then
+        assert_eq!(tx.hash, [1; 32]);
+    }
+
+    #[tokio::test]
+    async fn failed_submissions_are_not_resent_elsewhere() {
+        // given
+        let mut primary = given_connected_endpoint();
+        primary
+            .expect_submit_l2_state()
+            .once()
+            .returning(|_, _| Err(Error::Network("connection reset".into())));
+        let mut backup = given_connected_endpoint();
+        backup.expect_submit_l2_state().never();
+
+        let failover = Failover::new(vec![
+            ("primary".to_string(), 0, primary),
+            ("backup".to_string(), 1, backup),
+        ])
+        .unwrap();
+
+        // when
+        let result = failover.submit_l2_state(vec![1], PostingMode::Blob).await;
+
+        // then
+        assert!(matches!(result, Err(Error::Network(_))));
+    }
+
+    #[tokio::test]
+    async fn submissions_go_to_the_next_endpoint_if_the_first_fails_before_sending() {
+        // given
+        let mut primary = given_connected_endpoint();
+        primary
+            .expect_submit_l2_state()
+            .once()
+            .returning(|_, _| Err(Error::NetworkBeforeSend("gas estimation timed out".into())));
+        let mut backup = given_connected_endpoint();
+        backup.expect_submit_l2_state().once().returning(|_, mode| {
+            Ok(L1Tx {
+                hash: [1; 32],
+                nonce: 0,
+                fees: TxFees::default(),
+                gas_limit: 0,
+                blob_gas: 0,
+                mode,
+            })
+        });
+
+        let failover = Failover::new(vec![
+            ("primary".to_string(), 0, primary),
+            ("backup".to_string(), 1, backup),
+        ])
+        .unwrap();
+
+        // when
+        let tx = failover
+            .submit_l2_state(vec![1], PostingMode::Blob)
+            .await
+            .unwrap();
+
+        // then
+        assert_eq!(tx.hash, [1; 32]);
+    }
+
+    #[tokio::test]
+    async fn other_errors_are_not_retried_elsewhere() {
+        // given
+        let mut primary = given_connected_endpoint();
+        primary
+            .expect_submit()
+            .once()
+            .returning(|_| Err(Error::Other("reverted".into())));
+        let mut backup = given_connected_endpoint();
+        backup.expect_submit().never();
+
+        let failover = Failover::new(vec![
+            ("primary".to_string(), 0, primary),
+            ("backup".to_string(), 1, backup),
+        ])
+        .unwrap();
+
+        // when
+        let result = failover.submit(ValidatedFuelBlock::new([0; 32], 1)).await;
+
+        // then
+        assert!(matches!(result, Err(Error::Other(_))));
+    }
+
+    #[tokio::test]
+    async fn reads_go_to_the_most_advanced_endpoint() {
+        // given
+        let mut lagging = given_endpoint_at_height(10);
+        lagging.expect_balance().never();
+        let mut advanced = given_endpoint_at_height(12);
+        advanced.expect_balance().returning(|| Ok(U256::from(7)));
+        let mut unreachable = given_unreachable_endpoint();
+        unreachable
+            .expect_get_block_number()
+            .returning(|| Err(Error::Network("timeout".into())));
+
+        let failover = Failover::new(vec![
+            ("lagging".to_string(), 0, lagging),
+            ("advanced".to_string(), 1, advanced),
+            ("unreachable".to_string(), 2, unreachable),
+        ])
+        .unwrap();
+
+        // when
+        let height = failover.get_block_number().await.unwrap();
+        let balance = failover.balance().await.unwrap();
+
+        // then
+        assert_eq!(height, 12);
+        assert_eq!(balance, U256::from(7));
+    }
+
+    #[tokio::test]
+    async fn tx_is_squeezed_out_only_if_all_endpoints_agree() {
+        // given
+        let mut lagging = given_connected_endpoint();
+        lagging.expect_is_squeezed_out().returning(|_| Ok(true));
+        let mut advanced = given_connected_endpoint();
+        advanced.expect_is_squeezed_out().returning(|_| Ok(false));
+
+        let failover = Failover::new(vec![
+            ("lagging".to_string(), 0, lagging),
+            ("advanced".to_string(), 1, advanced),
+        ])
+        .unwrap();
+
+        // when
+        let squeezed_out = failover.is_squeezed_out([0; 32]).await.unwrap();
+
+        // then
+        assert!(!squeezed_out);
+    }
+
+    #[test]
+    fn needs_a_connected_endpoint() {
+        // given
+        let endpoints = vec![("unreachable".to_string(), 0, given_unreachable_endpoint())];
+
+        // when
+        let result = Failover::new(endpoints);
+
+        // then
+        assert!(matches!(result, Err(Error::Network(_))));
+    }
+
+    #[test]
+    fn endpoints_are_named_without_their_path() {
+        // given
+        let endpoints = [
+            "https://mainnet.infura.io/v3/secret-key",
+            "wss://eth.example.com:8546",
+            "wss://eth.example.com:8546/other",
+        ]
+        .map(|url| RpcEndpoint::new(Url::parse(url).unwrap(), 0));
+
+        // when
+        let names = endpoint_names(&endpoints);
+
+        // then
+        assert_eq!(
+            names,
+            vec![
+                "mainnet.infura.io",
+                "eth.example.com:8546#1",
+                "eth.example.com:8546#2"
+            ]
+        );
+    }
+}
@@ -4,23 +4,51 @@ use ::metrics::{
 
 use std::num::NonZeroU32;
 
-use ports::types::{ValidatedFuelBlock, U256};
+use ports::types::{
+    BaseFees, CommitTx, StateFrame, TransactionResponse, TxFees, ValidatedFuelBlock, U256,
+};
 
 use crate::{
     error::{Error, Result},
     metrics::Metrics,
+    state_tx::{L1Tx, PostingMode},
     websocket::event_streamer::EthEventStreamer,
 };
 
 #[cfg_attr(test, mockall::automock)]
 #[async_trait::async_trait]
 pub trait EthApi {
-    async fn submit(&self, block: ValidatedFuelBlock) -> Result<()>;
+    /// Connects to the node unless already connected. Nothing is sent to the node if it fails.
+    async fn connect(&self) -> Result<()>;
+    fn is_connected(&self) -> bool;
+    async fn submit(&self, block: ValidatedFuelBlock) -> Result<CommitTx>;
+    async fn replace_commit(
+        &self,
+        block: ValidatedFuelBlock,
+        nonce: u64,
+        min_fees: TxFees,
+    ) -> Result<CommitTx>;
     async fn get_block_number(&self) -> Result<u64>;
+    async fn get_block_hash(&self, height: u64) -> Result<Option<[u8; 32]>>;
     async fn balance(&self) -> Result<U256>;
+    async fn base_fees(&self) -> Result<BaseFees>;
     fn commit_interval(&self) -> NonZeroU32;
-    fn event_streamer(&self, eth_block_height: u64) -> EthEventStreamer;
-    async fn submit_l2_state(&self, state_data: Vec<u8>) -> Result<[u8; 32]>;
+    fn event_streamer(&self, eth_block_height: u64) -> Result<EthEventStreamer>;
+    async fn submit_l2_state(&self, state_data: Vec<u8>, mode: PostingMode) -> Result<L1Tx>;
+    async fn replace_l2_state(
+        &self,
+        state_data: Vec<u8>,
+        nonce: u64,
+        min_fees: TxFees,
+        mode: PostingMode,
+    ) -> Result<L1Tx>;
+    async fn get_transaction_response(
+        &self,
+        tx_hash: [u8; 32],
+    ) -> Result<Option<TransactionResponse>>;
+    async fn is_squeezed_out(&self, tx_hash: [u8; 32]) -> Result<bool>;
+    /// Frames posted by the tx, `None` if the node doesn't know of the tx.
+    async fn get_l2_state(&self, tx_hash: [u8; 32]) -> Result<Option<Vec<StateFrame>>>;
     #[cfg(feature = "test-helpers")]
     async fn finalized(&self, block: ValidatedFuelBlock) -> Result<bool>;
     #[cfg(feature = "test-helpers")]
@@ -35,10 +63,10 @@ pub struct HealthTrackingMiddleware<T> {
 }
 
 impl<T> HealthTrackingMiddleware<T> {
-    pub fn new(adapter: T, unhealthy_after_n_errors: usize) -> Self {
+    pub fn new(adapter: T, endpoint: &str, unhealthy_after_n_errors: usize) -> Self {
         Self {
             adapter,
-            metrics: Metrics::default(),
+            metrics: Metrics::new(endpoint),
             health_tracker: ConnectionHealthTracker::new(unhealthy_after_n_errors),
         }
     }
@@ -52,7 +80,7 @@ impl<T> HealthTrackingMiddleware<T> {
             Ok(_val) => {
                 self.health_tracker.note_success();
             }
-            Err(Error::Network(..)) => {
+            Err(Error::Network(..) | Error::NetworkBeforeSend(..)) => {
                 self.metrics.eth_network_errors.inc();
                 self.health_tracker.note_failure();
             }
@@ -73,22 +101,55 @@ impl<T> EthApi for HealthTrackingMiddleware<T>
 where
     T: EthApi + Send + Sync,
 {
-    async fn submit(&self, block: ValidatedFuelBlock) -> Result<()> {
+    async fn connect(&self) -> Result<()> {
+        let response = self.adapter.connect().await;
+        self.note_network_status(&response);
+        response
+    }
+
+    fn is_connected(&self) -> bool {
+        self.adapter.is_connected()
+    }
+
+    async fn submit(&self, block: ValidatedFuelBlock) -> Result<CommitTx> {
         let response = self.adapter.submit(block).await;
         self.note_network_status(&response);
         response
     }
 
+    async fn replace_commit(
+        &self,
+        block: ValidatedFuelBlock,
+        nonce: u64,
+        min_fees: TxFees,
+    ) -> Result<CommitTx> {
+        let response = self.adapter.replace_commit(block, nonce, min_fees).await;
+        self.note_network_status(&response);
+        response
+    }
+
     async fn get_block_number(&self) -> Result<u64> {
         let response = self.adapter.get_block_number().await;
         self.note_network_status(&response);
         response
     }
 
-    fn event_streamer(&self, eth_block_height: u64) -> EthEventStreamer {
+    async fn get_block_hash(&self, height: u64) -> Result<Option<[u8; 32]>> {
+        let response = self.adapter.get_block_hash(height).await;
+        self.note_network_status(&response);
+        response
+    }
+
+    fn event_streamer(&self, eth_block_height: u64) -> Result<EthEventStreamer> {
         self.adapter.event_streamer(eth_block_height)
     }
 
+    async fn base_fees(&self) -> Result<BaseFees> {
+        let response = self.adapter.base_fees().await;
+        self.note_network_status(&response);
+        response
+    }
+
     async fn balance(&self) -> Result<U256> {
         let response = self.adapter.balance().await;
         self.note_network_status(&response);
@@ -99,8 +160,44 @@ where
         self.adapter.commit_interval()
     }
 
-    async fn submit_l2_state(&self, tx: Vec<u8>) -> Result<[u8; 32]> {
-        let response = self.adapter.submit_l2_state(tx).await;
+    async fn submit_l2_state(&self, tx: Vec<u8>, mode: PostingMode) -> Result<L1Tx> {
+        let response = self.adapter.submit_l2_state(tx, mode).await;
+        self.note_network_status(&response);
+        response
+    }
+
+    async fn replace_l2_state(
+        &self,
+        state_data: Vec<u8>,
+        nonce: u64,
+        min_fees: TxFees,
+        mode: PostingMode,
+    ) -> Result<L1Tx> {
+        let response = self
+            .adapter
+            .replace_l2_state(state_data, nonce, min_fees, mode)
+            .await;
+        self.note_network_status(&response);
+        response
+    }
+
+    async fn get_transaction_response(
+        &self,
+        tx_hash: [u8; 32],
+    ) -> Result<Option<TransactionResponse>> {
+        let response = self.adapter.get_transaction_response(tx_hash).await;
+        self.note_network_status(&response);
+        response
+    }
+
+    async fn is_squeezed_out(&self, tx_hash: [u8; 32]) -> Result<bool> {
+        let response = self.adapter.is_squeezed_out(tx_hash).await;
+        self.note_network_status(&response);
+        response
+    }
+
+    async fn get_l2_state(&self, tx_hash: [u8; 32]) -> Result<Option<Vec<StateFrame>>> {
+        let response = self.adapter.get_l2_state(tx_hash).await;
         self.note_network_status(&response);
         response
     }
@@ -136,7 +233,7 @@ mod tests {
             .expect_get_block_number()
             .returning(|| Ok(10u32.into()));
 
-        let adapter = HealthTrackingMiddleware::new(eth_adapter, 1);
+        let adapter = HealthTrackingMiddleware::new(eth_adapter, "node", 1);
         let health_check = adapter.connection_health_checker();
 
         let _ = adapter.submit(given_a_block(42)).await;
@@ -160,7 +257,7 @@ mod tests {
             .expect_get_block_number()
             .returning(|| Err(Error::Other("An error".into())));
 
-        let adapter = HealthTrackingMiddleware::new(eth_adapter, 2);
+        let adapter = HealthTrackingMiddleware::new(eth_adapter, "node", 2);
         let health_check = adapter.connection_health_checker();
 
         let _ = adapter.submit(given_a_block(42)).await;
@@ -183,7 +280,7 @@ mod tests {
             .expect_get_block_number()
             .returning(|| Err(Error::Network("An error".into())));
 
-        let adapter = HealthTrackingMiddleware::new(eth_adapter, 3);
+        let adapter = HealthTrackingMiddleware::new(eth_adapter, "node", 3);
         let health_check = adapter.connection_health_checker();
         assert!(health_check.healthy());
 
@@ -209,7 +306,7 @@ mod tests {
             .returning(|| Err(Error::Network("An error".into())));
 
         let registry = Registry::new();
-        let adapter = HealthTrackingMiddleware::new(eth_adapter, 3);
+        let adapter = HealthTrackingMiddleware::new(eth_adapter, "node", 3);
         adapter.register_metrics(&registry);
 
         let _ = adapter.submit(given_a_block(42)).await;
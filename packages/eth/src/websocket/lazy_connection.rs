@@ -0,0 +1,196 @@
+use std::num::NonZeroU32;
+
+use futures::future::BoxFuture;
+use ports::types::{
+    BaseFees, CommitTx, StateFrame, TransactionResponse, TxFees, ValidatedFuelBlock, U256,
+};
+use tokio::sync::OnceCell;
+
+use crate::{
+    error::{Error, Result},
+    state_tx::{L1Tx, PostingMode},
+    websocket::{event_streamer::EthEventStreamer, health_tracking_middleware::EthApi},
+};
+
+type Connect<T> = Box<dyn Fn() -> BoxFuture<'static, Result<T>> + Send + Sync>;
+
+/// A connection that is established on first use. Until then every call tries to connect again, so
+/// that an endpoint that was down at startup is picked up once it comes back.
+pub struct LazyConnection<T> {
+    connection: OnceCell<T>,
+    connect: Connect<T>,
+}
+
+impl<T> LazyConnection<T> {
+    pub fn new(
+        connect: impl Fn() -> BoxFuture<'static, Result<T>> + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            connection: OnceCell::new(),
+            connect: Box::new(connect),
+        }
+    }
+
+    async fn connection(&self) -> Result<&T> {
+        self.connection.get_or_try_init(|| (self.connect)()).await
+    }
+
+    fn connected(&self) -> &T {
+        self.connection
+            .get()
+            .expect("only called on established connections")
+    }
+}
+
+#[async_trait::async_trait]
+impl<T> EthApi for LazyConnection<T>
+where
+    T: EthApi + Send + Sync,
+{
+    async fn connect(&self) -> Result<()> {
+        self.connection().await?.connect().await
+    }
+
+    fn is_connected(&self) -> bool {
+        self.connection.initialized()
+    }
+
+    async fn submit(&self, block: ValidatedFuelBlock) -> Result<CommitTx> {
+        self.connection().await?.submit(block).await
+    }
+
+    async fn replace_commit(
+        &self,
+        block: ValidatedFuelBlock,
+        nonce: u64,
+        min_fees: TxFees,
+    ) -> Result<CommitTx> {
+        self.connection()
+            .await?
+            .replace_commit(block, nonce, min_fees)
+            .await
+    }
+
+    async fn get_block_number(&self) -> Result<u64> {
+        self.connection().await?.get_block_number().await
+    }
+
+    async fn get_block_hash(&self, height: u64) -> Result<Option<[u8; 32]>> {
+        self.connection().await?.get_block_hash(height).await
+    }
+
+    async fn balance(&self) -> Result<U256> {
+        self.connection().await?.balance().await
+    }
+
+    async fn base_fees(&self) -> Result<BaseFees> {
+        self.connection().await?.base_fees().await
+    }
+
+    fn commit_interval(&self) -> NonZeroU32 {
+        self.connected().commit_interval()
+    }
+
+    fn event_streamer(&self, eth_block_height: u64) -> Result<EthEventStreamer> {
+        self.connection
+            .get()
+            .ok_or_else(|| Error::Network("not connected yet".to_string()))?
+            .event_streamer(eth_block_height)
+    }
+
+    async fn submit_l2_state(&self, state_data: Vec<u8>, mode: PostingMode) -> Result<L1Tx> {
+        self.connection()
+            .await?
+            .submit_l2_state(state_data, mode)
+            .await
+    }
+
+    async fn replace_l2_state(
+        &self,
+        state_data: Vec<u8>,
+        nonce: u64,
+        min_fees: TxFees,
+        mode: PostingMode,
+    ) -> Result<L1Tx> {
+        self.connection()
+            .await?
+            .replace_l2_state(state_data, nonce, min_fees, mode)
+            .await
+    }
+
+    async fn get_transaction_response(
+        &self,
+        tx_hash: [u8; 32],
+    ) -> Result<Option<TransactionResponse>> {
+        self.connection()
+            .await?
+            .get_transaction_response(tx_hash)
+            .await
+    }
+
+    async fn is_squeezed_out(&self, tx_hash: [u8; 32]) -> Result<bool> {
+        self.connection().await?.is_squeezed_out(tx_hash).await
+    }
+
+    async fn get_l2_state(&self, tx_hash: [u8; 32]) -> Result<Option<Vec<StateFrame>>> {
+        self.connection().await?.get_l2_state(tx_hash).await
+    }
+
+    #[cfg(feature = "test-helpers")]
+    async fn finalized(&self, block: ValidatedFuelBlock) -> Result<bool> {
+        self.connection().await?.finalized(block).await
+    }
+
+    #[cfg(feature = "test-helpers")]
+    async fn block_hash_at_commit_height(&self, commit_height: u32) -> Result<[u8; 32]> {
+        self.connection()
+            .await?
+            .block_hash_at_commit_height(commit_height)
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+
+    use futures::FutureExt;
+
+    use super::*;
+    use crate::websocket::health_tracking_middleware::MockEthApi;
+
+    #[tokio::test]
+    async fn endpoints_down_at_startup_are_connected_to_once_they_are_back() {
+        // given
+        let attempts = Arc::new(AtomicUsize::new(0));
+        let connection = LazyConnection::new({
+            let attempts = Arc::clone(&attempts);
+            move || {
+                let attempt = attempts.fetch_add(1, Ordering::Relaxed);
+                async move {
+                    if attempt == 0 {
+                        return Err(Error::Network("connection refused".into()));
+                    }
+                    let mut api = MockEthApi::new();
+                    api.expect_get_block_number().returning(|| Ok(10));
+                    Ok(api)
+                }
+                .boxed()
+            }
+        });
+        let down = connection.get_block_number().await;
+
+        // when
+        let height = connection.get_block_number().await.unwrap();
+        let _ = connection.get_block_number().await.unwrap();
+
+        // then
+        assert!(matches!(down, Err(Error::Network(_))));
+        assert_eq!(height, 10);
+        assert!(connection.is_connected());
+        assert_eq!(attempts.load(Ordering::Relaxed), 2);
+    }
+}
@@ -0,0 +1,116 @@
+use std::{ops::Range, sync::Arc};
+
+use ethers::types::H160;
+use ports::storage::Storage;
+use tracing::{info, warn};
+
+use crate::error::Result;
+
+/// Hands out the nonces of a single wallet. The next nonce is kept in storage so that concurrent
+/// submissions and restarts neither reuse nor skip one.
+#[derive(Clone)]
+pub struct NonceManager {
+    storage: Arc<dyn Storage>,
+    address: H160,
+}
+
+impl NonceManager {
+    pub fn new(storage: Arc<dyn Storage>, address: H160) -> Self {
+        Self { storage, address }
+    }
+
+    /// `pending_count` is the node's view of the wallet so that txs sent from outside of the
+    /// committer are never collided with.
+    pub async fn reserve(&self, pending_count: u64) -> Result<u64> {
+        Ok(self
+            .storage
+            .reserve_nonce(self.address.to_fixed_bytes(), pending_count)
+            .await?)
+    }
+
+    /// Aligns the stored nonce with the node's `latest` and `pending` tx counts. Returns the
+    /// nonces that were handed out but never reached the node. They need to be filled before any
+    /// later tx can be mined.
+    pub async fn reconcile(&self, latest_count: u64, pending_count: u64) -> Result<Range<u64>> {
+        let address = self.address.to_fixed_bytes();
+
+        match self.storage.next_nonce(address).await? {
+            Some(next_nonce) if next_nonce > pending_count => {
+                warn!(
+                    "nonces {pending_count}..{next_nonce} of {:?} were reserved but never reached the node",
+                    self.address
+                );
+                Ok(pending_count..next_nonce)
+            }
+            stored => {
+                if let Some(next_nonce) = stored.filter(|nonce| *nonce < latest_count) {
+                    warn!(
+                        "{:?} sent txs outside of the committer, moving nonce from {next_nonce} to {pending_count}",
+                        self.address
+                    );
+                }
+                if latest_count < pending_count {
+                    info!(
+                        "{} txs of {:?} are waiting in the mempool",
+                        pending_count - latest_count,
+                        self.address
+                    );
+                }
+
+                self.storage.set_next_nonce(address, pending_count).await?;
+
+                Ok(pending_count..pending_count)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mockall::predicate;
+    use ports::storage::MockStorage;
+
+    use super::*;
+
+    const ADDRESS: [u8; 20] = [1; 20];
+
+    fn given_storage_with_next_nonce(next_nonce: Option<u64>) -> MockStorage {
+        let mut storage = MockStorage::new();
+        storage
+            .expect_next_nonce()
+            .with(predicate::eq(ADDRESS))
+            .return_once(move |_| Box::pin(async move { Ok(next_nonce) }));
+
+        storage
+    }
+
+    #[tokio::test]
+    async fn reconcile_reports_nonces_that_never_reached_the_node() {
+        // given
+        let storage = given_storage_with_next_nonce(Some(8));
+        let nonce_manager = NonceManager::new(Arc::new(storage), H160::from(ADDRESS));
+
+        // when
+        let gaps = nonce_manager.reconcile(4, 5).await.unwrap();
+
+        // then
+        assert_eq!(gaps, 5..8);
+    }
+
+    #[tokio::test]
+    async fn reconcile_catches_up_with_the_node() {
+        // given
+        let mut storage = given_storage_with_next_nonce(Some(3));
+        storage
+            .expect_set_next_nonce()
+            .with(predicate::eq(ADDRESS), predicate::eq(5))
+            .return_once(|_, _| Box::pin(async { Ok(()) }));
+        let nonce_manager = NonceManager::new(Arc::new(storage), H160::from(ADDRESS));
+
+        // when
+        let gaps = nonce_manager.reconcile(5, 5).await.unwrap();
+
+        // then
+        assert!(gaps.is_empty());
+    }
+}
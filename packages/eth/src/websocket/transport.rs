@@ -0,0 +1,113 @@
+use ethers::{
+    providers::{
+        Http, HttpClientError, JsonRpcClient, JsonRpcError, ProviderError, PubsubClient, RpcError,
+        Ws, WsClientError,
+    },
+    types::U256,
+};
+use serde::{de::DeserializeOwned, Serialize};
+use url::Url;
+
+use crate::error::{Error, Result};
+
+/// JSON-RPC transport of the L1 adapter, chosen by the scheme of the RPC url.
+#[derive(Debug, Clone)]
+pub enum Transport {
+    Ws(Ws),
+    /// Has no subscriptions, events are polled for instead.
+    Http(Http),
+}
+
+impl Transport {
+    pub async fn connect(url: &Url) -> Result<Self> {
+        match url.scheme() {
+            "ws" | "wss" => Ws::connect(url.as_str())
+                .await
+                .map(Self::Ws)
+                .map_err(|e| Error::Network(e.to_string())),
+            "http" | "https" => Ok(Self::Http(Http::new(url.clone()))),
+            scheme => Err(Error::Other(format!(
+                "unsupported rpc url scheme `{scheme}`, expected one of ws, wss, http or https"
+            ))),
+        }
+    }
+
+    pub fn supports_subscriptions(&self) -> bool {
+        matches!(self, Self::Ws(_))
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum TransportError {
+    #[error(transparent)]
+    Ws(#[from] WsClientError),
+    #[error(transparent)]
+    Http(#[from] HttpClientError),
+    #[error("subscriptions are not supported over http")]
+    SubscriptionsUnsupported,
+}
+
+impl RpcError for TransportError {
+    fn as_error_response(&self) -> Option<&JsonRpcError> {
+        match self {
+            Self::Ws(err) => err.as_error_response(),
+            Self::Http(err) => err.as_error_response(),
+            Self::SubscriptionsUnsupported => None,
+        }
+    }
+
+    fn as_serde_error(&self) -> Option<&serde_json::Error> {
+        match self {
+            Self::Ws(err) => err.as_serde_error(),
+            Self::Http(err) => err.as_serde_error(),
+            Self::SubscriptionsUnsupported => None,
+        }
+    }
+}
+
+impl From<TransportError> for ProviderError {
+    fn from(err: TransportError) -> Self {
+        match err {
+            TransportError::Ws(err) => err.into(),
+            TransportError::Http(err) => err.into(),
+            err => Self::JsonRpcClientError(Box::new(err)),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl JsonRpcClient for Transport {
+    type Error = TransportError;
+
+    async fn request<T, R>(&self, method: &str, params: T) -> std::result::Result<R, Self::Error>
+    where
+        T: std::fmt::Debug + Serialize + Send + Sync,
+        R: DeserializeOwned + Send,
+    {
+        match self {
+            Self::Ws(ws) => Ok(JsonRpcClient::request(ws, method, params).await?),
+            Self::Http(http) => Ok(JsonRpcClient::request(http, method, params).await?),
+        }
+    }
+}
+
+impl PubsubClient for Transport {
+    type NotificationStream = <Ws as PubsubClient>::NotificationStream;
+
+    fn subscribe<T: Into<U256>>(
+        &self,
+        id: T,
+    ) -> std::result::Result<Self::NotificationStream, Self::Error> {
+        match self {
+            Self::Ws(ws) => Ok(ws.subscribe(id)?),
+            Self::Http(_) => Err(TransportError::SubscriptionsUnsupported),
+        }
+    }
+
+    fn unsubscribe<T: Into<U256>>(&self, id: T) -> std::result::Result<(), Self::Error> {
+        match self {
+            Self::Ws(ws) => Ok(ws.unsubscribe(id)?),
+            Self::Http(_) => Err(TransportError::SubscriptionsUnsupported),
+        }
+    }
+}
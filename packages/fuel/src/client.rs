@@ -1,29 +1,73 @@
-use fuel_core_client::client::{types::Block, FuelClient as GqlClient};
+use std::{
+    num::{NonZeroU32, NonZeroUsize},
+    ops::RangeInclusive,
+    time::Duration,
+};
+
+use cynic::QueryBuilder;
+use fuel_core_client::client::{
+    pagination::{PageDirection, PaginationRequest},
+    types::Block,
+    FuelClient as GqlClient,
+};
+use futures::{stream, Future, Stream, StreamExt, TryStreamExt};
 use metrics::{
     prometheus::core::Collector, ConnectionHealthTracker, HealthChecker, RegistersMetrics,
 };
+use ports::fuel::FullFuelBlock;
+use tokio::sync::mpsc;
 use url::Url;
 
-use crate::{metrics::Metrics, Error, Result};
+use crate::{full_block::FullBlocksQuery, metrics::Metrics, Error, Result};
+
+/// Upper bound on `block_page_size`, keeping page requests within what fuel-core serves in one
+/// response.
+pub const MAX_BLOCK_PAGE_SIZE: u32 = 1_000;
+
+/// How long to wait by default before asking again for a block that wasn't produced yet.
+const DEFAULT_BLOCK_POLL_INTERVAL: Duration = Duration::from_secs(1);
+/// Blocks fetched ahead of the consumer.
+const BLOCK_BUFFER: usize = 100;
 
 #[derive(Clone)]
 pub struct HttpClient {
     client: GqlClient,
+    block_page_size: NonZeroU32,
+    block_page_concurrency: NonZeroUsize,
+    block_poll_interval: Duration,
     metrics: Metrics,
     health_tracker: ConnectionHealthTracker,
 }
 
 impl HttpClient {
+    /// Block ranges are fetched `block_page_size` blocks per request, with up to
+    /// `block_page_concurrency` requests in flight.
     #[must_use]
-    pub fn new(url: &Url, unhealthy_after_n_errors: usize) -> Self {
+    pub fn new(
+        url: &Url,
+        unhealthy_after_n_errors: usize,
+        block_page_size: NonZeroU32,
+        block_page_concurrency: NonZeroUsize,
+    ) -> Self {
         let client = GqlClient::new(url).expect("Url to be well formed");
         Self {
             client,
+            block_page_size,
+            block_page_concurrency,
+            block_poll_interval: DEFAULT_BLOCK_POLL_INTERVAL,
             metrics: Metrics::default(),
             health_tracker: ConnectionHealthTracker::new(unhealthy_after_n_errors),
         }
     }
 
+    /// How long `poll_blocks_from` waits before asking again for a block that wasn't produced
+    /// yet, or after a failed request.
+    #[must_use]
+    pub fn with_block_poll_interval(mut self, block_poll_interval: Duration) -> Self {
+        self.block_poll_interval = block_poll_interval;
+        self
+    }
+
     #[cfg(feature = "test-helpers")]
     pub async fn produce_blocks(&self, num: u32) -> Result<()> {
         self.client
@@ -52,7 +96,7 @@ impl HttpClient {
         match self.client.block_by_height(height.into()).await {
             Ok(maybe_block) => {
                 self.handle_network_success();
-                Ok(maybe_block.map(Into::into))
+                Ok(maybe_block)
             }
             Err(err) => {
                 self.handle_network_error();
@@ -74,6 +118,122 @@ impl HttpClient {
         }
     }
 
+    pub(crate) async fn _blocks_in_height_range(
+        &self,
+        range: RangeInclusive<u32>,
+    ) -> Result<Vec<Block>> {
+        let end = *range.end();
+        let blocks = self
+            .paginated(range, |request| self.blocks_page(request))
+            .await?;
+
+        Ok(blocks
+            .into_iter()
+            .filter(|block| block.header.height <= end)
+            .collect())
+    }
+
+    pub(crate) async fn _full_blocks_in_height_range(
+        &self,
+        range: RangeInclusive<u32>,
+    ) -> Result<Vec<FullFuelBlock>> {
+        let end = *range.end();
+        let blocks = self
+            .paginated(range, |request| self.full_blocks_page(request))
+            .await?;
+
+        Ok(blocks
+            .into_iter()
+            .filter(|full_block| full_block.block.header.height <= end)
+            .collect())
+    }
+
+    /// Fetches `range` in pages of `block_page_size` blocks, with up to `block_page_concurrency`
+    /// requests in flight.
+    async fn paginated<T, F, Fut>(
+        &self,
+        range: RangeInclusive<u32>,
+        fetch_page: F,
+    ) -> Result<Vec<T>>
+    where
+        F: Fn(PaginationRequest<String>) -> Fut,
+        Fut: Future<Output = Result<Vec<T>>>,
+    {
+        let pages: Vec<Vec<T>> = stream::iter(page_requests(range, self.block_page_size.get()))
+            .map(fetch_page)
+            .buffered(self.block_page_concurrency.get())
+            .try_collect()
+            .await?;
+
+        Ok(pages.into_iter().flatten().collect())
+    }
+
+    async fn blocks_page(&self, request: PaginationRequest<String>) -> Result<Vec<Block>> {
+        match self.client.blocks(request).await {
+            Ok(page) => {
+                self.handle_network_success();
+                Ok(page.results)
+            }
+            Err(err) => {
+                self.handle_network_error();
+                Err(Error::Network(err.to_string()))
+            }
+        }
+    }
+
+    async fn full_blocks_page(
+        &self,
+        request: PaginationRequest<String>,
+    ) -> Result<Vec<FullFuelBlock>> {
+        let query = FullBlocksQuery::build(request.into());
+        match self.client.query(query).await {
+            Ok(response) => {
+                self.handle_network_success();
+                response
+                    .blocks
+                    .edges
+                    .into_iter()
+                    .map(|edge| FullFuelBlock::try_from(edge.node))
+                    .collect()
+            }
+            Err(err) => {
+                self.handle_network_error();
+                Err(Error::Network(err.to_string()))
+            }
+        }
+    }
+
+    /// fuel-core offers no subscription to new blocks, so a background task follows the chain by
+    /// asking for the next height every `block_poll_interval` until the stream is dropped. Failed
+    /// requests are retried at the same pace, they are already accounted for by the health
+    /// tracker. Unlike `latest_block` this needs no `chain_info` call.
+    pub(crate) fn _poll_blocks_from(
+        &self,
+        height: u32,
+    ) -> impl Stream<Item = Result<Block>> + Send + Sync {
+        let (sender, receiver) = mpsc::channel(BLOCK_BUFFER);
+        let client = self.clone();
+
+        tokio::spawn(async move {
+            let mut height = height;
+            while !sender.is_closed() {
+                match client._block_at_height(height).await {
+                    Ok(Some(block)) => {
+                        if sender.send(Ok(block)).await.is_err() {
+                            return;
+                        }
+                        height += 1;
+                    }
+                    Ok(None) | Err(_) => tokio::time::sleep(client.block_poll_interval).await,
+                }
+            }
+        });
+
+        stream::unfold(receiver, |mut receiver| async move {
+            receiver.recv().await.map(|block| (block, receiver))
+        })
+    }
+
     #[must_use]
     pub fn connection_health_checker(&self) -> HealthChecker {
         self.health_tracker.tracker()
@@ -89,8 +249,55 @@ impl HttpClient {
     }
 }
 
+/// Splits `range` into requests of at most `page_size` blocks. The cursor of the `blocks` query
+/// is the height of the block preceding the page. `page_size` is bounded by `MAX_BLOCK_PAGE_SIZE`,
+/// so the page lengths fit into the `i32` the query takes.
+fn page_requests(range: RangeInclusive<u32>, page_size: u32) -> Vec<PaginationRequest<String>> {
+    let (start, end) = (u64::from(*range.start()), u64::from(*range.end()));
+
+    (start..=end)
+        .step_by(page_size as usize)
+        .map(|page_start| {
+            let page_end = end.min(page_start + u64::from(page_size) - 1);
+            PaginationRequest {
+                cursor: page_start.checked_sub(1).map(|height| height.to_string()),
+                results: (page_end - page_start + 1) as i32,
+                direction: PageDirection::Forward,
+            }
+        })
+        .collect()
+}
+
 impl RegistersMetrics for HttpClient {
     fn metrics(&self) -> Vec<Box<dyn Collector>> {
         self.metrics.metrics()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn splits_ranges_into_pages() {
+        // given
+        let range = 0..=24;
+
+        // when
+        let requests = page_requests(range, 10);
+
+        // then
+        let pages: Vec<_> = requests
+            .into_iter()
+            .map(|request| (request.cursor, request.results))
+            .collect();
+        assert_eq!(
+            pages,
+            vec![
+                (None, 10),
+                (Some("9".to_string()), 10),
+                (Some("19".to_string()), 5)
+            ]
+        );
+    }
+}
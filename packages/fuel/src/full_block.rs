@@ -0,0 +1,88 @@
+//! `blocks` query that also returns the payload of every transaction, so that a page of full
+//! blocks takes a single request. fuel-core-client only offers blocks with transaction ids.
+
+use fuel_core_client::client::{
+    schema::{
+        block::{BlockVersion, Consensus, Header},
+        schema, BlockId, ConnectionArgs, HexString, TransactionId,
+    },
+    types::Block,
+};
+use ports::fuel::FullFuelBlock;
+
+use crate::Error;
+
+#[derive(cynic::QueryFragment, Clone, Debug)]
+#[cynic(
+    schema_path = "./assets/schema.sdl",
+    graphql_type = "Query",
+    variables = "ConnectionArgs"
+)]
+pub struct FullBlocksQuery {
+    #[arguments(after: $after, before: $before, first: $first, last: $last)]
+    pub blocks: FullBlockConnection,
+}
+
+#[derive(cynic::QueryFragment, Clone, Debug)]
+#[cynic(schema_path = "./assets/schema.sdl", graphql_type = "BlockConnection")]
+pub struct FullBlockConnection {
+    pub edges: Vec<FullBlockEdge>,
+}
+
+#[derive(cynic::QueryFragment, Clone, Debug)]
+#[cynic(schema_path = "./assets/schema.sdl", graphql_type = "BlockEdge")]
+pub struct FullBlockEdge {
+    pub node: FullBlock,
+}
+
+#[derive(cynic::QueryFragment, Clone, Debug)]
+#[cynic(schema_path = "./assets/schema.sdl", graphql_type = "Block")]
+pub struct FullBlock {
+    pub version: BlockVersion,
+    pub id: BlockId,
+    pub header: Header,
+    pub consensus: Consensus,
+    pub transaction_ids: Vec<TransactionId>,
+    pub transactions: Vec<RawTransaction>,
+}
+
+#[derive(cynic::QueryFragment, Clone, Debug)]
+#[cynic(schema_path = "./assets/schema.sdl", graphql_type = "Transaction")]
+pub struct RawTransaction {
+    pub raw_payload: HexString,
+}
+
+impl TryFrom<FullBlock> for FullFuelBlock {
+    type Error = Error;
+
+    fn try_from(full_block: FullBlock) -> crate::Result<Self> {
+        let FullBlock {
+            version,
+            id,
+            header,
+            consensus,
+            transaction_ids,
+            transactions,
+        } = full_block;
+
+        let block = Block::try_from(fuel_core_client::client::schema::block::Block {
+            version,
+            id,
+            header,
+            consensus,
+            transaction_ids,
+        })
+        .map_err(|e| Error::Network(format!("fuel node served an invalid block: {e}")))?;
+
+        // the payloads are taken as served so they can be checked against the transactions root
+        let raw_transactions = transactions
+            .into_iter()
+            .map(|transaction| transaction.raw_payload.into())
+            .collect();
+
+        Ok(Self {
+            block,
+            raw_transactions,
+        })
+    }
+}
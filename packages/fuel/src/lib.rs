@@ -1,6 +1,12 @@
 #![deny(unused_crate_dependencies)]
-use ports::fuel::FuelBlock;
+use std::{ops::RangeInclusive, pin::Pin};
+
+use ports::{
+    fuel::{FuelBlock, FullFuelBlock},
+    types::Stream,
+};
 mod client;
+mod full_block;
 mod metrics;
 
 pub use client::*;
@@ -17,6 +23,27 @@ impl ports::fuel::Api for client::HttpClient {
     async fn latest_block(&self) -> ports::fuel::Result<FuelBlock> {
         self._latest_block().await
     }
+
+    async fn blocks_in_height_range(
+        &self,
+        range: RangeInclusive<u32>,
+    ) -> ports::fuel::Result<Vec<FuelBlock>> {
+        self._blocks_in_height_range(range).await
+    }
+
+    async fn full_blocks_in_height_range(
+        &self,
+        range: RangeInclusive<u32>,
+    ) -> ports::fuel::Result<Vec<FullFuelBlock>> {
+        self._full_blocks_in_height_range(range).await
+    }
+
+    fn poll_blocks_from(
+        &self,
+        height: u32,
+    ) -> Pin<Box<dyn Stream<Item = ports::fuel::Result<FuelBlock>> + Send + Sync>> {
+        Box::pin(self._poll_blocks_from(height))
+    }
 }
 
 #[cfg(test)]
@@ -25,6 +52,12 @@ mod tests {
         prometheus::{proto::Metric, Registry},
         RegistersMetrics,
     };
+    use std::{
+        num::{NonZeroU32, NonZeroUsize},
+        time::Duration,
+    };
+
+    use futures::StreamExt;
     use ports::fuel::Api;
     use url::Url;
 
@@ -89,7 +122,7 @@ mod tests {
         // killing the node once the SDK supports it.
         let url = Url::parse("localhost:12344").unwrap();
 
-        let fuel_adapter = HttpClient::new(&url, 1);
+        let fuel_adapter = HttpClient::new(&url, 1, NonZeroU32::MIN, NonZeroUsize::MIN);
 
         let registry = Registry::default();
         fuel_adapter.register_metrics(&registry);
@@ -110,13 +143,38 @@ mod tests {
         assert_eq!(network_errors_metric.get_value(), 1f64);
     }
 
+    #[tokio::test]
+    async fn block_polling_outlives_network_errors() {
+        // given
+        let url = Url::parse("http://localhost:12344").unwrap();
+        let fuel_adapter = HttpClient::new(&url, 1, NonZeroU32::MIN, NonZeroUsize::MIN)
+            .with_block_poll_interval(Duration::from_millis(10));
+        let registry = Registry::default();
+        fuel_adapter.register_metrics(&registry);
+
+        // when
+        let mut blocks = fuel_adapter.poll_blocks_from(1);
+        let next = tokio::time::timeout(Duration::from_millis(200), blocks.next()).await;
+
+        // then
+        assert!(next.is_err(), "stream should neither end nor yield");
+        let network_errors = registry
+            .gather()
+            .iter()
+            .find(|metric| metric.get_name() == "fuel_network_errors")
+            .and_then(|metric| metric.get_metric().first())
+            .map(|metric| metric.get_counter().get_value())
+            .unwrap();
+        assert!(network_errors > 1f64);
+    }
+
     #[tokio::test]
     async fn correctly_tracks_network_health() {
         // temporary 'fake' address to cause a network error the same effect will be achieved by
         // killing the node once the SDK supports it.
         let url = Url::parse("http://localhost:12344").unwrap();
 
-        let fuel_adapter = client::HttpClient::new(&url, 3);
+        let fuel_adapter = client::HttpClient::new(&url, 3, NonZeroU32::MIN, NonZeroUsize::MIN);
         let health_check = fuel_adapter.connection_health_checker();
 
         assert!(health_check.healthy());
@@ -7,8 +7,11 @@ mod ports {
 
     #[cfg(feature = "storage")]
     pub mod storage;
+
+    #[cfg(feature = "da")]
+    pub mod da;
 }
 
-#[cfg(any(feature = "l1", feature = "fuel", feature = "storage"))]
+#[cfg(any(feature = "l1", feature = "fuel", feature = "storage", feature = "da"))]
 pub use ports::*;
 pub mod types;
@@ -0,0 +1,156 @@
+use crate::types::{BaseFees, DaSubmission, L1Height, StateFrame, SubmissionStatus};
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("network error: {0}")]
+    Network(String),
+    #[error("{0}")]
+    Other(String),
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// A layer the state of fuel blocks is made available on.
+#[cfg_attr(feature = "test-helpers", mockall::automock)]
+#[async_trait::async_trait]
+pub trait Api: Send + Sync {
+    /// Current height of the layer, used to tell how long a submission has been pending.
+    async fn height(&self) -> Result<L1Height>;
+    async fn base_fees(&self) -> Result<BaseFees>;
+    /// Most bytes of framed state a single submission carries.
+    fn max_payload_size(&self) -> usize;
+    /// Bytes the layer charges for as a whole, e.g. an Ethereum blob. Payloads are worth posting
+    /// once they fill a number of these.
+    fn payload_unit(&self) -> usize;
+    /// Largest fragment of block state that fits into a submission on its own.
+    fn max_fragment_size(&self) -> usize;
+    /// Posts the oldest of the frames that fit into a single submission, framed so that readers
+    /// can reassemble the block states. Returns the submission along with the number of frames
+    /// it carries.
+    async fn submit(&self, frames: Vec<StateFrame>) -> Result<(DaSubmission, usize)>;
+    /// The most a replacement of `submission` may cost, in the smallest unit of the fee token of
+    /// the layer.
+    fn replacement_fee(&self, submission: &DaSubmission) -> Result<u128>;
+    /// Resends the `frames` of `submission`, paying enough for the layer to take the replacement
+    /// in its place.
+    async fn replace(
+        &self,
+        frames: Vec<StateFrame>,
+        submission: &DaSubmission,
+    ) -> Result<DaSubmission>;
+    async fn confirm(&self, tx_hash: [u8; 32]) -> Result<SubmissionStatus>;
+    /// The frames carried by the submission, `None` if the layer doesn't know of it.
+    async fn retrieve(&self, tx_hash: [u8; 32]) -> Result<Option<Vec<StateFrame>>>;
+}
+
+#[cfg(feature = "test-helpers")]
+pub use in_memory::InMemory;
+
+#[cfg(feature = "test-helpers")]
+mod in_memory {
+    use std::{
+        collections::HashMap,
+        sync::{Arc, Mutex},
+    };
+
+    use super::{Api, Result};
+    use crate::types::{
+        BaseFees, DaSubmission, L1Height, StateFrame, SubmissionStatus, TransactionResponse,
+    };
+
+    const PAYLOAD_UNIT: usize = 128 * 1024;
+    const MAX_PAYLOAD_SIZE: usize = 6 * PAYLOAD_UNIT;
+
+    /// Keeps submissions in memory and includes each of them right away.
+    #[derive(Debug, Clone, Default)]
+    pub struct InMemory {
+        submissions: Arc<Mutex<HashMap<[u8; 32], Vec<StateFrame>>>>,
+        base_fees: BaseFees,
+    }
+
+    impl InMemory {
+        pub fn new(base_fees: BaseFees) -> Self {
+            Self {
+                submissions: Arc::default(),
+                base_fees,
+            }
+        }
+
+        fn store(&self, frames: Vec<StateFrame>) -> DaSubmission {
+            let mut submissions = self.submissions.lock().expect("lock not poisoned");
+
+            let mut hash = [0; 32];
+            hash[..8].copy_from_slice(&(submissions.len() as u64 + 1).to_be_bytes());
+            submissions.insert(hash, frames);
+
+            DaSubmission {
+                hash,
+                details: vec![],
+            }
+        }
+
+        fn num_submissions(&self) -> u32 {
+            self.submissions.lock().expect("lock not poisoned").len() as u32
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl Api for InMemory {
+        async fn height(&self) -> Result<L1Height> {
+            Ok(self.num_submissions().into())
+        }
+
+        async fn base_fees(&self) -> Result<BaseFees> {
+            Ok(self.base_fees)
+        }
+
+        fn max_payload_size(&self) -> usize {
+            MAX_PAYLOAD_SIZE
+        }
+
+        fn payload_unit(&self) -> usize {
+            PAYLOAD_UNIT
+        }
+
+        fn max_fragment_size(&self) -> usize {
+            PAYLOAD_UNIT - StateFrame::PAYLOAD_HEADER_SIZE - StateFrame::HEADER_SIZE
+        }
+
+        async fn submit(&self, frames: Vec<StateFrame>) -> Result<(DaSubmission, usize)> {
+            let num_frames = frames.len();
+            Ok((self.store(frames), num_frames))
+        }
+
+        fn replacement_fee(&self, _: &DaSubmission) -> Result<u128> {
+            Ok(0)
+        }
+
+        async fn replace(&self, frames: Vec<StateFrame>, _: &DaSubmission) -> Result<DaSubmission> {
+            Ok(self.store(frames))
+        }
+
+        async fn confirm(&self, tx_hash: [u8; 32]) -> Result<SubmissionStatus> {
+            let included = self
+                .submissions
+                .lock()
+                .expect("lock not poisoned")
+                .contains_key(&tx_hash);
+
+            Ok(if included {
+                let height = u64::from(self.num_submissions());
+                SubmissionStatus::Included(TransactionResponse::new(height, [0; 32], true, 0, 0))
+            } else {
+                SubmissionStatus::Dropped
+            })
+        }
+
+        async fn retrieve(&self, tx_hash: [u8; 32]) -> Result<Option<Vec<StateFrame>>> {
+            Ok(self
+                .submissions
+                .lock()
+                .expect("lock not poisoned")
+                .get(&tx_hash)
+                .cloned())
+        }
+    }
+}
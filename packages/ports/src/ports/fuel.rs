@@ -1,3 +1,5 @@
+use std::{ops::RangeInclusive, pin::Pin};
+
 pub use fuel_core_client::client::types::{
     block::{
         Block as FuelBlock, Consensus as FuelConsensus, Header as FuelHeader,
@@ -6,6 +8,8 @@ pub use fuel_core_client::client::types::{
     primitives::{BlockId as FuelBlockId, Bytes32 as FuelBytes32, PublicKey as FuelPublicKey},
 };
 
+use crate::types::Stream;
+
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
     #[error("{0}")]
@@ -14,9 +18,30 @@ pub enum Error {
 
 pub type Result<T> = std::result::Result<T, Error>;
 
+/// A fuel block along with its canonically encoded transactions, in block order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FullFuelBlock {
+    pub block: FuelBlock,
+    pub raw_transactions: Vec<Vec<u8>>,
+}
+
 #[cfg_attr(feature = "test-helpers", mockall::automock)]
 #[async_trait::async_trait]
 pub trait Api: Send + Sync {
     async fn block_at_height(&self, height: u32) -> Result<Option<FuelBlock>>;
     async fn latest_block(&self) -> Result<FuelBlock>;
+    /// Blocks in `range`, ordered by height. Blocks past the tip of the chain are left out.
+    async fn blocks_in_height_range(&self, range: RangeInclusive<u32>) -> Result<Vec<FuelBlock>>;
+    /// Like `blocks_in_height_range`, but the transactions come along with each block.
+    async fn full_blocks_in_height_range(
+        &self,
+        range: RangeInclusive<u32>,
+    ) -> Result<Vec<FullFuelBlock>>;
+    /// Blocks from `height` onwards, in order. The node offers no subscription to new blocks, so
+    /// they are found by polling it for the next height. The stream never ends on its own, failed
+    /// requests are retried until it is dropped.
+    fn poll_blocks_from(
+        &self,
+        height: u32,
+    ) -> Pin<Box<dyn Stream<Item = Result<FuelBlock>> + Send + Sync>>;
 }
@@ -1,7 +1,8 @@
 use std::pin::Pin;
 
 use crate::types::{
-    FuelBlockCommittedOnL1, InvalidL1Height, L1Height, Stream, ValidatedFuelBlock, U256,
+    CommitTx, FuelBlockCommittedOnL1, InvalidL1Height, L1Height, Stream, TransactionResponse,
+    TxFees, ValidatedFuelBlock, U256,
 };
 
 #[derive(Debug, thiserror::Error)]
@@ -23,17 +24,32 @@ impl From<InvalidL1Height> for Error {
 #[cfg_attr(feature = "test-helpers", mockall::automock)]
 #[async_trait::async_trait]
 pub trait Contract: Send + Sync {
-    async fn submit(&self, block: ValidatedFuelBlock) -> Result<()>;
-    fn event_streamer(&self, height: L1Height) -> Box<dyn EventStreamer + Send + Sync>;
+    async fn submit(&self, block: ValidatedFuelBlock) -> Result<CommitTx>;
+    /// Sends the commit of `block` in place of the pending commit tx with the given `nonce`,
+    /// paying at least `min_fees`.
+    async fn replace_commit(
+        &self,
+        block: ValidatedFuelBlock,
+        nonce: u64,
+        min_fees: TxFees,
+    ) -> Result<CommitTx>;
+    fn event_streamer(&self, height: L1Height) -> Result<Box<dyn EventStreamer + Send + Sync>>;
     fn commit_interval(&self) -> std::num::NonZeroU32;
 }
 
 #[cfg_attr(feature = "test-helpers", mockall::automock)]
 #[async_trait::async_trait]
 pub trait Api {
-    async fn submit_l2_state(&self, state_data: Vec<u8>) -> Result<[u8; 32]>;
     async fn get_block_number(&self) -> Result<L1Height>;
+    /// Hash of the canonical block at `height`, `None` if there is no such block yet.
+    async fn get_block_hash(&self, height: L1Height) -> Result<Option<[u8; 32]>>;
     async fn balance(&self) -> Result<U256>;
+    async fn get_transaction_response(
+        &self,
+        tx_hash: [u8; 32],
+    ) -> Result<Option<TransactionResponse>>;
+    /// `true` if the node knows of neither a receipt nor a mempool entry for the tx.
+    async fn is_squeezed_out(&self, tx_hash: [u8; 32]) -> Result<bool>;
 }
 
 #[cfg_attr(feature = "test-helpers", mockall::automock)]
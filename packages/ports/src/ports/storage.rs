@@ -1,6 +1,9 @@
 use std::sync::Arc;
 
-use crate::types::{BlockSubmission, StateFragment, StateFragmentId, StateSubmission};
+use crate::types::{
+    BlockSubmission, CommitLocation, CommitTx, L1Height, StateFragment, StateFragmentId,
+    StateSubmission, SubmissionTx, TransactionResponse, TransactionState,
+};
 
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
@@ -19,18 +22,70 @@ pub trait Storage: Send + Sync {
     async fn insert(&self, submission: BlockSubmission) -> Result<()>;
     async fn submission_w_latest_block(&self) -> Result<Option<BlockSubmission>>;
     async fn set_submission_completed(&self, fuel_block_hash: [u8; 32]) -> Result<BlockSubmission>;
+    /// Bumps the attempt count and drops the receipt of the previous commit tx. The tx itself is
+    /// kept until it gets replaced, so that its fate can still be learned.
+    async fn record_resubmission(
+        &self,
+        fuel_block_hash: [u8; 32],
+        retry_at: L1Height,
+    ) -> Result<BlockSubmission>;
+    async fn record_commit_event(
+        &self,
+        fuel_block_hash: [u8; 32],
+        location: CommitLocation,
+    ) -> Result<BlockSubmission>;
+    /// Forgets the commit location and marks the submission incomplete again.
+    async fn revert_commit_event(&self, fuel_block_hash: [u8; 32]) -> Result<BlockSubmission>;
+    /// Like `revert_commit_event`, but also drops the receipt and schedules a resubmission.
+    async fn requeue_submission(
+        &self,
+        fuel_block_hash: [u8; 32],
+        retry_at: L1Height,
+    ) -> Result<BlockSubmission>;
+    async fn submissions_committed_since(
+        &self,
+        l1_height: L1Height,
+    ) -> Result<Vec<BlockSubmission>>;
+    async fn set_submission_tx(&self, fuel_block_hash: [u8; 32], tx: CommitTx) -> Result<()>;
+    /// Incomplete submissions whose commit wasn't seen landing on L1 yet, lowest block first.
+    async fn submissions_awaiting_commit(&self) -> Result<Vec<BlockSubmission>>;
+    /// Submissions whose commit tx was sent but not yet seen included in a block.
+    async fn submissions_awaiting_receipt(&self) -> Result<Vec<BlockSubmission>>;
+    async fn set_submission_receipt(
+        &self,
+        fuel_block_hash: [u8; 32],
+        receipt: TransactionResponse,
+    ) -> Result<BlockSubmission>;
 
     async fn insert_state(
         &self,
         state: StateSubmission,
         fragments: Vec<StateFragment>,
     ) -> Result<()>;
-    async fn get_unsubmitted_fragments(&self) -> Result<Vec<StateFragment>>;
+    /// Oldest unsubmitted fragments whose data adds up to at most `max_total_size` bytes.
+    async fn get_unsubmitted_fragments(&self, max_total_size: usize) -> Result<Vec<StateFragment>>;
     async fn record_pending_tx(
         &self,
-        tx_hash: [u8; 32],
+        tx: SubmissionTx,
         fragment_ids: Vec<StateFragmentId>,
     ) -> Result<()>;
+    async fn get_tx_fragments(&self, tx_hash: [u8; 32]) -> Result<Vec<StateFragment>>;
     async fn has_pending_txs(&self) -> Result<bool>;
+    async fn get_pending_txs(&self) -> Result<Vec<SubmissionTx>>;
+    /// The state tx with the given hash, whatever its state.
+    async fn submission_tx(&self, tx_hash: [u8; 32]) -> Result<Option<SubmissionTx>>;
+    async fn update_submission_tx_state(
+        &self,
+        hash: [u8; 32],
+        state: TransactionState,
+    ) -> Result<()>;
     async fn state_submission_w_latest_block(&self) -> Result<Option<StateSubmission>>;
+    async fn state_submission(&self, fuel_block_hash: [u8; 32]) -> Result<Option<StateSubmission>>;
+    /// Up to `limit` heights in `from..=to`, lowest first, for which no state was imported yet.
+    async fn missing_state_heights(&self, from: u32, to: u32, limit: usize) -> Result<Vec<u32>>;
+
+    /// Hands out the next nonce of `address`, never lower than `min_nonce`.
+    async fn reserve_nonce(&self, address: [u8; 20], min_nonce: u64) -> Result<u64>;
+    async fn next_nonce(&self, address: [u8; 20]) -> Result<Option<u64>>;
+    async fn set_next_nonce(&self, address: [u8; 20], nonce: u64) -> Result<()>;
 }
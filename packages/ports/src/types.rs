@@ -1,18 +1,30 @@
 #[cfg(feature = "l1")]
 pub use ethers_core::types::{H160, U256};
-#[cfg(feature = "l1")]
+#[cfg(any(feature = "l1", feature = "fuel"))]
 pub use futures::Stream;
 
 mod block_submission;
+mod compression;
+mod da_submission;
 #[cfg(feature = "l1")]
 mod fuel_block_committed_on_l1;
+#[cfg(feature = "fuel")]
+mod fuel_block_state;
 mod l1_height;
+mod l1_tx;
 mod state_submission;
+mod transaction_response;
 
 pub use block_submission::*;
+pub use compression::*;
+pub use da_submission::*;
 #[cfg(feature = "l1")]
 pub use fuel_block_committed_on_l1::*;
+#[cfg(feature = "fuel")]
+pub use fuel_block_state::*;
 pub use l1_height::*;
+pub use l1_tx::*;
 pub use state_submission::*;
+pub use transaction_response::*;
 #[cfg(any(feature = "fuel", feature = "l1"))]
 pub use validator::block::*;
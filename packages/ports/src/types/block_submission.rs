@@ -1,4 +1,4 @@
-use crate::types::L1Height;
+use crate::types::{L1Height, TransactionResponse, TxFees};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct BlockSubmission {
@@ -8,6 +8,61 @@ pub struct BlockSubmission {
     // L1 block height moments before submitting the fuel block. Used to filter stale events in
     // the commit listener.
     pub submittal_height: L1Height,
+    /// Hash of the commit tx, once it was sent.
+    pub tx_hash: Option<[u8; 32]>,
+    /// Nonce of the commit tx, once it was sent. Replacements of the tx reuse it.
+    pub tx_nonce: Option<u64>,
+    /// Fees the commit tx was sent with. Replacements have to outbid them.
+    pub tx_fees: Option<TxFees>,
+    /// Receipt of the commit tx, once it was included in an L1 block.
+    pub tx_receipt: Option<TransactionResponse>,
+    /// How many times the commit tx was sent.
+    pub attempts: u32,
+    /// L1 height from which on the submission gets resent if still incomplete.
+    pub retry_at: L1Height,
+    /// L1 block in which the commit was seen, once it was.
+    pub commit_location: Option<CommitLocation>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CommitLocation {
+    pub l1_height: L1Height,
+    pub l1_block_hash: [u8; 32],
+}
+
+impl CommitLocation {
+    // The block containing the commit counts as the first confirmation.
+    pub fn confirmations(&self, current_height: L1Height) -> u64 {
+        u64::from(current_height)
+            .saturating_sub(self.l1_height.into())
+            .saturating_add(1)
+    }
+}
+
+/// A commit tx as sent to L1.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CommitTx {
+    pub hash: [u8; 32],
+    pub nonce: u64,
+    pub fees: TxFees,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BlockSubmissionState {
+    Pending,
+    Completed,
+    /// The commit tx was included but reverted.
+    Failed,
+}
+
+impl BlockSubmission {
+    pub fn state(&self) -> BlockSubmissionState {
+        match self.tx_receipt {
+            Some(receipt) if !receipt.succeeded() => BlockSubmissionState::Failed,
+            _ if self.completed => BlockSubmissionState::Completed,
+            _ => BlockSubmissionState::Pending,
+        }
+    }
 }
 
 #[cfg(feature = "test-helpers")]
@@ -18,6 +73,13 @@ impl rand::distributions::Distribution<BlockSubmission> for rand::distributions:
             block_height: rng.gen(),
             completed: rng.gen(),
             submittal_height: rng.gen(),
+            tx_hash: rng.gen(),
+            tx_nonce: None,
+            tx_fees: None,
+            tx_receipt: None,
+            attempts: rng.gen_range(1..10),
+            retry_at: rng.gen(),
+            commit_location: None,
         }
     }
 }
@@ -0,0 +1,44 @@
+use serde::Deserialize;
+
+/// Codec the state of a fuel block was compressed with before it was split into fragments.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Compression {
+    #[default]
+    None,
+    Zstd,
+    Brotli,
+}
+
+impl Compression {
+    /// Stable identifier of the codec, persisted alongside the state so it can be decoded later.
+    pub fn id(&self) -> u8 {
+        match self {
+            Self::None => 0,
+            Self::Zstd => 1,
+            Self::Brotli => 2,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct InvalidCompression(u8);
+impl std::fmt::Display for InvalidCompression {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "Unknown compression codec id: {}", self.0)
+    }
+}
+impl std::error::Error for InvalidCompression {}
+
+impl TryFrom<u8> for Compression {
+    type Error = InvalidCompression;
+
+    fn try_from(id: u8) -> Result<Self, Self::Error> {
+        match id {
+            0 => Ok(Self::None),
+            1 => Ok(Self::Zstd),
+            2 => Ok(Self::Brotli),
+            _ => Err(InvalidCompression(id)),
+        }
+    }
+}
@@ -0,0 +1,20 @@
+use crate::types::TransactionResponse;
+
+/// State sent to a data availability layer, as far as the committer needs to know about it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DaSubmission {
+    pub hash: [u8; 32],
+    /// Whatever the layer needs to replace or read back the submission later. Stored by the
+    /// committer without being looked into.
+    pub details: Vec<u8>,
+}
+
+/// Where a submission stands on the data availability layer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SubmissionStatus {
+    /// Waiting to be included.
+    Pending,
+    /// Neither included nor waiting to be, it has to be submitted again.
+    Dropped,
+    Included(TransactionResponse),
+}
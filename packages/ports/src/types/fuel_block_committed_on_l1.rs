@@ -1,9 +1,14 @@
-use crate::types::U256;
+use crate::types::{L1Height, U256};
 
 #[derive(Clone, Copy)]
 pub struct FuelBlockCommittedOnL1 {
     pub fuel_block_hash: [u8; 32],
     pub commit_height: U256,
+    /// L1 block containing the event.
+    pub l1_height: L1Height,
+    pub l1_block_hash: [u8; 32],
+    /// Set when a reorg dropped the block containing the event.
+    pub removed: bool,
 }
 
 impl std::fmt::Debug for FuelBlockCommittedOnL1 {
@@ -15,6 +20,8 @@ impl std::fmt::Debug for FuelBlockCommittedOnL1 {
         f.debug_struct("FuelBlockCommittedOnL1")
             .field("hash", &hash)
             .field("commit_height", &self.commit_height)
+            .field("l1_height", &self.l1_height)
+            .field("removed", &self.removed)
             .finish()
     }
 }
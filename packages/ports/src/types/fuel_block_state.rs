@@ -0,0 +1,273 @@
+//! Canonical serialization of a fuel block, posted to L1 as the state of the block.
+//!
+//! ```text
+//! state:      version (1) | block id (32) | header | consensus | tx count (u32) | txs
+//! header:     da height (u64) | consensus parameters version (u32)
+//!             | state transition bytecode version (u32) | transactions count (u16)
+//!             | message receipt count (u32) | transactions root (32) | message outbox root (32)
+//!             | event inbox root (32) | height (u32) | prev root (32) | time (u64)
+//!             | application hash (32)
+//! consensus:  0 (unknown)
+//!             | 1 (genesis) chain config hash (32) | coins root (32) | contracts root (32)
+//!               | messages root (32) | transactions root (32)
+//!             | 2 (PoA) signature (64)
+//! tx:         length (u32) | canonically encoded tx
+//! ```
+//!
+//! All integers are big endian. The block producer is not part of the state, it is recovered from
+//! the PoA signature. Version 1 carried the transaction ids instead of the transactions.
+
+use fuel_core_client::client::types::{
+    block::{Block, Consensus, Genesis, Header, PoAConsensus},
+    primitives::{Bytes32, Signature},
+};
+
+use crate::fuel::FullFuelBlock;
+
+pub const BLOCK_STATE_VERSION: u8 = 2;
+
+const UNKNOWN_CONSENSUS: u8 = 0;
+const GENESIS_CONSENSUS: u8 = 1;
+const POA_CONSENSUS: u8 = 2;
+
+#[derive(Debug, Clone)]
+pub struct InvalidBlockState(String);
+impl std::fmt::Display for InvalidBlockState {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "Invalid block state: {}", self.0)
+    }
+}
+impl std::error::Error for InvalidBlockState {}
+
+pub fn encode_block_state(full_block: &FullFuelBlock) -> Vec<u8> {
+    let FullFuelBlock {
+        block,
+        raw_transactions,
+    } = full_block;
+    let header = &block.header;
+
+    let mut state = vec![BLOCK_STATE_VERSION];
+    state.extend_from_slice(block.id.as_ref());
+    state.extend_from_slice(&header.da_height.to_be_bytes());
+    state.extend_from_slice(&header.consensus_parameters_version.to_be_bytes());
+    state.extend_from_slice(&header.state_transition_bytecode_version.to_be_bytes());
+    state.extend_from_slice(&header.transactions_count.to_be_bytes());
+    state.extend_from_slice(&header.message_receipt_count.to_be_bytes());
+    state.extend_from_slice(header.transactions_root.as_ref());
+    state.extend_from_slice(header.message_outbox_root.as_ref());
+    state.extend_from_slice(header.event_inbox_root.as_ref());
+    state.extend_from_slice(&header.height.to_be_bytes());
+    state.extend_from_slice(header.prev_root.as_ref());
+    state.extend_from_slice(&header.time.0.to_be_bytes());
+    state.extend_from_slice(header.application_hash.as_ref());
+
+    match &block.consensus {
+        Consensus::Unknown => state.push(UNKNOWN_CONSENSUS),
+        Consensus::Genesis(genesis) => {
+            state.push(GENESIS_CONSENSUS);
+            for root in [
+                &genesis.chain_config_hash,
+                &genesis.coins_root,
+                &genesis.contracts_root,
+                &genesis.messages_root,
+                &genesis.transactions_root,
+            ] {
+                state.extend_from_slice(root.as_ref());
+            }
+        }
+        Consensus::PoAConsensus(poa) => {
+            state.push(POA_CONSENSUS);
+            state.extend_from_slice(poa.signature.as_ref());
+        }
+    }
+
+    // a block can't hold anywhere near u32::MAX txs, nor can a tx be that large
+    state.extend_from_slice(&(raw_transactions.len() as u32).to_be_bytes());
+    for tx in raw_transactions {
+        state.extend_from_slice(&(tx.len() as u32).to_be_bytes());
+        state.extend_from_slice(tx);
+    }
+
+    state
+}
+
+/// Rebuilds the block from its state. `block_producer` and the transaction ids are left unset, the
+/// transactions themselves end up in `raw_transactions`.
+pub fn decode_block_state(state: &[u8]) -> Result<FullFuelBlock, InvalidBlockState> {
+    let mut reader = Reader { state };
+
+    let version = reader.take::<1>()?[0];
+    if version != BLOCK_STATE_VERSION {
+        return Err(InvalidBlockState(format!(
+            "unsupported version {version}, expected {BLOCK_STATE_VERSION}"
+        )));
+    }
+
+    let id = reader.bytes32()?;
+    let header = Header {
+        id,
+        da_height: u64::from_be_bytes(reader.take()?),
+        consensus_parameters_version: u32::from_be_bytes(reader.take()?),
+        state_transition_bytecode_version: u32::from_be_bytes(reader.take()?),
+        transactions_count: u16::from_be_bytes(reader.take()?),
+        message_receipt_count: u32::from_be_bytes(reader.take()?),
+        transactions_root: reader.bytes32()?,
+        message_outbox_root: reader.bytes32()?,
+        event_inbox_root: reader.bytes32()?,
+        height: u32::from_be_bytes(reader.take()?),
+        prev_root: reader.bytes32()?,
+        time: tai64::Tai64(u64::from_be_bytes(reader.take()?)),
+        application_hash: reader.bytes32()?,
+    };
+
+    let consensus = match reader.take::<1>()?[0] {
+        UNKNOWN_CONSENSUS => Consensus::Unknown,
+        GENESIS_CONSENSUS => Consensus::Genesis(Genesis {
+            chain_config_hash: reader.bytes32()?,
+            coins_root: reader.bytes32()?,
+            contracts_root: reader.bytes32()?,
+            messages_root: reader.bytes32()?,
+            transactions_root: reader.bytes32()?,
+        }),
+        POA_CONSENSUS => Consensus::PoAConsensus(PoAConsensus {
+            signature: Signature::from_bytes(reader.take()?),
+        }),
+        tag => return Err(InvalidBlockState(format!("unknown consensus tag {tag}"))),
+    };
+
+    let tx_count = u32::from_be_bytes(reader.take()?);
+    let raw_transactions = (0..tx_count)
+        .map(|_| {
+            let len = u32::from_be_bytes(reader.take()?) as usize;
+            Ok(reader.take_slice(len)?.to_vec())
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    if !reader.state.is_empty() {
+        return Err(InvalidBlockState(format!(
+            "{} unexpected bytes after the last tx",
+            reader.state.len()
+        )));
+    }
+
+    let block = Block {
+        id,
+        header,
+        consensus,
+        transactions: vec![],
+        block_producer: None,
+    };
+
+    Ok(FullFuelBlock {
+        block,
+        raw_transactions,
+    })
+}
+
+struct Reader<'a> {
+    state: &'a [u8],
+}
+
+impl Reader<'_> {
+    fn bytes32(&mut self) -> Result<Bytes32, InvalidBlockState> {
+        Ok(Bytes32::from(self.take::<32>()?))
+    }
+
+    fn take<const N: usize>(&mut self) -> Result<[u8; N], InvalidBlockState> {
+        let bytes = self.take_slice(N)?;
+        Ok(bytes.try_into().expect("slice has exactly N bytes"))
+    }
+
+    fn take_slice(&mut self, len: usize) -> Result<&[u8], InvalidBlockState> {
+        if self.state.len() < len {
+            return Err(InvalidBlockState(format!(
+                "truncated: expected {len} more bytes, got {}",
+                self.state.len()
+            )));
+        }
+
+        let (taken, rest) = self.state.split_at(len);
+        self.state = rest;
+
+        Ok(taken)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn given_block(consensus: Consensus) -> FullFuelBlock {
+        let header = Header {
+            id: Bytes32::from([1; 32]),
+            da_height: 7,
+            consensus_parameters_version: 2,
+            state_transition_bytecode_version: 3,
+            transactions_count: 3,
+            message_receipt_count: 4,
+            transactions_root: Bytes32::from([2; 32]),
+            message_outbox_root: Bytes32::from([3; 32]),
+            event_inbox_root: Bytes32::from([4; 32]),
+            height: 10,
+            prev_root: Bytes32::from([5; 32]),
+            time: tai64::Tai64(1_000),
+            application_hash: Bytes32::from([6; 32]),
+        };
+
+        let block = Block {
+            id: Bytes32::from([1; 32]),
+            header,
+            consensus,
+            transactions: vec![],
+            block_producer: None,
+        };
+
+        FullFuelBlock {
+            block,
+            raw_transactions: vec![vec![8; 120], vec![], vec![9; 3]],
+        }
+    }
+
+    #[test]
+    fn block_state_round_trips() {
+        let genesis = Genesis {
+            chain_config_hash: Bytes32::from([10; 32]),
+            coins_root: Bytes32::from([11; 32]),
+            contracts_root: Bytes32::from([12; 32]),
+            messages_root: Bytes32::from([13; 32]),
+            transactions_root: Bytes32::from([14; 32]),
+        };
+        let poa = PoAConsensus {
+            signature: Signature::from_bytes([15; 64]),
+        };
+
+        for consensus in [
+            Consensus::Unknown,
+            Consensus::Genesis(genesis),
+            Consensus::PoAConsensus(poa),
+        ] {
+            // given
+            let block = given_block(consensus);
+
+            // when
+            let state = encode_block_state(&block);
+
+            // then
+            assert_eq!(state[0], BLOCK_STATE_VERSION);
+            assert_eq!(decode_block_state(&state).unwrap(), block);
+        }
+    }
+
+    #[test]
+    fn rejects_truncated_state() {
+        // given
+        let state = encode_block_state(&given_block(Consensus::Unknown));
+
+        // when
+        let result = decode_block_state(&state[..state.len() - 1]);
+
+        // then
+        let err = result.unwrap_err();
+        assert!(err.to_string().contains("truncated"));
+    }
+}
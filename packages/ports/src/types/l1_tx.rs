@@ -0,0 +1,81 @@
+/// Fee parameters of an EIP-4844 transaction, in wei.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct TxFees {
+    pub max_fee_per_gas: u128,
+    pub max_priority_fee_per_gas: u128,
+    pub max_fee_per_blob_gas: u128,
+}
+
+impl TxFees {
+    /// Raises every fee by `percentage`, rounding up so that tiny fees still increase.
+    pub fn bumped(&self, percentage: u32) -> Self {
+        let bump = |fee: u128| {
+            fee.saturating_mul(100 + u128::from(percentage))
+                .div_ceil(100)
+                .max(fee.saturating_add(1))
+        };
+
+        Self {
+            max_fee_per_gas: bump(self.max_fee_per_gas),
+            max_priority_fee_per_gas: bump(self.max_priority_fee_per_gas),
+            max_fee_per_blob_gas: bump(self.max_fee_per_blob_gas),
+        }
+    }
+
+    /// Takes the larger of the two values for every fee.
+    pub fn at_least(&self, other: &Self) -> Self {
+        Self {
+            max_fee_per_gas: self.max_fee_per_gas.max(other.max_fee_per_gas),
+            max_priority_fee_per_gas: self
+                .max_priority_fee_per_gas
+                .max(other.max_priority_fee_per_gas),
+            max_fee_per_blob_gas: self.max_fee_per_blob_gas.max(other.max_fee_per_blob_gas),
+        }
+    }
+
+    /// The most a tx consuming the given amounts of gas can cost.
+    pub fn max_total_fee(&self, gas_limit: u64, blob_gas: u64) -> u128 {
+        self.max_fee_per_gas
+            .saturating_mul(u128::from(gas_limit))
+            .saturating_add(
+                self.max_fee_per_blob_gas
+                    .saturating_mul(u128::from(blob_gas)),
+            )
+    }
+}
+
+/// Base fees of the latest L1 block, in wei.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct BaseFees {
+    pub base_fee_per_gas: u128,
+    /// `None` on chains without blobs
+    pub base_fee_per_blob_gas: Option<u128>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bumping_rounds_up() {
+        // given
+        let fees = TxFees {
+            max_fee_per_gas: 3,
+            max_priority_fee_per_gas: 0,
+            max_fee_per_blob_gas: 1_000,
+        };
+
+        // when
+        let bumped = fees.bumped(10);
+
+        // then
+        assert_eq!(
+            bumped,
+            TxFees {
+                max_fee_per_gas: 4,
+                max_priority_fee_per_gas: 1,
+                max_fee_per_blob_gas: 1_100,
+            }
+        );
+    }
+}
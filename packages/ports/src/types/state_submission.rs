@@ -1,10 +1,16 @@
 pub use sqlx::types::chrono::{DateTime, Utc};
 
+use crate::types::{Compression, DaSubmission, L1Height};
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct StateSubmission {
     pub block_hash: [u8; 32],
     pub block_height: u32,
     pub completed: bool,
+    /// Codec applied to the block state before it was split into fragments
+    pub compression: Compression,
+    /// Number of fragments the block state was split into
+    pub num_fragments: u32,
 }
 
 pub type StateFragmentId = ([u8; 32], u32);
@@ -20,9 +26,78 @@ pub struct StateFragment {
 }
 
 impl StateFragment {
-    pub const MAX_FRAGMENT_SIZE: usize = 128 * 1024;
-
     pub fn id(&self) -> StateFragmentId {
         (self.block_hash, self.fragment_index)
     }
 }
+
+/// A fragment together with what a reader of the blob needs to put the block state back together.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StateFrame {
+    pub block_hash: [u8; 32],
+    pub block_height: u32,
+    pub fragment_index: u32,
+    pub total_fragments: u32,
+    pub compression: Compression,
+    pub data: Vec<u8>,
+}
+
+impl StateFrame {
+    /// Size of the header preceding the frames of a blob payload: magic, version and frame count.
+    pub const PAYLOAD_HEADER_SIZE: usize = 4 + 1 + 2;
+    /// Size of the header preceding the data of every frame: block hash, block height, fragment
+    /// index, total fragments, compression and data length.
+    pub const HEADER_SIZE: usize = 32 + 4 + 4 + 4 + 1 + 4;
+
+    /// Bytes the frame takes up in a blob payload.
+    pub fn encoded_len(&self) -> usize {
+        Self::HEADER_SIZE + self.data.len()
+    }
+
+    pub fn new(submission: &StateSubmission, fragment: StateFragment) -> Self {
+        Self {
+            block_hash: submission.block_hash,
+            block_height: submission.block_height,
+            fragment_index: fragment.fragment_index,
+            total_fragments: submission.num_fragments,
+            compression: submission.compression,
+            data: fragment.raw_data,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SubmissionTx {
+    pub hash: [u8; 32],
+    /// See `DaSubmission::details`
+    pub details: Vec<u8>,
+    // L1 height at the moment the tx was sent. Used to decide when a stuck tx gets replaced.
+    pub submitted_at: L1Height,
+    pub state: TransactionState,
+}
+
+impl SubmissionTx {
+    pub fn pending(submission: DaSubmission, submitted_at: L1Height) -> Self {
+        Self {
+            hash: submission.hash,
+            details: submission.details,
+            submitted_at,
+            state: TransactionState::Pending,
+        }
+    }
+
+    /// The submission as it was handed to the data availability layer.
+    pub fn submission(&self) -> DaSubmission {
+        DaSubmission {
+            hash: self.hash,
+            details: self.details.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransactionState {
+    Pending,
+    Finalized,
+    Failed,
+}
@@ -0,0 +1,56 @@
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TransactionResponse {
+    block_number: u64,
+    block_hash: [u8; 32],
+    succeeded: bool,
+    gas_used: u64,
+    effective_gas_price: u128,
+}
+
+impl TransactionResponse {
+    pub fn new(
+        block_number: u64,
+        block_hash: [u8; 32],
+        succeeded: bool,
+        gas_used: u64,
+        effective_gas_price: u128,
+    ) -> Self {
+        Self {
+            block_number,
+            block_hash,
+            succeeded,
+            gas_used,
+            effective_gas_price,
+        }
+    }
+
+    pub fn block_number(&self) -> u64 {
+        self.block_number
+    }
+
+    /// Hash of the block the tx was included in, telling apart blocks of competing forks at the
+    /// same height.
+    pub fn block_hash(&self) -> [u8; 32] {
+        self.block_hash
+    }
+
+    pub fn succeeded(&self) -> bool {
+        self.succeeded
+    }
+
+    pub fn gas_used(&self) -> u64 {
+        self.gas_used
+    }
+
+    /// Price paid per unit of gas, in wei.
+    pub fn effective_gas_price(&self) -> u128 {
+        self.effective_gas_price
+    }
+
+    // The block containing the tx counts as the first confirmation.
+    pub fn confirmations(&self, current_block_number: u64) -> u64 {
+        current_block_number
+            .saturating_sub(self.block_number)
+            .saturating_add(1)
+    }
+}
@@ -2,18 +2,21 @@ use std::num::NonZeroU32;
 
 use async_trait::async_trait;
 use metrics::{
-    prometheus::{core::Collector, IntGauge, Opts},
+    prometheus::{core::Collector, IntCounter, IntGauge, Opts},
     RegistersMetrics,
 };
 use ports::{
     storage::Storage,
-    types::{BlockSubmission, ValidatedFuelBlock},
+    types::{BlockSubmission, BlockSubmissionState, L1Height, TxFees, ValidatedFuelBlock},
 };
-use tracing::info;
-use validator::Validator;
+use tracing::{info, warn};
+use validator::{ChainContinuity, Validator};
 
 use super::Runner;
-use crate::{Error, Result};
+use crate::{block_feed::BlockFeed, Error, Result, REPLACEMENT_FEE_BUMP_PERCENTAGE};
+
+/// Caps the exponential back-off between resubmissions at 2^6 times the base delay.
+const MAX_BACKOFF_EXPONENT: u32 = 6;
 
 pub struct BlockCommitter<L1, Db, Fuel, BlockValidator> {
     l1_adapter: L1,
@@ -21,18 +24,26 @@ pub struct BlockCommitter<L1, Db, Fuel, BlockValidator> {
     storage: Db,
     block_validator: BlockValidator,
     commit_interval: NonZeroU32,
+    num_blocks_before_resubmission: u64,
+    blocks: BlockFeed,
+    latest_block: Option<ValidatedFuelBlock>,
+    chain_continuity: ChainContinuity,
     metrics: Metrics,
 }
 
 struct Metrics {
     latest_fuel_block: IntGauge,
+    block_commit_retries: IntCounter,
 }
 
 impl<L1, Db, Fuel, BlockValidator> RegistersMetrics
     for BlockCommitter<L1, Db, Fuel, BlockValidator>
 {
     fn metrics(&self) -> Vec<Box<dyn Collector>> {
-        vec![Box::new(self.metrics.latest_fuel_block.clone())]
+        vec![
+            Box::new(self.metrics.latest_fuel_block.clone()),
+            Box::new(self.metrics.block_commit_retries.clone()),
+        ]
     }
 }
 
@@ -44,7 +55,16 @@ impl Default for Metrics {
         ))
         .expect("fuel_network_errors metric to be correctly configured");
 
-        Self { latest_fuel_block }
+        let block_commit_retries = IntCounter::with_opts(Opts::new(
+            "block_commit_retries",
+            "Number of times a fuel block commit was resent.",
+        ))
+        .expect("block_commit_retries metric to be correctly configured");
+
+        Self {
+            latest_fuel_block,
+            block_commit_retries,
+        }
     }
 }
 
@@ -55,6 +75,8 @@ impl<L1, Db, Fuel, BlockValidator> BlockCommitter<L1, Db, Fuel, BlockValidator>
         fuel_adapter: Fuel,
         block_validator: BlockValidator,
         commit_interval: NonZeroU32,
+        num_blocks_before_resubmission: u64,
+        chain_continuity: ChainContinuity,
     ) -> Self {
         Self {
             l1_adapter: l1,
@@ -62,9 +84,26 @@ impl<L1, Db, Fuel, BlockValidator> BlockCommitter<L1, Db, Fuel, BlockValidator>
             fuel_adapter,
             block_validator,
             commit_interval,
+            num_blocks_before_resubmission,
+            blocks: BlockFeed::default(),
+            latest_block: None,
+            chain_continuity,
             metrics: Metrics::default(),
         }
     }
+
+    // Doubles the wait with every attempt, up to `MAX_BACKOFF_EXPONENT`.
+    fn retry_at(&self, submitted_at: L1Height, attempts: u32) -> Result<L1Height> {
+        let exponent = attempts.saturating_sub(1).min(MAX_BACKOFF_EXPONENT);
+        let delay = self
+            .num_blocks_before_resubmission
+            .saturating_mul(1 << exponent);
+
+        u64::from(submitted_at)
+            .saturating_add(delay)
+            .try_into()
+            .map_err(|e: ports::types::InvalidL1Height| Error::Other(e.to_string()))
+    }
 }
 
 impl<L1, Db, Fuel, BlockValidator> BlockCommitter<L1, Db, Fuel, BlockValidator>
@@ -82,19 +121,130 @@ where
             block_height: fuel_block.height(),
             submittal_height,
             completed: false,
+            tx_hash: None,
+            tx_nonce: None,
+            tx_fees: None,
+            tx_receipt: None,
+            attempts: 1,
+            retry_at: self.retry_at(submittal_height, 1)?,
+            commit_location: None,
         };
 
         self.storage.insert(submission).await?;
 
-        // if we have a network failure the DB entry will be left at completed:false.
-        self.l1_adapter.submit(fuel_block).await?;
+        // if we have a network failure the DB entry will be left at completed:false and the
+        // block gets resubmitted once `retry_at` is reached.
+        self.send_commit(fuel_block).await
+    }
+
+    async fn send_commit(&self, fuel_block: ValidatedFuelBlock) -> Result<()> {
+        let tx = self.l1_adapter.submit(fuel_block).await?;
+        self.storage
+            .set_submission_tx(fuel_block.hash(), tx)
+            .await?;
 
         Ok(())
     }
 
-    async fn fetch_latest_block(&self) -> Result<ValidatedFuelBlock> {
-        let latest_block = self.fuel_adapter.latest_block().await?;
-        let validated_block = self.block_validator.validate(&latest_block)?;
+    // Reuses the nonce of the pending tx so that at most one of them can land.
+    async fn replace_commit(
+        &self,
+        fuel_block: ValidatedFuelBlock,
+        nonce: u64,
+        fees: TxFees,
+    ) -> Result<()> {
+        // commit txs carry no blobs
+        let min_fees = TxFees {
+            max_fee_per_blob_gas: 0,
+            ..fees.bumped(REPLACEMENT_FEE_BUMP_PERCENTAGE)
+        };
+        let tx = self
+            .l1_adapter
+            .replace_commit(fuel_block, nonce, min_fees)
+            .await?;
+        self.storage
+            .set_submission_tx(fuel_block.hash(), tx)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Resends every commit that is due, including the ones re-queued after a reorg, not only
+    /// the one of the latest block.
+    async fn resubmit_stuck(&self) -> Result<()> {
+        let submissions = self.storage.submissions_awaiting_commit().await?;
+        if submissions.is_empty() {
+            return Ok(());
+        }
+
+        let current_height = self.l1_adapter.get_block_number().await?;
+        for submission in submissions {
+            if current_height < submission.retry_at {
+                continue;
+            }
+
+            self.resubmit(submission, current_height).await?;
+        }
+
+        Ok(())
+    }
+
+    async fn resubmit(&self, submission: BlockSubmission, current_height: L1Height) -> Result<()> {
+        let fuel_block = self.fetch_block(submission.block_height).await?;
+        self.chain_continuity.validate(
+            submission.block_height,
+            submission.block_hash,
+            &[fuel_block],
+            None,
+        )?;
+
+        // a reverted tx used up its nonce, so there is nothing left to replace
+        let pending_tx = match (submission.state(), submission.tx_nonce, submission.tx_fees) {
+            (BlockSubmissionState::Pending, Some(nonce), Some(fees)) => Some((nonce, fees)),
+            _ => None,
+        };
+
+        let retry_at = self.retry_at(current_height, submission.attempts.saturating_add(1))?;
+        let submission = self
+            .storage
+            .record_resubmission(submission.block_hash, retry_at)
+            .await?;
+        self.metrics.block_commit_retries.inc();
+
+        warn!(
+            "resubmitting fuel block {}, attempt {}",
+            submission.block_height, submission.attempts
+        );
+
+        match pending_tx {
+            Some((nonce, fees)) => self.replace_commit(fuel_block, nonce, fees).await,
+            None => self.send_commit(fuel_block).await,
+        }
+    }
+
+    /// Takes the tip from the followed chain, polling the node only while the chain isn't
+    /// followed.
+    async fn fetch_latest_block(&mut self) -> Result<ValidatedFuelBlock> {
+        let streamed = self
+            .blocks
+            .ready_blocks()
+            .map(|blocks| blocks.into_iter().last());
+
+        let validated_block = match (streamed, self.latest_block) {
+            (Some(Some(block)), _) => self
+                .block_validator
+                .validate(&block)
+                .inspect_err(|_| self.blocks.stop())?,
+            (Some(None), Some(latest_block)) => latest_block,
+            _ => {
+                let latest_block = self.fuel_adapter.latest_block().await?;
+                let validated_block = self.block_validator.validate(&latest_block)?;
+                self.blocks
+                    .follow(&self.fuel_adapter, validated_block.height() + 1);
+                validated_block
+            }
+        };
+        self.latest_block = Some(validated_block);
 
         self.metrics
             .latest_fuel_block
@@ -123,6 +273,29 @@ where
             .map(|submission| submission.block_height))
     }
 
+    /// Makes sure `next` extends the last committed block, walking every block the node serves
+    /// in between.
+    async fn check_continuity(&self, next: &ValidatedFuelBlock) -> Result<()> {
+        let Some(committed) = self.storage.submission_w_latest_block().await? else {
+            return Ok(());
+        };
+
+        let chain = self
+            .fuel_adapter
+            .blocks_in_height_range(committed.block_height..=next.height())
+            .await?
+            .iter()
+            .map(|block| self.block_validator.validate(block))
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+
+        Ok(self.chain_continuity.validate(
+            committed.block_height,
+            committed.block_hash,
+            &chain,
+            Some(next),
+        )?)
+    }
+
     async fn fetch_block(&self, height: u32) -> Result<ValidatedFuelBlock> {
         let fuel_block = self
             .fuel_adapter
@@ -147,11 +320,13 @@ where
     BlockValidator: Validator,
 {
     async fn run(&mut self) -> Result<()> {
+        self.chain_continuity.ensure_intact()?;
+
         let current_block = self.fetch_latest_block().await?;
         let current_epoch_block_height = self.current_epoch_block_height(current_block.height());
 
         if self.check_if_stale(current_epoch_block_height).await? {
-            return Ok(());
+            return self.resubmit_stuck().await;
         }
 
         let block = if current_block.height() == current_epoch_block_height {
@@ -160,9 +335,9 @@ where
             self.fetch_block(current_epoch_block_height).await?
         };
 
-        self.submit_block(block)
-            .await
-            .map_err(|e| Error::Other(e.to_string()))?;
+        self.check_continuity(&block).await?;
+
+        self.submit_block(block).await?;
         info!("submitted {block:?}!");
 
         Ok(())
@@ -174,6 +349,7 @@ mod tests {
     use std::sync::Arc;
 
     use fuel_crypto::{Message, SecretKey, Signature};
+    use futures::StreamExt;
     use metrics::prometheus::{proto::Metric, Registry};
     use rand::{rngs::StdRng, Rng, SeedableRng};
     use validator::BlockValidator;
@@ -184,7 +360,7 @@ mod tests {
     use ports::{
         fuel::{FuelBlock, FuelBlockId, FuelConsensus, FuelHeader, FuelPoAConsensus},
         l1::{Contract, EventStreamer, MockContract},
-        types::{L1Height, U256},
+        types::{CommitTx, L1Height, TransactionResponse, U256},
     };
     use storage::{Postgres, PostgresProcess};
 
@@ -203,10 +379,23 @@ mod tests {
 
     #[async_trait::async_trait]
     impl Contract for MockL1 {
-        async fn submit(&self, block: ValidatedFuelBlock) -> ports::l1::Result<()> {
+        async fn submit(&self, block: ValidatedFuelBlock) -> ports::l1::Result<CommitTx> {
             self.contract.submit(block).await
         }
-        fn event_streamer(&self, height: L1Height) -> Box<dyn EventStreamer + Send + Sync> {
+
+        async fn replace_commit(
+            &self,
+            block: ValidatedFuelBlock,
+            nonce: u64,
+            min_fees: TxFees,
+        ) -> ports::l1::Result<CommitTx> {
+            self.contract.replace_commit(block, nonce, min_fees).await
+        }
+
+        fn event_streamer(
+            &self,
+            height: L1Height,
+        ) -> ports::l1::Result<Box<dyn EventStreamer + Send + Sync>> {
             self.contract.event_streamer(height)
         }
 
@@ -217,17 +406,28 @@ mod tests {
 
     #[async_trait::async_trait]
     impl ports::l1::Api for MockL1 {
-        async fn submit_l2_state(&self, state_data: Vec<u8>) -> ports::l1::Result<[u8; 32]> {
-            self.api.submit_l2_state(state_data).await
-        }
-
         async fn get_block_number(&self) -> ports::l1::Result<L1Height> {
             self.api.get_block_number().await
         }
 
+        async fn get_block_hash(&self, height: L1Height) -> ports::l1::Result<Option<[u8; 32]>> {
+            self.api.get_block_hash(height).await
+        }
+
         async fn balance(&self) -> ports::l1::Result<U256> {
             self.api.balance().await
         }
+
+        async fn get_transaction_response(
+            &self,
+            tx_hash: [u8; 32],
+        ) -> ports::l1::Result<Option<TransactionResponse>> {
+            self.api.get_transaction_response(tx_hash).await
+        }
+
+        async fn is_squeezed_out(&self, tx_hash: [u8; 32]) -> ports::l1::Result<bool> {
+            self.api.is_squeezed_out(tx_hash).await
+        }
     }
 
     fn given_l1_that_expects_submission(block: ValidatedFuelBlock) -> MockL1 {
@@ -236,7 +436,7 @@ mod tests {
         l1.contract
             .expect_submit()
             .with(predicate::eq(block))
-            .return_once(move |_| Ok(()));
+            .return_once(move |_| Ok(given_commit_tx([1; 32])));
 
         l1.api
             .expect_get_block_number()
@@ -258,8 +458,15 @@ mod tests {
         let l1 = given_l1_that_expects_submission(validated_missed_block);
         let process = PostgresProcess::shared().await.unwrap();
         let db = db_with_submissions(&process, vec![0, 2]).await;
-        let mut block_committer =
-            BlockCommitter::new(l1, db, fuel_adapter, block_validator, 2.try_into().unwrap());
+        let mut block_committer = BlockCommitter::new(
+            l1,
+            db,
+            fuel_adapter,
+            block_validator,
+            2.try_into().unwrap(),
+            10,
+            ChainContinuity::default(),
+        );
 
         // when
         block_committer.run().await.unwrap();
@@ -282,9 +489,19 @@ mod tests {
 
         let mut l1 = MockL1::new();
         l1.contract.expect_submit().never();
+        l1.api
+            .expect_get_block_number()
+            .returning(|| Ok(0u32.into()));
 
-        let mut block_committer =
-            BlockCommitter::new(l1, db, fuel_adapter, block_validator, 2.try_into().unwrap());
+        let mut block_committer = BlockCommitter::new(
+            l1,
+            db,
+            fuel_adapter,
+            block_validator,
+            2.try_into().unwrap(),
+            10,
+            ChainContinuity::default(),
+        );
 
         // when
         block_committer.run().await.unwrap();
@@ -306,9 +523,19 @@ mod tests {
 
         let mut l1 = MockL1::new();
         l1.contract.expect_submit().never();
+        l1.api
+            .expect_get_block_number()
+            .returning(|| Ok(0u32.into()));
 
-        let mut block_committer =
-            BlockCommitter::new(l1, db, fuel_adapter, block_validator, 2.try_into().unwrap());
+        let mut block_committer = BlockCommitter::new(
+            l1,
+            db,
+            fuel_adapter,
+            block_validator,
+            2.try_into().unwrap(),
+            10,
+            ChainContinuity::default(),
+        );
 
         // when
         block_committer.run().await.unwrap();
@@ -328,8 +555,15 @@ mod tests {
         let process = PostgresProcess::shared().await.unwrap();
         let db = db_with_submissions(&process, vec![0, 2]).await;
         let l1 = given_l1_that_expects_submission(ValidatedFuelBlock::new(*block.id, 4));
-        let mut block_committer =
-            BlockCommitter::new(l1, db, fuel_adapter, block_validator, 2.try_into().unwrap());
+        let mut block_committer = BlockCommitter::new(
+            l1,
+            db,
+            fuel_adapter,
+            block_validator,
+            2.try_into().unwrap(),
+            10,
+            ChainContinuity::default(),
+        );
 
         // when
         block_committer.run().await.unwrap();
@@ -338,6 +572,162 @@ mod tests {
         // Mock verifies that submit was called with the appropriate block
     }
 
+    #[tokio::test]
+    async fn follows_the_chain_once_the_tip_was_polled() {
+        // given
+        let secret_key = given_secret_key();
+        let block_validator = BlockValidator::new(secret_key.public_key());
+        let polled_block = given_a_block(3, &secret_key);
+        let streamed_block = given_a_block(4, &secret_key);
+
+        let mut fuel_adapter = ports::fuel::MockApi::new();
+        given_committed_blocks(&mut fuel_adapter);
+        fuel_adapter
+            .expect_latest_block()
+            .once()
+            .return_once(move || Ok(polled_block));
+        let stream_block = streamed_block.clone();
+        fuel_adapter
+            .expect_poll_blocks_from()
+            .with(eq(4))
+            .once()
+            .return_once(move |_| {
+                Box::pin(
+                    futures::stream::iter([Ok(stream_block)]).chain(futures::stream::pending()),
+                )
+            });
+
+        let mut l1 = MockL1::new();
+        l1.api
+            .expect_get_block_number()
+            .returning(|| Ok(0u32.into()));
+        l1.contract
+            .expect_submit()
+            .with(predicate::eq(ValidatedFuelBlock::new(
+                *streamed_block.id,
+                4,
+            )))
+            .once()
+            .return_once(|_| Ok(given_commit_tx([1; 32])));
+
+        let process = PostgresProcess::shared().await.unwrap();
+        let db = db_with_submissions(&process, vec![0, 2]).await;
+        let mut block_committer = BlockCommitter::new(
+            l1,
+            db,
+            fuel_adapter,
+            block_validator,
+            2.try_into().unwrap(),
+            10,
+            ChainContinuity::default(),
+        );
+        block_committer.run().await.unwrap();
+
+        // when
+        block_committer.run().await.unwrap();
+
+        // then
+        // mocks verify that the tip was polled only once and the streamed block got submitted
+    }
+
+    #[tokio::test]
+    async fn halts_submissions_once_the_node_serves_a_fork() {
+        // given
+        let secret_key = given_secret_key();
+        let block_validator = BlockValidator::new(secret_key.public_key());
+        let block = given_a_block(4, &secret_key);
+        let fuel_adapter = given_fetcher(vec![block]);
+
+        let process = PostgresProcess::shared().await.unwrap();
+        let db = process.create_random_db().await.unwrap();
+        let committed_on_another_chain = BlockSubmission {
+            block_hash: [7; 32],
+            ..given_a_pending_submission(2)
+        };
+        db.insert(committed_on_another_chain).await.unwrap();
+
+        let mut l1 = MockL1::new();
+        l1.contract.expect_submit().never();
+
+        let chain_continuity = ChainContinuity::default();
+        let mut block_committer = BlockCommitter::new(
+            l1,
+            db,
+            fuel_adapter,
+            block_validator,
+            2.try_into().unwrap(),
+            10,
+            chain_continuity.clone(),
+        );
+
+        // when
+        let first_run = block_committer.run().await;
+        let second_run = block_committer.run().await;
+
+        // then
+        assert!(matches!(first_run, Err(Error::ChainDiscontinuity(_))));
+        assert!(matches!(second_run, Err(Error::ChainDiscontinuity(_))));
+        assert!(!chain_continuity.is_intact());
+    }
+
+    #[tokio::test]
+    async fn wont_commit_on_top_of_a_block_whose_prev_root_was_swapped() {
+        // given
+        let secret_key = given_secret_key();
+        let block_validator = BlockValidator::new(secret_key.public_key());
+        let block = given_a_block(4, &secret_key);
+
+        let mut fuel_adapter = ports::fuel::MockApi::new();
+        let latest_block = block.clone();
+        fuel_adapter
+            .expect_latest_block()
+            .returning(move || Ok(latest_block.clone()));
+        fuel_adapter
+            .expect_poll_blocks_from()
+            .returning(|_| Box::pin(futures::stream::pending()));
+        fuel_adapter
+            .expect_blocks_in_height_range()
+            .with(eq(2..=4))
+            .returning(|range| {
+                Ok(range
+                    .map(|height| {
+                        let mut block = given_a_block(height, &given_secret_key());
+                        if height == 3 {
+                            block.header.prev_root = [7; 32].into();
+                        }
+                        block
+                    })
+                    .collect())
+            });
+
+        let process = PostgresProcess::shared().await.unwrap();
+        let db = process.create_random_db().await.unwrap();
+        let committed = BlockSubmission {
+            block_hash: *given_a_block(2, &secret_key).id,
+            ..given_a_pending_submission(2)
+        };
+        db.insert(committed).await.unwrap();
+
+        let mut l1 = MockL1::new();
+        l1.contract.expect_submit().never();
+
+        let mut block_committer = BlockCommitter::new(
+            l1,
+            db,
+            fuel_adapter,
+            block_validator,
+            2.try_into().unwrap(),
+            10,
+            ChainContinuity::default(),
+        );
+
+        // when
+        let result = block_committer.run().await;
+
+        // then
+        assert!(matches!(result, Err(Error::BlockValidation(_))));
+    }
+
     #[tokio::test]
     async fn updates_block_metric_regardless_if_block_is_published() {
         // given
@@ -351,9 +741,19 @@ mod tests {
 
         let mut l1 = MockL1::new();
         l1.contract.expect_submit().never();
+        l1.api
+            .expect_get_block_number()
+            .returning(|| Ok(0u32.into()));
 
-        let mut block_committer =
-            BlockCommitter::new(l1, db, fuel_adapter, block_validator, 2.try_into().unwrap());
+        let mut block_committer = BlockCommitter::new(
+            l1,
+            db,
+            fuel_adapter,
+            block_validator,
+            2.try_into().unwrap(),
+            10,
+            ChainContinuity::default(),
+        );
 
         let registry = Registry::default();
         block_committer.register_metrics(&registry);
@@ -373,13 +773,213 @@ mod tests {
         assert_eq!(latest_block_metric.get_value(), 5f64);
     }
 
+    #[tokio::test]
+    async fn will_resubmit_block_not_committed_in_time() {
+        // given
+        let secret_key = given_secret_key();
+        let block_validator = BlockValidator::new(secret_key.public_key());
+        let block = given_a_block(4, &secret_key);
+        let fuel_adapter = given_fetcher(vec![block.clone()]);
+
+        let process = PostgresProcess::shared().await.unwrap();
+        let db = db_with_submissions(&process, vec![0, 2]).await;
+        let stuck_submission = BlockSubmission {
+            block_hash: *block.id,
+            completed: false,
+            attempts: 1,
+            retry_at: 10u32.into(),
+            ..given_a_pending_submission(4)
+        };
+        db.insert(stuck_submission).await.unwrap();
+
+        let mut l1 = MockL1::new();
+        l1.contract
+            .expect_submit()
+            .with(predicate::eq(ValidatedFuelBlock::new(*block.id, 4)))
+            .return_once(move |_| Ok(given_commit_tx([2; 32])));
+        l1.api
+            .expect_get_block_number()
+            .returning(|| Ok(10u32.into()));
+
+        let mut block_committer = BlockCommitter::new(
+            l1,
+            db.clone(),
+            fuel_adapter,
+            block_validator,
+            2.try_into().unwrap(),
+            10,
+            ChainContinuity::default(),
+        );
+        let registry = Registry::default();
+        block_committer.register_metrics(&registry);
+
+        // when
+        block_committer.run().await.unwrap();
+
+        // then
+        let submission = db.submission_w_latest_block().await.unwrap().unwrap();
+        assert_eq!(submission.attempts, 2);
+        assert_eq!(submission.retry_at, 30u32.into());
+        assert_eq!(submission.tx_hash, Some([2; 32]));
+
+        let retries = registry
+            .gather()
+            .iter()
+            .find(|metric| metric.get_name() == "block_commit_retries")
+            .and_then(|metric| metric.get_metric().first())
+            .map(|metric| metric.get_counter().get_value())
+            .unwrap();
+        assert_eq!(retries, 1f64);
+    }
+
+    #[tokio::test]
+    async fn replaces_a_stuck_commit_tx_instead_of_sending_another_one() {
+        // given
+        let secret_key = given_secret_key();
+        let block_validator = BlockValidator::new(secret_key.public_key());
+        let block = given_a_block(4, &secret_key);
+        let fuel_adapter = given_fetcher(vec![block.clone()]);
+
+        let process = PostgresProcess::shared().await.unwrap();
+        let db = db_with_submissions(&process, vec![0, 2]).await;
+        let stuck_tx = given_commit_tx([1; 32]);
+        let stuck_submission = BlockSubmission {
+            block_hash: *block.id,
+            completed: false,
+            attempts: 1,
+            retry_at: 10u32.into(),
+            ..given_a_pending_submission(4)
+        };
+        db.insert(stuck_submission).await.unwrap();
+        db.set_submission_tx(*block.id, stuck_tx).await.unwrap();
+
+        let mut l1 = MockL1::new();
+        l1.contract.expect_submit().never();
+        let bumped_fees = TxFees {
+            max_fee_per_gas: 4_000_000_000,
+            max_priority_fee_per_gas: 2_000_000_000,
+            max_fee_per_blob_gas: 0,
+        };
+        l1.contract
+            .expect_replace_commit()
+            .with(
+                predicate::eq(ValidatedFuelBlock::new(*block.id, 4)),
+                predicate::eq(stuck_tx.nonce),
+                predicate::eq(bumped_fees),
+            )
+            .once()
+            .return_once(move |_, nonce, fees| {
+                Ok(CommitTx {
+                    hash: [2; 32],
+                    nonce,
+                    fees,
+                })
+            });
+        l1.api
+            .expect_get_block_number()
+            .returning(|| Ok(10u32.into()));
+
+        let mut block_committer = BlockCommitter::new(
+            l1,
+            db.clone(),
+            fuel_adapter,
+            block_validator,
+            2.try_into().unwrap(),
+            10,
+            ChainContinuity::default(),
+        );
+
+        // when
+        block_committer.run().await.unwrap();
+
+        // then
+        let submission = db.submission_w_latest_block().await.unwrap().unwrap();
+        assert_eq!(submission.tx_hash, Some([2; 32]));
+        assert_eq!(submission.tx_nonce, Some(stuck_tx.nonce));
+        assert_eq!(submission.tx_fees, Some(bumped_fees));
+    }
+
+    #[tokio::test]
+    async fn resubmits_requeued_submissions_below_the_latest_one() {
+        // given
+        let secret_key = given_secret_key();
+        let block_validator = BlockValidator::new(secret_key.public_key());
+        let requeued_block = given_a_block(2, &secret_key);
+        let fuel_adapter = given_fetcher(vec![given_a_block(4, &secret_key)]);
+
+        let process = PostgresProcess::shared().await.unwrap();
+        let db = process.create_random_db().await.unwrap();
+        let requeued_submission = BlockSubmission {
+            block_hash: *requeued_block.id,
+            completed: false,
+            tx_hash: None,
+            retry_at: 10u32.into(),
+            ..given_a_pending_submission(2)
+        };
+        let latest_submission = BlockSubmission {
+            block_hash: *given_a_block(4, &secret_key).id,
+            completed: false,
+            ..given_a_pending_submission(4)
+        };
+        db.insert(requeued_submission.clone()).await.unwrap();
+        db.insert(latest_submission.clone()).await.unwrap();
+
+        let mut l1 = MockL1::new();
+        l1.contract
+            .expect_submit()
+            .with(predicate::eq(ValidatedFuelBlock::new(
+                *requeued_block.id,
+                2,
+            )))
+            .once()
+            .return_once(move |_| Ok(given_commit_tx([2; 32])));
+        l1.api
+            .expect_get_block_number()
+            .returning(|| Ok(10u32.into()));
+
+        let mut block_committer = BlockCommitter::new(
+            l1,
+            db.clone(),
+            fuel_adapter,
+            block_validator,
+            2.try_into().unwrap(),
+            10,
+            ChainContinuity::default(),
+        );
+
+        // when
+        block_committer.run().await.unwrap();
+
+        // then
+        let awaiting = db.submissions_awaiting_commit().await.unwrap();
+        assert_eq!(awaiting[0].tx_hash, Some([2; 32]));
+        assert_eq!(awaiting[0].attempts, requeued_submission.attempts + 1);
+        assert_eq!(awaiting[1], latest_submission);
+    }
+
+    fn given_commit_tx(hash: [u8; 32]) -> CommitTx {
+        CommitTx {
+            hash,
+            nonce: 3,
+            fees: TxFees {
+                max_fee_per_gas: 2_000_000_000,
+                max_priority_fee_per_gas: 1_000_000_000,
+                max_fee_per_blob_gas: 0,
+            },
+        }
+    }
+
     async fn db_with_submissions(
         process: &Arc<PostgresProcess>,
         pending_submissions: Vec<u32>,
     ) -> Postgres {
         let db = process.create_random_db().await.unwrap();
         for height in pending_submissions {
-            db.insert(given_a_pending_submission(height)).await.unwrap();
+            let submission = BlockSubmission {
+                block_hash: *given_a_block(height, &given_secret_key()).id,
+                ..given_a_pending_submission(height)
+            };
+            db.insert(submission).await.unwrap();
         }
 
         db
@@ -393,6 +993,7 @@ mod tests {
                 .with(eq(block.header.height))
                 .returning(move |_| Ok(Some(block.clone())));
         }
+        given_committed_blocks(&mut fetcher);
         if let Some(block) = available_blocks
             .into_iter()
             .max_by_key(|el| el.header.height)
@@ -401,13 +1002,30 @@ mod tests {
                 .expect_latest_block()
                 .returning(move || Ok(block.clone()));
         }
+        fetcher
+            .expect_poll_blocks_from()
+            .returning(|_| Box::pin(futures::stream::pending()));
+
+        fetcher
+    }
 
+    // serves the blocks the submissions of `db_with_submissions` were made for, and the ones in
+    // between
+    fn given_committed_blocks(fetcher: &mut ports::fuel::MockApi) {
         fetcher
+            .expect_block_at_height()
+            .returning(|height| Ok(Some(given_a_block(height, &given_secret_key()))));
+        fetcher.expect_blocks_in_height_range().returning(|range| {
+            Ok(range
+                .map(|height| given_a_block(height, &given_secret_key()))
+                .collect())
+        });
     }
 
     fn given_a_pending_submission(block_height: u32) -> BlockSubmission {
         let mut submission: BlockSubmission = rand::thread_rng().gen();
         submission.block_height = block_height;
+        submission.retry_at = L1Height::from(u32::MAX);
 
         submission
     }
@@ -0,0 +1,49 @@
+use std::pin::Pin;
+
+use futures::{FutureExt, StreamExt};
+use ports::{fuel::FuelBlock, types::Stream};
+use tracing::warn;
+
+type BlockStream = Pin<Box<dyn Stream<Item = ports::fuel::Result<FuelBlock>> + Send + Sync>>;
+
+/// Follows the fuel chain through `ports::fuel::Api::poll_blocks_from`. Should an adapter's stream
+/// fail or end nonetheless, the owner is left to poll the node until it follows the chain again.
+#[derive(Default)]
+pub(crate) struct BlockFeed {
+    stream: Option<BlockStream>,
+}
+
+impl BlockFeed {
+    pub fn follow(&mut self, fuel_adapter: &impl ports::fuel::Api, height: u32) {
+        self.stream = Some(fuel_adapter.poll_blocks_from(height));
+    }
+
+    pub fn stop(&mut self) {
+        self.stream = None;
+    }
+
+    /// Blocks that arrived since the last call, without waiting for more. `None` if the chain
+    /// isn't followed, in which case it has to be polled.
+    pub fn ready_blocks(&mut self) -> Option<Vec<FuelBlock>> {
+        let stream = self.stream.as_mut()?;
+
+        let mut blocks = vec![];
+        while let Some(next) = stream.next().now_or_never() {
+            match next {
+                Some(Ok(block)) => blocks.push(block),
+                Some(Err(err)) => {
+                    warn!("fuel block stream failed, falling back to polling: {err}");
+                    self.stream = None;
+                    break;
+                }
+                None => {
+                    warn!("fuel block stream ended, falling back to polling");
+                    self.stream = None;
+                    break;
+                }
+            }
+        }
+
+        Some(blocks)
+    }
+}
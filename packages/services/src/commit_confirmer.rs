@@ -0,0 +1,199 @@
+use async_trait::async_trait;
+use metrics::{
+    prometheus::{core::Collector, IntCounter, Opts},
+    RegistersMetrics,
+};
+use ports::{storage::Storage, types::L1Height};
+use tracing::{info, warn};
+
+use super::Runner;
+
+/// Completes block submissions once their commit is `confirmation_depth` blocks deep and
+/// re-queues the ones whose commit block is no longer canonical.
+pub struct CommitConfirmer<L1, Db> {
+    l1_adapter: L1,
+    storage: Db,
+    confirmation_depth: u64,
+    reorg_lookback: u64,
+    metrics: Metrics,
+}
+
+impl<L1, Db> CommitConfirmer<L1, Db> {
+    /// Commits up to `reorg_lookback` blocks below the confirmation depth are still checked for
+    /// having been reorged out.
+    pub fn new(l1_adapter: L1, storage: Db, confirmation_depth: u64, reorg_lookback: u64) -> Self {
+        Self {
+            l1_adapter,
+            storage,
+            confirmation_depth,
+            reorg_lookback,
+            metrics: Metrics::default(),
+        }
+    }
+}
+
+#[async_trait]
+impl<L1, Db> Runner for CommitConfirmer<L1, Db>
+where
+    L1: ports::l1::Api + Send + Sync,
+    Db: Storage,
+{
+    async fn run(&mut self) -> crate::Result<()> {
+        let current_height = self.l1_adapter.get_block_number().await?;
+
+        let lookback = self.confirmation_depth.saturating_add(self.reorg_lookback);
+        let since = L1Height::try_from(u64::from(current_height).saturating_sub(lookback))
+            .map_err(|e| crate::Error::Other(e.to_string()))?;
+
+        for submission in self.storage.submissions_committed_since(since).await? {
+            let Some(location) = submission.commit_location else {
+                continue;
+            };
+
+            let canonical_hash = self.l1_adapter.get_block_hash(location.l1_height).await?;
+            if canonical_hash != Some(location.l1_block_hash) {
+                warn!(
+                    "commit of fuel block {} was reorged out of l1 block {:?}, requeueing",
+                    submission.block_height, location.l1_height
+                );
+                self.storage
+                    .requeue_submission(submission.block_hash, current_height)
+                    .await?;
+                self.metrics.block_commit_reorgs.inc();
+                continue;
+            }
+
+            if submission.completed
+                || location.confirmations(current_height) < self.confirmation_depth
+            {
+                continue;
+            }
+
+            self.storage
+                .set_submission_completed(submission.block_hash)
+                .await?;
+            info!(
+                "commit of fuel block {} confirmed in l1 block {:?}",
+                submission.block_height, location.l1_height
+            );
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Clone)]
+struct Metrics {
+    block_commit_reorgs: IntCounter,
+}
+
+impl<L1, Db> RegistersMetrics for CommitConfirmer<L1, Db> {
+    fn metrics(&self) -> Vec<Box<dyn Collector>> {
+        vec![Box::new(self.metrics.block_commit_reorgs.clone())]
+    }
+}
+
+impl Default for Metrics {
+    fn default() -> Self {
+        let block_commit_reorgs = IntCounter::with_opts(Opts::new(
+            "block_commit_reorgs",
+            "Number of fuel block commits that were reorged out of L1.",
+        ))
+        .expect("block_commit_reorgs metric to be correctly configured");
+
+        Self {
+            block_commit_reorgs,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mockall::predicate;
+    use ports::types::{BlockSubmission, CommitLocation};
+    use rand::Rng;
+    use storage::{Postgres, PostgresProcess};
+
+    use super::*;
+
+    const COMMIT_BLOCK_HASH: [u8; 32] = [2; 32];
+
+    fn given_l1(current_height: u32, canonical_hash: [u8; 32]) -> ports::l1::MockApi {
+        let mut l1 = ports::l1::MockApi::new();
+        l1.expect_get_block_number()
+            .returning(move || Ok(current_height.into()));
+        l1.expect_get_block_hash()
+            .with(predicate::eq(L1Height::from(10u32)))
+            .returning(move |_| Ok(Some(canonical_hash)));
+
+        l1
+    }
+
+    async fn given_db_with_commit(process: &PostgresProcess, completed: bool) -> Postgres {
+        let db = process.create_random_db().await.unwrap();
+
+        let submission = BlockSubmission {
+            completed,
+            commit_location: Some(CommitLocation {
+                l1_height: 10u32.into(),
+                l1_block_hash: COMMIT_BLOCK_HASH,
+            }),
+            ..rand::thread_rng().gen()
+        };
+        db.insert(submission).await.unwrap();
+
+        db
+    }
+
+    #[tokio::test]
+    async fn completes_submission_once_deep_enough() {
+        // given
+        let process = PostgresProcess::shared().await.unwrap();
+        let db = given_db_with_commit(&process, false).await;
+
+        let mut confirmer =
+            CommitConfirmer::new(given_l1(12, COMMIT_BLOCK_HASH), db.clone(), 3, 128);
+
+        // when
+        confirmer.run().await.unwrap();
+
+        // then
+        let submission = db.submission_w_latest_block().await.unwrap().unwrap();
+        assert!(submission.completed);
+    }
+
+    #[tokio::test]
+    async fn waits_for_confirmation_depth() {
+        // given
+        let process = PostgresProcess::shared().await.unwrap();
+        let db = given_db_with_commit(&process, false).await;
+
+        let mut confirmer =
+            CommitConfirmer::new(given_l1(11, COMMIT_BLOCK_HASH), db.clone(), 3, 128);
+
+        // when
+        confirmer.run().await.unwrap();
+
+        // then
+        let submission = db.submission_w_latest_block().await.unwrap().unwrap();
+        assert!(!submission.completed);
+    }
+
+    #[tokio::test]
+    async fn requeues_completed_submission_reorged_out() {
+        // given
+        let process = PostgresProcess::shared().await.unwrap();
+        let db = given_db_with_commit(&process, true).await;
+
+        let mut confirmer = CommitConfirmer::new(given_l1(20, [3; 32]), db.clone(), 3, 128);
+
+        // when
+        confirmer.run().await.unwrap();
+
+        // then
+        let submission = db.submission_w_latest_block().await.unwrap().unwrap();
+        assert!(!submission.completed);
+        assert_eq!(submission.commit_location, None);
+        assert_eq!(submission.retry_at, 20u32.into());
+    }
+}
@@ -6,10 +6,10 @@ use metrics::{
 };
 use ports::{
     storage::Storage,
-    types::{FuelBlockCommittedOnL1, L1Height},
+    types::{CommitLocation, FuelBlockCommittedOnL1, L1Height},
 };
 use tokio_util::sync::CancellationToken;
-use tracing::{error, info};
+use tracing::{error, info, warn};
 
 use super::Runner;
 
@@ -48,11 +48,26 @@ where
         &self,
         committed_on_l1: FuelBlockCommittedOnL1,
     ) -> crate::Result<()> {
+        if committed_on_l1.removed {
+            warn!("block commit reorged out of l1 {committed_on_l1:?}");
+
+            self.storage
+                .revert_commit_event(committed_on_l1.fuel_block_hash)
+                .await?;
+
+            return Ok(());
+        }
+
         info!("block committed on l1 {committed_on_l1:?}");
 
+        // the submission is completed by the `CommitConfirmer` once the commit is deep enough
+        let location = CommitLocation {
+            l1_height: committed_on_l1.l1_height,
+            l1_block_hash: committed_on_l1.l1_block_hash,
+        };
         let submission = self
             .storage
-            .set_submission_completed(committed_on_l1.fuel_block_hash)
+            .record_commit_event(committed_on_l1.fuel_block_hash, location)
             .await?;
 
         self.metrics
@@ -79,7 +94,7 @@ where
         let height = self.determine_starting_l1_height().await?;
 
         self.contract
-            .event_streamer(height)
+            .event_streamer(height)?
             .establish_stream()
             .await?
             .map_err(Into::into)
@@ -128,7 +143,7 @@ mod tests {
     use ports::{
         l1::{MockContract, MockEventStreamer},
         storage::Storage,
-        types::{BlockSubmission, FuelBlockCommittedOnL1, L1Height, U256},
+        types::{BlockSubmission, CommitLocation, FuelBlockCommittedOnL1, L1Height, U256},
     };
     use rand::Rng;
     use storage::{Postgres, PostgresProcess};
@@ -137,7 +152,7 @@ mod tests {
     use crate::{CommitListener, Runner};
 
     #[tokio::test]
-    async fn listener_will_update_storage_if_event_is_emitted() {
+    async fn listener_will_record_commit_location_if_event_is_emitted() {
         use ports::storage::Storage;
         // given
         let mut rng = rand::thread_rng();
@@ -147,7 +162,10 @@ mod tests {
         };
         let block_hash = submission.block_hash;
 
-        let contract = given_contract_with_events(vec![block_hash], submission.submittal_height);
+        let contract = given_contract_with_events(
+            vec![given_event(block_hash, false)],
+            submission.submittal_height,
+        );
 
         let process = PostgresProcess::shared().await.unwrap();
         let db = db_with_submission(&process, submission).await;
@@ -161,7 +179,41 @@ mod tests {
         //then
         let res = db.submission_w_latest_block().await.unwrap().unwrap();
 
-        assert!(res.completed);
+        assert_eq!(res.commit_location, Some(given_location()));
+        assert!(!res.completed);
+    }
+
+    #[tokio::test]
+    async fn listener_will_revert_submission_if_event_is_removed() {
+        use ports::storage::Storage;
+        // given
+        let mut rng = rand::thread_rng();
+        let submission = BlockSubmission {
+            completed: true,
+            commit_location: Some(given_location()),
+            ..rng.gen()
+        };
+        let block_hash = submission.block_hash;
+
+        let contract = given_contract_with_events(
+            vec![given_event(block_hash, true)],
+            submission.submittal_height,
+        );
+
+        let process = PostgresProcess::shared().await.unwrap();
+        let db = db_with_submission(&process, submission).await;
+
+        let mut commit_listener =
+            CommitListener::new(contract, db.clone(), CancellationToken::default());
+
+        // when
+        commit_listener.run().await.unwrap();
+
+        //then
+        let res = db.submission_w_latest_block().await.unwrap().unwrap();
+
+        assert_eq!(res.commit_location, None);
+        assert!(!res.completed);
     }
 
     #[tokio::test]
@@ -175,7 +227,10 @@ mod tests {
         let block_hash = submission.block_hash;
         let fuel_block_height = submission.block_height;
 
-        let contract = given_contract_with_events(vec![block_hash], submission.submittal_height);
+        let contract = given_contract_with_events(
+            vec![given_event(block_hash, false)],
+            submission.submittal_height,
+        );
 
         let process = PostgresProcess::shared().await.unwrap();
         let db = db_with_submission(&process, submission).await;
@@ -214,7 +269,10 @@ mod tests {
         let incoming_hash = incoming_block.block_hash;
 
         let contract = given_contract_with_events(
-            vec![missing_hash, incoming_hash],
+            vec![
+                given_event(missing_hash, false),
+                given_event(incoming_hash, false),
+            ],
             incoming_block.submittal_height,
         );
 
@@ -231,7 +289,7 @@ mod tests {
         let latest_submission = db.submission_w_latest_block().await.unwrap().unwrap();
         assert_eq!(
             BlockSubmission {
-                completed: true,
+                commit_location: Some(given_location()),
                 ..incoming_block.clone()
             },
             latest_submission
@@ -249,8 +307,26 @@ mod tests {
         db
     }
 
+    fn given_location() -> CommitLocation {
+        CommitLocation {
+            l1_height: 10u32.into(),
+            l1_block_hash: [2; 32],
+        }
+    }
+
+    fn given_event(fuel_block_hash: [u8; 32], removed: bool) -> FuelBlockCommittedOnL1 {
+        let location = given_location();
+        FuelBlockCommittedOnL1 {
+            fuel_block_hash,
+            commit_height: U256::default(),
+            l1_height: location.l1_height,
+            l1_block_hash: location.l1_block_hash,
+            removed,
+        }
+    }
+
     fn given_contract_with_events(
-        events: Vec<[u8; 32]>,
+        events: Vec<FuelBlockCommittedOnL1>,
         starting_from_height: L1Height,
     ) -> MockContract {
         let mut contract = MockContract::new();
@@ -259,21 +335,14 @@ mod tests {
         contract
             .expect_event_streamer()
             .with(predicate::eq(starting_from_height))
-            .return_once(move |_| event_streamer);
+            .return_once(move |_| Ok(event_streamer));
 
         contract
     }
 
-    fn given_event_streamer_w_events(events: Vec<[u8; 32]>) -> MockEventStreamer {
+    fn given_event_streamer_w_events(events: Vec<FuelBlockCommittedOnL1>) -> MockEventStreamer {
         let mut streamer = MockEventStreamer::new();
-        let events = events
-            .into_iter()
-            .map(|block_hash| FuelBlockCommittedOnL1 {
-                fuel_block_hash: block_hash,
-                commit_height: U256::default(),
-            })
-            .map(Ok)
-            .collect::<Vec<_>>();
+        let events = events.into_iter().map(Ok).collect::<Vec<_>>();
 
         streamer
             .expect_establish_stream()
@@ -0,0 +1,180 @@
+use async_trait::async_trait;
+use ports::{
+    storage::Storage,
+    types::{CommitLocation, L1Height},
+};
+use tracing::{info, warn};
+
+use super::Runner;
+
+/// Records where block commits landed through the receipt of their tx. Covers commits whose event
+/// the `CommitListener` missed and detects the ones that reverted.
+pub struct CommitReceiptListener<L1, Db> {
+    l1_adapter: L1,
+    storage: Db,
+}
+
+impl<L1, Db> CommitReceiptListener<L1, Db> {
+    pub fn new(l1_adapter: L1, storage: Db) -> Self {
+        Self {
+            l1_adapter,
+            storage,
+        }
+    }
+}
+
+#[async_trait]
+impl<L1, Db> Runner for CommitReceiptListener<L1, Db>
+where
+    L1: ports::l1::Api + Send + Sync,
+    Db: Storage,
+{
+    async fn run(&mut self) -> crate::Result<()> {
+        for submission in self.storage.submissions_awaiting_receipt().await? {
+            let Some(tx_hash) = submission.tx_hash else {
+                continue;
+            };
+
+            let Some(receipt) = self.l1_adapter.get_transaction_response(tx_hash).await? else {
+                continue; // not included yet
+            };
+
+            let l1_height = L1Height::try_from(receipt.block_number())
+                .map_err(|e| crate::Error::Other(e.to_string()))?;
+
+            let submission = self
+                .storage
+                .set_submission_receipt(submission.block_hash, receipt)
+                .await?;
+
+            if !receipt.succeeded() {
+                warn!(
+                    "commit tx {} of fuel block {} reverted",
+                    hex::encode(tx_hash),
+                    submission.block_height
+                );
+                continue;
+            }
+
+            if submission.commit_location.is_some() {
+                continue; // already recorded through the commit event
+            }
+
+            self.storage
+                .record_commit_event(
+                    submission.block_hash,
+                    CommitLocation {
+                        l1_height,
+                        l1_block_hash: receipt.block_hash(),
+                    },
+                )
+                .await?;
+            info!(
+                "fuel block {} committed in l1 block {}",
+                submission.block_height,
+                receipt.block_number()
+            );
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mockall::predicate;
+    use ports::types::BlockSubmissionState;
+    use ports::types::{BlockSubmission, CommitLocation, TransactionResponse};
+    use rand::Rng;
+    use storage::{Postgres, PostgresProcess};
+
+    use super::*;
+
+    const TX_HASH: [u8; 32] = [1; 32];
+    const COMMIT_BLOCK_HASH: [u8; 32] = [2; 32];
+
+    fn given_l1_with_receipt(receipt: Option<TransactionResponse>) -> ports::l1::MockApi {
+        let mut l1 = ports::l1::MockApi::new();
+        l1.expect_get_transaction_response()
+            .with(predicate::eq(TX_HASH))
+            .return_once(move |_| Ok(receipt));
+        l1.expect_get_block_hash().never();
+
+        l1
+    }
+
+    async fn given_db_with_sent_commit(process: &PostgresProcess) -> (Postgres, BlockSubmission) {
+        let db = process.create_random_db().await.unwrap();
+
+        let submission = BlockSubmission {
+            completed: false,
+            tx_hash: Some(TX_HASH),
+            ..rand::thread_rng().gen()
+        };
+        db.insert(submission.clone()).await.unwrap();
+
+        (db, submission)
+    }
+
+    #[tokio::test]
+    async fn successful_commit_tx_records_the_commit_location() {
+        // given
+        let process = PostgresProcess::shared().await.unwrap();
+        let (db, submission) = given_db_with_sent_commit(&process).await;
+
+        let receipt = TransactionResponse::new(10, COMMIT_BLOCK_HASH, true, 50_000, 1_000_000_000);
+        let mut listener =
+            CommitReceiptListener::new(given_l1_with_receipt(Some(receipt)), db.clone());
+
+        // when
+        listener.run().await.unwrap();
+
+        // then
+        let stored = db.submission_w_latest_block().await.unwrap().unwrap();
+        assert_eq!(
+            stored,
+            BlockSubmission {
+                tx_receipt: Some(receipt),
+                commit_location: Some(CommitLocation {
+                    l1_height: 10u32.into(),
+                    l1_block_hash: COMMIT_BLOCK_HASH,
+                }),
+                ..submission
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn reverted_commit_tx_fails_the_submission() {
+        // given
+        let process = PostgresProcess::shared().await.unwrap();
+        let (db, _) = given_db_with_sent_commit(&process).await;
+
+        let receipt = TransactionResponse::new(10, COMMIT_BLOCK_HASH, false, 50_000, 1_000_000_000);
+        let mut listener =
+            CommitReceiptListener::new(given_l1_with_receipt(Some(receipt)), db.clone());
+
+        // when
+        listener.run().await.unwrap();
+
+        // then
+        let stored = db.submission_w_latest_block().await.unwrap().unwrap();
+        assert_eq!(stored.state(), BlockSubmissionState::Failed);
+        assert!(!stored.completed);
+    }
+
+    #[tokio::test]
+    async fn commit_tx_without_receipt_is_left_pending() {
+        // given
+        let process = PostgresProcess::shared().await.unwrap();
+        let (db, _) = given_db_with_sent_commit(&process).await;
+
+        let mut listener = CommitReceiptListener::new(given_l1_with_receipt(None), db.clone());
+
+        // when
+        listener.run().await.unwrap();
+
+        // then
+        assert_eq!(db.submissions_awaiting_receipt().await.unwrap().len(), 1);
+    }
+}
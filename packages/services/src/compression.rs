@@ -0,0 +1,112 @@
+use ports::types::Compression;
+
+use crate::{Error, Result};
+
+/// Compresses the state of fuel blocks before it is split into fragments.
+#[derive(Debug, Clone, Copy)]
+pub struct Compressor {
+    compression: Compression,
+    level: u32,
+}
+
+impl Compressor {
+    /// `level` is passed to the codec as is. Codecs clamp it to their supported range (zstd: 1-22,
+    /// brotli: 0-11).
+    pub fn new(compression: Compression, level: u32) -> Self {
+        Self { compression, level }
+    }
+
+    pub fn no_compression() -> Self {
+        Self::new(Compression::None, 0)
+    }
+
+    pub fn compression(&self) -> Compression {
+        self.compression
+    }
+
+    pub fn compress(&self, data: &[u8]) -> Result<Vec<u8>> {
+        match self.compression {
+            Compression::None => Ok(data.to_vec()),
+            Compression::Zstd => {
+                let level = i32::try_from(self.level).unwrap_or(i32::MAX);
+                zstd::bulk::compress(data, level).map_err(compression_error)
+            }
+            Compression::Brotli => {
+                let params = brotli::enc::BrotliEncoderParams {
+                    quality: i32::try_from(self.level).unwrap_or(i32::MAX),
+                    ..Default::default()
+                };
+
+                let mut compressed = vec![];
+                brotli::BrotliCompress(&mut &data[..], &mut compressed, &params)
+                    .map_err(compression_error)?;
+
+                Ok(compressed)
+            }
+        }
+    }
+
+    pub fn decompress(compression: Compression, data: &[u8]) -> Result<Vec<u8>> {
+        match compression {
+            Compression::None => Ok(data.to_vec()),
+            Compression::Zstd => zstd::stream::decode_all(data).map_err(compression_error),
+            Compression::Brotli => {
+                let mut decompressed = vec![];
+                brotli::BrotliDecompress(&mut &data[..], &mut decompressed)
+                    .map_err(compression_error)?;
+
+                Ok(decompressed)
+            }
+        }
+    }
+}
+
+fn compression_error(error: std::io::Error) -> Error {
+    Error::Other(format!("compression failed: {error}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn given_state() -> Vec<u8> {
+        (0..10_000u32)
+            .flat_map(|i| (i % 64).to_be_bytes())
+            .collect()
+    }
+
+    #[test]
+    fn codecs_round_trip() {
+        for compression in [Compression::None, Compression::Zstd, Compression::Brotli] {
+            // given
+            let state = given_state();
+            let compressor = Compressor::new(compression, 5);
+
+            // when
+            let compressed = compressor.compress(&state).unwrap();
+
+            // then
+            let decompressed = Compressor::decompress(compression, &compressed).unwrap();
+            assert_eq!(decompressed, state, "{compression:?} failed to round trip");
+        }
+    }
+
+    #[test]
+    fn codecs_shrink_redundant_state() {
+        for compression in [Compression::Zstd, Compression::Brotli] {
+            // given
+            let state = given_state();
+            let compressor = Compressor::new(compression, 5);
+
+            // when
+            let compressed = compressor.compress(&state).unwrap();
+
+            // then
+            assert!(
+                compressed.len() < state.len() / 10,
+                "{compression:?} compressed to {} bytes",
+                compressed.len()
+            );
+        }
+    }
+}
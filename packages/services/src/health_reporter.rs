@@ -1,37 +1,59 @@
+use std::collections::BTreeMap;
+
 use metrics::HealthChecker;
 use serde::Serialize;
+use validator::ChainContinuity;
 
 #[derive(Debug, Serialize)]
 pub struct HealthReport {
     fuel_connection_up: bool,
+    /// Whether any of the eth endpoints is healthy.
     eth_connection_healthy: bool,
+    eth_endpoints: BTreeMap<String, bool>,
+    /// `false` once the fuel node was caught serving a chain other than the committed one.
+    fuel_chain_intact: bool,
 }
 
 impl HealthReport {
     pub fn healthy(&self) -> bool {
-        self.fuel_connection_up && self.eth_connection_healthy
+        self.fuel_connection_up && self.eth_connection_healthy && self.fuel_chain_intact
     }
 }
 
 pub struct HealthReporter {
     fuel_connection: HealthChecker,
-    eth_connection: HealthChecker,
+    eth_endpoints: Vec<(String, HealthChecker)>,
+    chain_continuity: ChainContinuity,
 }
 
 impl HealthReporter {
+    /// `eth_health_checks` holds the health check of every eth endpoint, by name.
     #[must_use]
-    pub fn new(fuel_health_check: HealthChecker, eth_health_check: HealthChecker) -> Self {
+    pub fn new(
+        fuel_health_check: HealthChecker,
+        eth_health_checks: Vec<(String, HealthChecker)>,
+        chain_continuity: ChainContinuity,
+    ) -> Self {
         Self {
             fuel_connection: fuel_health_check,
-            eth_connection: eth_health_check,
+            eth_endpoints: eth_health_checks,
+            chain_continuity,
         }
     }
 
     #[must_use]
     pub fn report(&self) -> HealthReport {
+        let eth_endpoints: BTreeMap<_, _> = self
+            .eth_endpoints
+            .iter()
+            .map(|(name, health_check)| (name.clone(), health_check.healthy()))
+            .collect();
+
         HealthReport {
             fuel_connection_up: self.fuel_connection.healthy(),
-            eth_connection_healthy: self.eth_connection.healthy(),
+            eth_connection_healthy: eth_endpoints.values().any(|healthy| *healthy),
+            eth_endpoints,
+            fuel_chain_intact: self.chain_continuity.is_intact(),
         }
     }
 }
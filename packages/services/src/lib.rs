@@ -1,21 +1,35 @@
 #![deny(unused_crate_dependencies)]
 mod block_committer;
+mod block_feed;
+mod commit_confirmer;
 mod commit_listener;
+mod commit_receipt_listener;
+mod compression;
 mod health_reporter;
 mod status_reporter;
 mod wallet_balance_tracker;
 
 mod state_committer;
 mod state_importer;
+mod state_listener;
+mod state_verifier;
 
 pub use block_committer::BlockCommitter;
+pub use commit_confirmer::CommitConfirmer;
 pub use commit_listener::CommitListener;
+pub use commit_receipt_listener::CommitReceiptListener;
+pub use compression::Compressor;
 pub use health_reporter::HealthReporter;
 pub use status_reporter::StatusReporter;
 pub use wallet_balance_tracker::WalletBalanceTracker;
 
-pub use state_committer::StateCommitter;
+pub use state_committer::{BatchingPolicy, FeeCeiling, StateCommitter};
 pub use state_importer::StateImporter;
+pub use state_listener::StateListener;
+pub use state_verifier::{StateReport, StateVerification, StateVerifier};
+
+/// Fees of a replacement tx get doubled so that nodes accept it in place of the stuck one.
+const REPLACEMENT_FEE_BUMP_PERCENTAGE: u32 = 100;
 
 #[derive(thiserror::Error, Debug)]
 pub enum Error {
@@ -27,6 +41,8 @@ pub enum Error {
     Storage(String),
     #[error("Block validation error: {0}")]
     BlockValidation(String),
+    #[error("Chain discontinuity: {0}")]
+    ChainDiscontinuity(String),
 }
 
 impl From<ports::l1::Error> for Error {
@@ -38,6 +54,15 @@ impl From<ports::l1::Error> for Error {
     }
 }
 
+impl From<ports::da::Error> for Error {
+    fn from(error: ports::da::Error) -> Self {
+        match error {
+            ports::da::Error::Network(e) => Self::Network(e),
+            _ => Self::Other(error.to_string()),
+        }
+    }
+}
+
 impl From<ports::fuel::Error> for Error {
     fn from(error: ports::fuel::Error) -> Self {
         match error {
@@ -50,6 +75,10 @@ impl From<validator::Error> for Error {
     fn from(error: validator::Error) -> Self {
         match error {
             validator::Error::BlockValidation(e) => Self::BlockValidation(e),
+            validator::Error::ChainDiscontinuity(e) => Self::ChainDiscontinuity(e),
+            error @ validator::Error::TransactionsMismatch(_) => {
+                Self::BlockValidation(error.to_string())
+            }
         }
     }
 }
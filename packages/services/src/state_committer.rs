@@ -1,69 +1,336 @@
 use async_trait::async_trait;
-use ports::{storage::Storage, types::StateFragmentId};
+use std::{
+    collections::{hash_map::Entry, HashMap},
+    time::{Duration, Instant},
+};
+
+use metrics::{
+    prometheus::{core::Collector, IntCounter, Opts},
+    RegistersMetrics,
+};
+use ports::{
+    storage::Storage,
+    types::{BaseFees, DateTime, StateFragment, StateFragmentId, StateFrame, SubmissionTx, Utc},
+};
+use tracing::{info, warn};
 
 use crate::{Result, Runner};
 
-pub struct StateCommitter<L1, Db> {
-    l1_adapter: L1,
+/// Decides when enough state accumulated to be worth the fixed cost of a submission.
+#[derive(Debug, Clone, Copy)]
+pub struct BatchingPolicy {
+    min_units: usize,
+    max_latency: Duration,
+}
+
+impl BatchingPolicy {
+    /// State is posted once it fills `min_units` payload units of the data availability layer,
+    /// e.g. blobs, or once the oldest unsubmitted fragment waited for `max_latency`, whichever
+    /// comes first. A full submission is posted regardless of `min_units`.
+    pub fn new(min_units: usize, max_latency: Duration) -> Self {
+        Self {
+            min_units,
+            max_latency,
+        }
+    }
+
+    fn is_ready(
+        &self,
+        payload_size: usize,
+        payload_unit: usize,
+        max_payload_size: usize,
+        oldest_fragment: DateTime<Utc>,
+    ) -> bool {
+        let min_payload_size = self
+            .min_units
+            .saturating_mul(payload_unit)
+            .min(max_payload_size);
+        if payload_size >= min_payload_size {
+            return true;
+        }
+
+        // a negative age means the clocks of the db and the committer disagree, don't wait on it
+        let waited = (Utc::now() - oldest_fragment)
+            .to_std()
+            .unwrap_or(Duration::MAX);
+        waited >= self.max_latency
+    }
+}
+
+/// Base fees above which posting state is deferred, for at most `max_deferral`.
+#[derive(Debug, Clone, Copy)]
+pub struct FeeCeiling {
+    max_base_fee_per_gas: u128,
+    max_base_fee_per_blob_gas: u128,
+    max_deferral: Duration,
+}
+
+impl FeeCeiling {
+    pub fn new(
+        max_base_fee_per_gas: u128,
+        max_base_fee_per_blob_gas: u128,
+        max_deferral: Duration,
+    ) -> Self {
+        Self {
+            max_base_fee_per_gas,
+            max_base_fee_per_blob_gas,
+            max_deferral,
+        }
+    }
+
+    /// Posts state regardless of the fees.
+    pub fn none() -> Self {
+        Self::new(u128::MAX, u128::MAX, Duration::ZERO)
+    }
+
+    fn is_exceeded_by(&self, fees: &BaseFees) -> bool {
+        fees.base_fee_per_gas > self.max_base_fee_per_gas
+            || fees
+                .base_fee_per_blob_gas
+                .is_some_and(|fee| fee > self.max_base_fee_per_blob_gas)
+    }
+}
+
+/// Posts state to the data availability layer `Da`.
+pub struct StateCommitter<Da, Db> {
+    da: Da,
     storage: Db,
+    num_blocks_before_replacement: u64,
+    max_tx_fee: u128,
+    batching: BatchingPolicy,
+    fee_ceiling: FeeCeiling,
+    deferring_since: Option<Instant>,
+    metrics: Metrics,
 }
 
-impl<L1, Db> StateCommitter<L1, Db> {
-    pub fn new(l1: L1, storage: Db) -> Self {
+impl<Da, Db> StateCommitter<Da, Db> {
+    pub fn new(
+        da: Da,
+        storage: Db,
+        num_blocks_before_replacement: u64,
+        max_tx_fee: u128,
+        batching: BatchingPolicy,
+        fee_ceiling: FeeCeiling,
+    ) -> Self {
         Self {
-            l1_adapter: l1,
+            da,
             storage,
+            num_blocks_before_replacement,
+            max_tx_fee,
+            batching,
+            fee_ceiling,
+            deferring_since: None,
+            metrics: Metrics::default(),
         }
     }
 }
 
-impl<L1, Db> StateCommitter<L1, Db>
+impl<Da, Db> StateCommitter<Da, Db>
 where
-    L1: ports::l1::Api,
+    Da: ports::da::Api,
     Db: Storage,
 {
-    async fn prepare_fragments(&self) -> Result<(Vec<StateFragmentId>, Vec<u8>)> {
-        let fragments = self.storage.get_unsubmitted_fragments().await?;
+    /// The fragments to post next, if the batching policy deems them worth a tx.
+    async fn prepare_fragments(&self) -> Result<Option<(Vec<StateFragmentId>, Vec<StateFrame>)>> {
+        // fetching up to a whole fragment more than a submission can carry tells whether all
+        // unsubmitted fragments fit into it: if they don't, packing drops at least one of them
+        let max_payload_size = self.da.max_payload_size();
+        let fragments = self
+            .storage
+            .get_unsubmitted_fragments(max_payload_size + self.da.max_fragment_size())
+            .await?;
+        let Some(oldest_fragment) = fragments.iter().map(|fragment| fragment.created_at).min()
+        else {
+            return Ok(None);
+        };
+
+        let (fragment_ids, frames) = self.to_frames(fragments).await?;
+        let num_available = frames.len();
+        let (fragment_ids, frames) = pack(fragment_ids, frames, max_payload_size);
+
+        let tx_is_full = frames.len() < num_available;
+        if !tx_is_full
+            && !self.batching.is_ready(
+                payload_size(&frames),
+                self.da.payload_unit(),
+                max_payload_size,
+                oldest_fragment,
+            )
+        {
+            return Ok(None);
+        }
 
-        let num_fragments = fragments.len();
-        let mut fragment_ids = Vec::with_capacity(num_fragments);
-        let mut data = Vec::with_capacity(num_fragments);
+        Ok(Some((fragment_ids, frames)))
+    }
+
+    /// Pairs the fragments with the details of their block so they can be framed.
+    async fn to_frames(
+        &self,
+        fragments: Vec<StateFragment>,
+    ) -> Result<(Vec<StateFragmentId>, Vec<StateFrame>)> {
+        let mut submissions = HashMap::new();
+        let mut fragment_ids = Vec::with_capacity(fragments.len());
+        let mut frames = Vec::with_capacity(fragments.len());
         for fragment in fragments {
+            let submission = match submissions.entry(fragment.block_hash) {
+                Entry::Occupied(entry) => entry.into_mut(),
+                Entry::Vacant(entry) => {
+                    let submission = self
+                        .storage
+                        .state_submission(fragment.block_hash)
+                        .await?
+                        .ok_or_else(|| {
+                            crate::Error::Storage(format!(
+                                "no state submission for fragment of block {}",
+                                hex::encode(fragment.block_hash)
+                            ))
+                        })?;
+                    entry.insert(submission)
+                }
+            };
+
             fragment_ids.push(fragment.id());
-            data.extend(fragment.raw_data);
+            frames.push(StateFrame::new(submission, fragment));
+        }
+
+        Ok((fragment_ids, frames))
+    }
+
+    /// `true` while the base fees are above the ceiling and the state wasn't deferred for too
+    /// long yet.
+    fn should_defer(&mut self, fees: &BaseFees) -> bool {
+        if !self.fee_ceiling.is_exceeded_by(fees) {
+            return false;
+        }
+
+        let deferring_since = *self.deferring_since.get_or_insert_with(Instant::now);
+        if deferring_since.elapsed() < self.fee_ceiling.max_deferral {
+            info!(
+                "deferring state submission: base fee {} wei, blob base fee {:?} wei",
+                fees.base_fee_per_gas, fees.base_fee_per_blob_gas
+            );
+            return true;
         }
 
-        Ok((fragment_ids, data))
+        warn!(
+            "submitting state despite base fee {} wei and blob base fee {:?} wei, deferred for {:?} already",
+            fees.base_fee_per_gas,
+            fees.base_fee_per_blob_gas,
+            deferring_since.elapsed()
+        );
+
+        false
     }
 
-    async fn submit_state(&self) -> Result<()> {
-        let (fragment_ids, data) = self.prepare_fragments().await?;
-        if fragment_ids.is_empty() {
+    async fn submit_state(&mut self) -> Result<()> {
+        let Some((fragment_ids, frames)) = self.prepare_fragments().await? else {
+            return Ok(());
+        };
+
+        let fees = self.da.base_fees().await?;
+        if self.should_defer(&fees) {
             return Ok(());
         }
 
-        let tx_hash = self.l1_adapter.submit_l2_state(data).await?;
+        let current_height = self.da.height().await?;
+        let (submission, num_submitted) = self.da.submit(frames).await?;
+        // the layer may carry fewer frames per submission than a blob tx, the rest waits
+        let mut fragment_ids = fragment_ids;
+        fragment_ids.truncate(num_submitted);
         self.storage
-            .record_pending_tx(tx_hash, fragment_ids)
+            .record_pending_tx(
+                SubmissionTx::pending(submission, current_height),
+                fragment_ids,
+            )
             .await?;
 
+        if let Some(deferring_since) = self.deferring_since.take() {
+            self.metrics
+                .state_submission_deferral_seconds
+                .inc_by(deferring_since.elapsed().as_secs());
+        }
+
         Ok(())
     }
 
-    async fn is_tx_pending(&self) -> Result<bool> {
-        self.storage.has_pending_txs().await.map_err(|e| e.into())
+    async fn replace_if_stuck(&self, pending_tx: SubmissionTx) -> Result<()> {
+        let current_height = self.da.height().await?;
+
+        let blocks_pending =
+            u64::from(current_height).saturating_sub(pending_tx.submitted_at.into());
+        if blocks_pending < self.num_blocks_before_replacement {
+            return Ok(());
+        }
+
+        let submission = pending_tx.submission();
+        let max_total_fee = self.da.replacement_fee(&submission)?;
+        if max_total_fee > self.max_tx_fee {
+            warn!(
+                "not replacing state tx {} pending for {blocks_pending} blocks: fee of {max_total_fee} wei would exceed the limit of {} wei",
+                hex::encode(pending_tx.hash),
+                self.max_tx_fee
+            );
+            return Ok(());
+        }
+
+        let fragments = self.storage.get_tx_fragments(pending_tx.hash).await?;
+        let (fragment_ids, frames) = self.to_frames(fragments).await?;
+
+        let replacement = self.da.replace(frames, &submission).await?;
+        let replacement_hash = replacement.hash;
+        self.storage
+            .record_pending_tx(
+                SubmissionTx::pending(replacement, current_height),
+                fragment_ids,
+            )
+            .await?;
+
+        info!(
+            "replaced state tx {} pending for {blocks_pending} blocks with {}",
+            hex::encode(pending_tx.hash),
+            hex::encode(replacement_hash)
+        );
+
+        Ok(())
     }
 }
 
+/// Size of the framed payload carrying `frames`.
+fn payload_size(frames: &[StateFrame]) -> usize {
+    StateFrame::PAYLOAD_HEADER_SIZE + frames.iter().map(StateFrame::encoded_len).sum::<usize>()
+}
+
+/// Keeps the oldest frames that, together with the framing overhead, fit into `max_payload_size`.
+fn pack(
+    mut fragment_ids: Vec<StateFragmentId>,
+    mut frames: Vec<StateFrame>,
+    max_payload_size: usize,
+) -> (Vec<StateFragmentId>, Vec<StateFrame>) {
+    let mut payload_size = StateFrame::PAYLOAD_HEADER_SIZE;
+    let num_fitting = frames
+        .iter()
+        .take_while(|frame| {
+            payload_size += frame.encoded_len();
+            payload_size <= max_payload_size
+        })
+        .count();
+
+    fragment_ids.truncate(num_fitting);
+    frames.truncate(num_fitting);
+
+    (fragment_ids, frames)
+}
+
 #[async_trait]
-impl<L1, Db> Runner for StateCommitter<L1, Db>
+impl<Da, Db> Runner for StateCommitter<Da, Db>
 where
-    L1: ports::l1::Api + Send + Sync,
+    Da: ports::da::Api,
     Db: Storage,
 {
     async fn run(&mut self) -> Result<()> {
-        if self.is_tx_pending().await? {
-            return Ok(());
+        // pending txs are ordered by submission height, the last one is the latest attempt
+        if let Some(pending_tx) = self.storage.get_pending_txs().await?.pop() {
+            return self.replace_if_stuck(pending_tx).await;
         };
 
         self.submit_state().await?;
@@ -72,49 +339,86 @@ where
     }
 }
 
+#[derive(Clone)]
+struct Metrics {
+    state_submission_deferral_seconds: IntCounter,
+}
+
+impl<Da, Db> RegistersMetrics for StateCommitter<Da, Db> {
+    fn metrics(&self) -> Vec<Box<dyn Collector>> {
+        vec![Box::new(
+            self.metrics.state_submission_deferral_seconds.clone(),
+        )]
+    }
+}
+
+impl Default for Metrics {
+    fn default() -> Self {
+        let state_submission_deferral_seconds = IntCounter::with_opts(Opts::new(
+            "state_submission_deferral_seconds",
+            "Seconds state submissions were deferred because of base fees above the ceiling.",
+        ))
+        .expect("state_submission_deferral_seconds metric to be correctly configured");
+
+        Self {
+            state_submission_deferral_seconds,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use mockall::predicate;
-    use ports::types::{L1Height, StateFragment, StateSubmission, U256};
-    use storage::PostgresProcess;
+    use ports::{
+        da::Api,
+        types::{Compression, DaSubmission, StateFragment, StateSubmission, SubmissionStatus},
+    };
+    use storage::{Postgres, PostgresProcess};
 
     use super::*;
 
-    struct MockL1 {
-        api: ports::l1::MockApi,
-    }
-    impl MockL1 {
-        fn new() -> Self {
-            Self {
-                api: ports::l1::MockApi::new(),
-            }
-        }
+    const PAYLOAD_UNIT: usize = 128 * 1024;
+    const UNITS_PER_SUBMISSION: usize = 6;
+    const MAX_PAYLOAD_SIZE: usize = UNITS_PER_SUBMISSION * PAYLOAD_UNIT;
+    const MAX_FRAGMENT_SIZE: usize =
+        PAYLOAD_UNIT - StateFrame::PAYLOAD_HEADER_SIZE - StateFrame::HEADER_SIZE;
+
+    fn given_da(current_height: u32) -> ports::da::MockApi {
+        given_da_with_base_fees(current_height, BaseFees::default())
     }
 
-    #[async_trait::async_trait]
-    impl ports::l1::Api for MockL1 {
-        async fn submit_l2_state(&self, state_data: Vec<u8>) -> ports::l1::Result<[u8; 32]> {
-            self.api.submit_l2_state(state_data).await
-        }
+    fn given_da_with_base_fees(current_height: u32, base_fees: BaseFees) -> ports::da::MockApi {
+        let mut da = ports::da::MockApi::new();
+        da.expect_height()
+            .returning(move || Ok(current_height.into()));
+        da.expect_base_fees().returning(move || Ok(base_fees));
+        da.expect_max_payload_size().return_const(MAX_PAYLOAD_SIZE);
+        da.expect_payload_unit().return_const(PAYLOAD_UNIT);
+        da.expect_max_fragment_size()
+            .return_const(MAX_FRAGMENT_SIZE);
 
-        async fn get_block_number(&self) -> ports::l1::Result<L1Height> {
-            Ok(0.into())
-        }
+        da
+    }
 
-        async fn balance(&self) -> ports::l1::Result<U256> {
-            Ok(U256::zero())
+    fn given_submission(hash: [u8; 32]) -> DaSubmission {
+        DaSubmission {
+            hash,
+            details: vec![7],
         }
     }
 
-    fn given_l1_that_expects_submission(fragment: StateFragment) -> MockL1 {
-        let mut l1 = MockL1::new();
+    fn given_da_that_expects_submission(frame: StateFrame) -> ports::da::MockApi {
+        let mut da = given_da(0);
 
-        l1.api
-            .expect_submit_l2_state()
-            .with(predicate::eq(fragment.raw_data))
-            .return_once(move |_| Ok([1u8; 32]));
+        da.expect_submit()
+            .with(predicate::eq(vec![frame]))
+            .return_once(move |frames| Ok((given_submission([1u8; 32]), frames.len())));
 
-        l1
+        da
+    }
+
+    fn given_no_batching() -> BatchingPolicy {
+        BatchingPolicy::new(1, Duration::ZERO)
     }
 
     fn given_state() -> (StateSubmission, StateFragment) {
@@ -123,6 +427,8 @@ mod tests {
                 block_hash: [0u8; 32],
                 block_height: 1,
                 completed: false,
+                compression: Compression::None,
+                num_fragments: 1,
             },
             StateFragment {
                 block_hash: [0u8; 32],
@@ -135,16 +441,40 @@ mod tests {
         )
     }
 
+    async fn given_db_with_pending_tx(process: &PostgresProcess) -> Result<Postgres> {
+        let (state, fragment) = given_state();
+        let fragment_id = fragment.id();
+
+        let db = process.create_random_db().await?;
+        db.insert_state(state, vec![fragment]).await?;
+
+        let submission = given_submission([1u8; 32]);
+        db.record_pending_tx(
+            SubmissionTx::pending(submission, 0u32.into()),
+            vec![fragment_id],
+        )
+        .await?;
+
+        Ok(db)
+    }
+
     #[tokio::test]
     async fn test_submit_state() -> Result<()> {
         // given
         let (state, fragment) = given_state();
-        let l1_mock = given_l1_that_expects_submission(fragment.clone());
+        let da_mock = given_da_that_expects_submission(StateFrame::new(&state, fragment.clone()));
 
         let process = PostgresProcess::shared().await.unwrap();
         let db = process.create_random_db().await?;
         db.insert_state(state, vec![fragment]).await?;
-        let mut committer = StateCommitter::new(l1_mock, db.clone());
+        let mut committer = StateCommitter::new(
+            da_mock,
+            db.clone(),
+            10,
+            u128::MAX,
+            given_no_batching(),
+            FeeCeiling::none(),
+        );
 
         // when
         committer.run().await.unwrap();
@@ -154,4 +484,355 @@ mod tests {
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn submitted_state_can_be_retrieved_from_the_da_layer() -> Result<()> {
+        // given
+        let (state, fragment) = given_state();
+        let expected_frames = vec![StateFrame::new(&state, fragment.clone())];
+
+        let process = PostgresProcess::shared().await.unwrap();
+        let db = process.create_random_db().await?;
+        db.insert_state(state, vec![fragment]).await?;
+
+        let da = ports::da::InMemory::default();
+        let mut committer = StateCommitter::new(
+            da.clone(),
+            db.clone(),
+            10,
+            u128::MAX,
+            given_no_batching(),
+            FeeCeiling::none(),
+        );
+
+        // when
+        committer.run().await.unwrap();
+
+        // then
+        let tx_hash = db.get_pending_txs().await?[0].hash;
+        assert_eq!(da.retrieve(tx_hash).await?, Some(expected_frames));
+        assert!(matches!(
+            da.confirm(tx_hash).await?,
+            SubmissionStatus::Included(_)
+        ));
+
+        Ok(())
+    }
+
+    fn given_state_with_fragments(
+        fragment_size: usize,
+        num_fragments: u32,
+    ) -> (StateSubmission, Vec<StateFragment>) {
+        let (state, fragment) = given_state();
+        let state = StateSubmission {
+            num_fragments,
+            ..state
+        };
+        let fragments = (0..num_fragments)
+            .map(|fragment_index| StateFragment {
+                fragment_index,
+                raw_data: vec![1; fragment_size],
+                ..fragment.clone()
+            })
+            .collect();
+
+        (state, fragments)
+    }
+
+    async fn submitted_frames_of(
+        state: StateSubmission,
+        fragments: Vec<StateFragment>,
+    ) -> Result<Vec<StateFrame>> {
+        let process = PostgresProcess::shared().await.unwrap();
+        let db = process.create_random_db().await?;
+        db.insert_state(state, fragments).await?;
+
+        let (sender, receiver) = std::sync::mpsc::channel();
+        let mut da_mock = given_da(0);
+        da_mock.expect_submit().return_once(move |frames| {
+            let num_frames = frames.len();
+            sender.send(frames).unwrap();
+            Ok((given_submission([1u8; 32]), num_frames))
+        });
+        let mut committer = StateCommitter::new(
+            da_mock,
+            db,
+            10,
+            u128::MAX,
+            given_no_batching(),
+            FeeCeiling::none(),
+        );
+
+        committer.run().await?;
+
+        Ok(receiver.recv().unwrap())
+    }
+
+    #[tokio::test]
+    async fn packs_small_fragments_into_a_single_tx() -> Result<()> {
+        // given
+        let (state, fragments) = given_state_with_fragments(1000, 10);
+
+        // when
+        let frames = submitted_frames_of(state, fragments).await?;
+
+        // then
+        let fragment_indexes: Vec<_> = frames.iter().map(|frame| frame.fragment_index).collect();
+        assert_eq!(fragment_indexes, (0..10).collect::<Vec<_>>());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn packs_no_more_than_a_submission_can_carry() -> Result<()> {
+        // given
+        let (state, fragments) =
+            given_state_with_fragments(MAX_FRAGMENT_SIZE, UNITS_PER_SUBMISSION as u32 + 1);
+
+        // when
+        let frames = submitted_frames_of(state, fragments).await?;
+
+        // then
+        assert_eq!(frames.len(), UNITS_PER_SUBMISSION);
+        assert!(payload_size(&frames) <= MAX_PAYLOAD_SIZE);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn waits_for_more_state_before_posting_a_mostly_empty_submission() -> Result<()> {
+        // given
+        let (state, fragments) = given_state_with_fragments(1000, 2);
+
+        let process = PostgresProcess::shared().await.unwrap();
+        let db = process.create_random_db().await?;
+        db.insert_state(state, fragments).await?;
+
+        let batching = BatchingPolicy::new(1, Duration::from_secs(3600));
+        let mut committer = StateCommitter::new(
+            given_da(0),
+            db.clone(),
+            10,
+            u128::MAX,
+            batching,
+            FeeCeiling::none(),
+        );
+
+        // when
+        committer.run().await.unwrap();
+
+        // then
+        assert!(!db.has_pending_txs().await?);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn posts_without_waiting_once_enough_payload_units_are_filled() -> Result<()> {
+        // given
+        let (state, fragments) = given_state_with_fragments(MAX_FRAGMENT_SIZE, 3);
+
+        let process = PostgresProcess::shared().await.unwrap();
+        let db = process.create_random_db().await?;
+        db.insert_state(state, fragments).await?;
+
+        let mut da_mock = given_da(0);
+        da_mock
+            .expect_submit()
+            .return_once(move |frames| Ok((given_submission([1u8; 32]), frames.len())));
+        let batching = BatchingPolicy::new(2, Duration::from_secs(3600));
+        let mut committer = StateCommitter::new(
+            da_mock,
+            db.clone(),
+            10,
+            u128::MAX,
+            batching,
+            FeeCeiling::none(),
+        );
+
+        // when
+        committer.run().await.unwrap();
+
+        // then
+        assert!(db.has_pending_txs().await?);
+
+        Ok(())
+    }
+
+    fn given_fee_spike() -> BaseFees {
+        BaseFees {
+            base_fee_per_gas: 10,
+            base_fee_per_blob_gas: Some(1_000),
+        }
+    }
+
+    #[tokio::test]
+    async fn defers_submission_while_the_blob_base_fee_is_above_the_ceiling() -> Result<()> {
+        // given
+        let (state, fragment) = given_state();
+
+        let process = PostgresProcess::shared().await.unwrap();
+        let db = process.create_random_db().await?;
+        db.insert_state(state, vec![fragment]).await?;
+
+        let da_mock = given_da_with_base_fees(0, given_fee_spike());
+        let fee_ceiling = FeeCeiling::new(100, 100, Duration::from_secs(3600));
+        let mut committer = StateCommitter::new(
+            da_mock,
+            db.clone(),
+            10,
+            u128::MAX,
+            given_no_batching(),
+            fee_ceiling,
+        );
+
+        // when
+        committer.run().await.unwrap();
+
+        // then
+        assert!(!db.has_pending_txs().await?);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn submits_despite_high_fees_once_deferred_for_too_long() -> Result<()> {
+        // given
+        let (state, fragment) = given_state();
+
+        let process = PostgresProcess::shared().await.unwrap();
+        let db = process.create_random_db().await?;
+        db.insert_state(state, vec![fragment]).await?;
+
+        let mut da_mock = given_da_with_base_fees(0, given_fee_spike());
+        da_mock
+            .expect_submit()
+            .return_once(move |frames| Ok((given_submission([1u8; 32]), frames.len())));
+        let fee_ceiling = FeeCeiling::new(100, 100, Duration::ZERO);
+        let mut committer = StateCommitter::new(
+            da_mock,
+            db.clone(),
+            10,
+            u128::MAX,
+            given_no_batching(),
+            fee_ceiling,
+        );
+
+        // when
+        committer.run().await.unwrap();
+
+        // then
+        assert!(db.has_pending_txs().await?);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn leaves_the_fragments_the_da_layer_did_not_take_for_later() -> Result<()> {
+        // given
+        let (state, fragments) = given_state_with_fragments(1000, 3);
+
+        let process = PostgresProcess::shared().await.unwrap();
+        let db = process.create_random_db().await?;
+        db.insert_state(state, fragments).await?;
+
+        let mut da_mock = given_da(0);
+        da_mock
+            .expect_submit()
+            .return_once(move |_| Ok((given_submission([1u8; 32]), 2)));
+        let mut committer = StateCommitter::new(
+            da_mock,
+            db.clone(),
+            10,
+            u128::MAX,
+            given_no_batching(),
+            FeeCeiling::none(),
+        );
+
+        // when
+        committer.run().await?;
+
+        // then
+        let submitted: Vec<_> = db
+            .get_tx_fragments([1u8; 32])
+            .await?
+            .iter()
+            .map(|fragment| fragment.fragment_index)
+            .collect();
+        assert_eq!(submitted, vec![0, 1]);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn will_replace_tx_pending_for_too_long() -> Result<()> {
+        // given
+        let process = PostgresProcess::shared().await.unwrap();
+        let db = given_db_with_pending_tx(&process).await?;
+
+        let (state, fragment) = given_state();
+        let mut da_mock = given_da(10);
+        da_mock.expect_replacement_fee().returning(|_| Ok(100));
+        da_mock
+            .expect_replace()
+            .withf(move |frames, submission| {
+                *frames == [StateFrame::new(&state, fragment.clone())]
+                    && *submission == given_submission([1u8; 32])
+            })
+            .return_once(move |_, _| Ok(given_submission([2u8; 32])));
+
+        let mut committer = StateCommitter::new(
+            da_mock,
+            db.clone(),
+            10,
+            u128::MAX,
+            given_no_batching(),
+            FeeCeiling::none(),
+        );
+
+        // when
+        committer.run().await.unwrap();
+
+        // then
+        let pending_txs = db.get_pending_txs().await?;
+        assert_eq!(pending_txs.len(), 2);
+        assert_eq!(pending_txs[1].hash, [2u8; 32]);
+        assert_eq!(pending_txs[1].submitted_at, 10u32.into());
+
+        let fragments = db.get_tx_fragments([2u8; 32]).await?;
+        assert_eq!(fragments.len(), 1);
+        assert_eq!(fragments[0].transaction_hash, Some([2u8; 32]));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn will_not_replace_tx_if_fee_limit_exceeded() -> Result<()> {
+        // given
+        let process = PostgresProcess::shared().await.unwrap();
+        let db = given_db_with_pending_tx(&process).await?;
+
+        let max_tx_fee = 100;
+        let mut da_mock = given_da(10);
+        da_mock
+            .expect_replacement_fee()
+            .returning(move |_| Ok(max_tx_fee + 1));
+
+        let mut committer = StateCommitter::new(
+            da_mock,
+            db.clone(),
+            10,
+            max_tx_fee,
+            given_no_batching(),
+            FeeCeiling::none(),
+        );
+
+        // when
+        committer.run().await.unwrap();
+
+        // then
+        assert_eq!(db.get_pending_txs().await?.len(), 1);
+
+        Ok(())
+    }
 }
@@ -1,25 +1,55 @@
+use std::ops::RangeInclusive;
+
 use async_trait::async_trait;
+use metrics::{
+    prometheus::{core::Collector, IntCounter, Opts},
+    RegistersMetrics,
+};
 use ports::{
-    fuel::FuelBlock,
+    fuel::{FuelBlock, FullFuelBlock},
     storage::Storage,
-    types::{StateFragment, StateSubmission},
+    types::{encode_block_state, Compression, StateFragment, StateSubmission},
 };
 use validator::Validator;
 
-use crate::{Result, Runner};
+use crate::{block_feed::BlockFeed, compression::Compressor, Error, Result, Runner};
 
 pub struct StateImporter<Db, A, BlockValidator> {
     storage: Db,
     fuel_adapter: A,
     block_validator: BlockValidator,
+    compressor: Compressor,
+    max_fragment_size: usize,
+    start_height: Option<u32>,
+    max_blocks_per_run: usize,
+    blocks: BlockFeed,
+    metrics: Metrics,
 }
 
 impl<Db, A, BlockValidator> StateImporter<Db, A, BlockValidator> {
-    pub fn new(storage: Db, fuel_adapter: A, block_validator: BlockValidator) -> Self {
+    /// At most `max_blocks_per_run` blocks are imported in one run, so that catching up after a
+    /// long downtime doesn't hold up shutdown. The state of a block is split into fragments of at
+    /// most `max_fragment_size` bytes, as dictated by the data availability layer. Without a
+    /// `start_height` the import starts at the tip of the chain.
+    pub fn new(
+        storage: Db,
+        fuel_adapter: A,
+        block_validator: BlockValidator,
+        compressor: Compressor,
+        max_fragment_size: usize,
+        start_height: Option<u32>,
+        max_blocks_per_run: usize,
+    ) -> Self {
         Self {
             storage,
             fuel_adapter,
             block_validator,
+            compressor,
+            max_fragment_size,
+            start_height,
+            max_blocks_per_run,
+            blocks: BlockFeed::default(),
+            metrics: Metrics::default(),
         }
     }
 }
@@ -30,73 +60,158 @@ where
     A: ports::fuel::Api,
     BlockValidator: Validator,
 {
-    async fn fetch_latest_block(&self) -> Result<FuelBlock> {
-        let latest_block = self.fuel_adapter.latest_block().await?;
+    /// Heights from the start height up to the tip of the fuel chain whose state was not imported
+    /// yet, e.g. because the committer was down while they were produced. Returned along with the
+    /// start height and the height of the tip.
+    async fn missing_heights(&self) -> Result<(u32, u32, Vec<u32>)> {
+        let latest_height = self.fuel_adapter.latest_block().await?.header.height;
+        let start_height = self.start_height(latest_height).await?;
+
+        let missing_heights = self
+            .storage
+            .missing_state_heights(start_height, latest_height, self.max_blocks_per_run)
+            .await?;
+
+        Ok((start_height, latest_height, missing_heights))
+    }
+
+    /// The configured start height, otherwise the latest block whose state was imported or, before
+    /// anything was, the tip.
+    async fn start_height(&self, latest_height: u32) -> Result<u32> {
+        if let Some(start_height) = self.start_height {
+            return Ok(start_height);
+        }
 
-        // validate block but don't return the validated block
-        // so we can use the original block for state submission
-        self.block_validator.validate(&latest_block)?;
+        let latest_imported = self.storage.state_submission_w_latest_block().await?;
 
-        Ok(latest_block)
+        Ok(latest_imported.map_or(latest_height, |submission| submission.block_height))
     }
 
-    async fn check_if_stale(&self, block_height: u32) -> Result<bool> {
-        let Some(submitted_height) = self.last_submitted_block_height().await? else {
-            return Ok(false);
-        };
+    // validates the block and its transactions but returns the original ones so they can be used
+    // for state submission
+    fn validated(&self, full_block: FullFuelBlock) -> Result<FullFuelBlock> {
+        self.block_validator.validate(&full_block.block)?;
+        self.block_validator
+            .validate_transactions(&full_block.block, &full_block.raw_transactions)?;
 
-        Ok(submitted_height >= block_height)
+        Ok(full_block)
     }
 
-    async fn last_submitted_block_height(&self) -> Result<Option<u32>> {
-        Ok(self
-            .storage
-            .state_submission_w_latest_block()
-            .await?
-            .map(|submission| submission.block_height))
+    /// Polls the node for the blocks that weren't imported yet. Once it caught up with the tip
+    /// the chain is followed instead.
+    async fn catch_up(&mut self) -> Result<()> {
+        let (start_height, latest_height, missing_heights) = self.missing_heights().await?;
+
+        for range in consecutive_ranges(&missing_heights) {
+            self.import_range(range).await?;
+        }
+
+        if missing_heights.len() < self.max_blocks_per_run {
+            let next_height = latest_height.saturating_add(1).max(start_height);
+            self.blocks.follow(&self.fuel_adapter, next_height);
+        }
+
+        Ok(())
+    }
+
+    async fn import_range(&self, range: RangeInclusive<u32>) -> Result<()> {
+        let expected_blocks = range.clone().count();
+        let blocks = self.fuel_adapter.full_blocks_in_height_range(range).await?;
+        if blocks.len() != expected_blocks {
+            return Err(Error::Other(format!(
+                "fuel node returned {} of the {expected_blocks} requested blocks",
+                blocks.len()
+            )));
+        }
+
+        for full_block in blocks {
+            let full_block = self.validated(full_block)?;
+            self.import_state(full_block).await?;
+        }
+
+        Ok(())
+    }
+
+    /// The followed chain carries no transaction payloads, so the followed heights are fetched
+    /// again along with their transactions.
+    async fn import_streamed(&self, blocks: Vec<FuelBlock>) -> Result<()> {
+        let (Some(first), Some(last)) = (blocks.first(), blocks.last()) else {
+            return Ok(());
+        };
+
+        self.import_range(first.header.height..=last.header.height)
+            .await
     }
 
     fn block_to_state_submission(
         &self,
-        block: FuelBlock,
+        full_block: FullFuelBlock,
     ) -> Result<(StateSubmission, Vec<StateFragment>)> {
-        use itertools::Itertools;
-
-        // Serialize the block into bytes
-        let fragments = block
-            .transactions
-            .iter()
-            .flat_map(|tx| tx.iter())
-            .chunks(StateFragment::MAX_FRAGMENT_SIZE)
-            .into_iter()
+        let (compression, data) = self.compress(encode_block_state(&full_block))?;
+        let block = full_block.block;
+
+        let fragments = data
+            .chunks(self.max_fragment_size)
             .enumerate()
             .map(|(index, chunk)| StateFragment {
                 block_hash: *block.id,
                 transaction_hash: None,
                 fragment_index: index as u32,
-                raw_data: chunk.copied().collect(),
+                raw_data: chunk.to_vec(),
                 created_at: ports::types::Utc::now(),
                 completed: false,
             })
-            .collect();
+            .collect::<Vec<_>>();
 
         let submission = StateSubmission {
             block_hash: *block.id,
             block_height: block.header.height,
             completed: false,
+            compression,
+            num_fragments: fragments.len() as u32,
         };
 
         Ok((submission, fragments))
     }
 
-    async fn import_state(&self, block: FuelBlock) -> Result<()> {
-        let (submission, fragments) = self.block_to_state_submission(block)?;
+    /// Falls back to the uncompressed state if the codec doesn't make it any smaller.
+    fn compress(&self, state: Vec<u8>) -> Result<(Compression, Vec<u8>)> {
+        let compressed = self.compressor.compress(&state)?;
+        if compressed.len() >= state.len() {
+            return Ok((Compression::None, state));
+        }
+
+        let saved = state.len() - compressed.len();
+        self.metrics
+            .state_bytes_saved_by_compression
+            .inc_by(saved as u64);
+
+        Ok((self.compressor.compression(), compressed))
+    }
+
+    async fn import_state(&self, full_block: FullFuelBlock) -> Result<()> {
+        let (submission, fragments) = self.block_to_state_submission(full_block)?;
         self.storage.insert_state(submission, fragments).await?;
 
         Ok(())
     }
 }
 
+/// Groups ascending heights into ranges of consecutive heights.
+fn consecutive_ranges(heights: &[u32]) -> Vec<RangeInclusive<u32>> {
+    let mut ranges: Vec<RangeInclusive<u32>> = vec![];
+    for &height in heights {
+        match ranges.last_mut() {
+            Some(range) if range.end().checked_add(1) == Some(height) => {
+                *range = *range.start()..=height;
+            }
+            _ => ranges.push(height..=height),
+        }
+    }
+
+    ranges
+}
+
 #[async_trait]
 impl<Db, Fuel, BlockValidator> Runner for StateImporter<Db, Fuel, BlockValidator>
 where
@@ -105,25 +220,49 @@ where
     BlockValidator: Validator,
 {
     async fn run(&mut self) -> Result<()> {
-        let block = self.fetch_latest_block().await?;
+        let Some(blocks) = self.blocks.ready_blocks() else {
+            return self.catch_up().await;
+        };
 
-        if self.check_if_stale(block.header.height).await? {
-            return Ok(());
-        }
+        // whatever couldn't be imported is picked up by polling
+        self.import_streamed(blocks)
+            .await
+            .inspect_err(|_| self.blocks.stop())
+    }
+}
 
-        if block.transactions.is_empty() {
-            return Ok(());
-        }
+#[derive(Clone)]
+struct Metrics {
+    state_bytes_saved_by_compression: IntCounter,
+}
 
-        self.import_state(block).await?;
+impl<Db, A, BlockValidator> RegistersMetrics for StateImporter<Db, A, BlockValidator> {
+    fn metrics(&self) -> Vec<Box<dyn Collector>> {
+        vec![Box::new(
+            self.metrics.state_bytes_saved_by_compression.clone(),
+        )]
+    }
+}
 
-        Ok(())
+impl Default for Metrics {
+    fn default() -> Self {
+        let state_bytes_saved_by_compression = IntCounter::with_opts(Opts::new(
+            "state_bytes_saved_by_compression",
+            "Bytes of state saved by compressing it before posting it.",
+        ))
+        .expect("state_bytes_saved_by_compression metric to be correctly configured");
+
+        Self {
+            state_bytes_saved_by_compression,
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
     use fuel_crypto::{Message, SecretKey, Signature};
+    use futures::StreamExt;
+    use mockall::predicate::eq;
     use ports::fuel::{FuelConsensus, FuelPoAConsensus};
     use rand::{rngs::StdRng, SeedableRng};
     use storage::PostgresProcess;
@@ -133,14 +272,25 @@ mod tests {
 
     use super::*;
 
+    const MAX_FRAGMENT_SIZE: usize = 128 * 1024;
+
     fn given_secret_key() -> SecretKey {
         let mut rng = StdRng::seed_from_u64(42);
 
         SecretKey::random(&mut rng)
     }
 
-    fn given_a_block(height: u32, secret_key: &SecretKey) -> FuelBlock {
-        let header = given_header(height);
+    fn given_a_block(height: u32, secret_key: &SecretKey) -> FullFuelBlock {
+        given_a_block_with_transactions(height, secret_key, vec![given_raw_transaction()])
+    }
+
+    fn given_a_block_with_transactions(
+        height: u32,
+        secret_key: &SecretKey,
+        raw_transactions: Vec<Vec<u8>>,
+    ) -> FullFuelBlock {
+        let mut header = given_header(height);
+        BlockValidator::commit_to_transactions(&mut header, &raw_transactions);
 
         let mut hasher = fuel_crypto::Hasher::default();
         hasher.input(header.prev_root.as_ref());
@@ -152,26 +302,31 @@ mod tests {
         let id_message = Message::from_bytes(*id);
         let signature = Signature::sign(secret_key, &id_message);
 
-        FuelBlock {
+        let block = FuelBlock {
             id,
             header,
             consensus: FuelConsensus::PoAConsensus(FuelPoAConsensus { signature }),
-            transactions: vec![[2u8; 32].into()],
+            transactions: vec![[2u8; 32].into(); raw_transactions.len()],
             block_producer: Some(secret_key.public_key()),
+        };
+
+        FullFuelBlock {
+            block,
+            raw_transactions,
         }
     }
 
-    fn given_header(height: u32) -> FuelHeader {
-        let application_hash = "0x8b96f712e293e801d53da77113fec3676c01669c6ea05c6c92a5889fce5f649d"
-            .parse()
-            .unwrap();
+    fn given_raw_transaction() -> Vec<u8> {
+        vec![2u8; 64]
+    }
 
+    fn given_header(height: u32) -> FuelHeader {
         ports::fuel::FuelHeader {
             id: Default::default(),
             da_height: Default::default(),
             consensus_parameters_version: Default::default(),
             state_transition_bytecode_version: Default::default(),
-            transactions_count: 1,
+            transactions_count: Default::default(),
             message_receipt_count: Default::default(),
             transactions_root: Default::default(),
             message_outbox_root: Default::default(),
@@ -179,16 +334,29 @@ mod tests {
             height,
             prev_root: Default::default(),
             time: tai64::Tai64(0),
-            application_hash,
+            application_hash: Default::default(),
         }
     }
 
-    fn given_fetcher(block: FuelBlock) -> ports::fuel::MockApi {
+    fn given_fetcher(blocks: Vec<FullFuelBlock>) -> ports::fuel::MockApi {
         let mut fetcher = ports::fuel::MockApi::new();
 
+        let latest_block = blocks.last().expect("at least one block").block.clone();
         fetcher
             .expect_latest_block()
-            .returning(move || Ok(block.clone()));
+            .returning(move || Ok(latest_block.clone()));
+        fetcher
+            .expect_full_blocks_in_height_range()
+            .returning(move |range| {
+                Ok(blocks
+                    .iter()
+                    .filter(|full_block| range.contains(&full_block.block.header.height))
+                    .cloned()
+                    .collect())
+            });
+        fetcher
+            .expect_poll_blocks_from()
+            .returning(|_| Box::pin(futures::stream::pending()));
 
         fetcher
     }
@@ -198,22 +366,277 @@ mod tests {
         // given
         let secret_key = given_secret_key();
         let block = given_a_block(1, &secret_key);
-        let block_id = *block.id;
-        let fuel_mock = given_fetcher(block);
+        let block_id = *block.block.id;
+        let fuel_mock = given_fetcher(vec![block]);
         let block_validator = BlockValidator::new(secret_key.public_key());
 
         let process = PostgresProcess::shared().await.unwrap();
         let db = process.create_random_db().await?;
-        let mut importer = StateImporter::new(db.clone(), fuel_mock, block_validator);
+        let mut importer = StateImporter::new(
+            db.clone(),
+            fuel_mock,
+            block_validator,
+            Compressor::no_compression(),
+            MAX_FRAGMENT_SIZE,
+            Some(1),
+            100,
+        );
 
         // when
         importer.run().await.unwrap();
 
         // then
-        let fragments = db.get_unsubmitted_fragments().await?;
+        let fragments = db.get_unsubmitted_fragments(usize::MAX).await?;
         assert_eq!(fragments.len(), 1);
         assert_eq!(fragments[0].block_hash, block_id);
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn state_is_compressed_with_the_configured_codec() -> Result<()> {
+        // given
+        let secret_key = given_secret_key();
+        let block =
+            given_a_block_with_transactions(1, &secret_key, vec![given_raw_transaction(); 100]);
+        let fuel_mock = given_fetcher(vec![block.clone()]);
+        let block_validator = BlockValidator::new(secret_key.public_key());
+
+        let process = PostgresProcess::shared().await.unwrap();
+        let db = process.create_random_db().await?;
+        let mut importer = StateImporter::new(
+            db.clone(),
+            fuel_mock,
+            block_validator,
+            Compressor::new(Compression::Zstd, 3),
+            MAX_FRAGMENT_SIZE,
+            Some(1),
+            100,
+        );
+
+        // when
+        importer.run().await.unwrap();
+
+        // then
+        let submission = db.state_submission_w_latest_block().await?.unwrap();
+        assert_eq!(submission.compression, Compression::Zstd);
+
+        let fragments = db.get_unsubmitted_fragments(usize::MAX).await?;
+        let state = Compressor::decompress(Compression::Zstd, &fragments[0].raw_data)?;
+        assert_eq!(state, encode_block_state(&block));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn imports_every_block_from_the_start_height_to_the_tip() -> Result<()> {
+        // given
+        let secret_key = given_secret_key();
+        let blocks: Vec<_> = (1..=5).map(|h| given_a_block(h, &secret_key)).collect();
+        let fuel_mock = given_fetcher(blocks);
+        let block_validator = BlockValidator::new(secret_key.public_key());
+
+        let process = PostgresProcess::shared().await.unwrap();
+        let db = process.create_random_db().await?;
+        let mut importer = StateImporter::new(
+            db.clone(),
+            fuel_mock,
+            block_validator,
+            Compressor::no_compression(),
+            MAX_FRAGMENT_SIZE,
+            Some(2),
+            100,
+        );
+
+        // when
+        importer.run().await.unwrap();
+
+        // then
+        assert_eq!(db.missing_state_heights(1, 5, 100).await?, vec![1]);
+
+        let fragments = db.get_unsubmitted_fragments(usize::MAX).await?;
+        assert_eq!(fragments.len(), 4);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn starts_at_the_tip_without_a_start_height() -> Result<()> {
+        // given
+        let secret_key = given_secret_key();
+        let blocks: Vec<_> = (1..=5).map(|h| given_a_block(h, &secret_key)).collect();
+        let fuel_mock = given_fetcher(blocks);
+        let block_validator = BlockValidator::new(secret_key.public_key());
+
+        let process = PostgresProcess::shared().await.unwrap();
+        let db = process.create_random_db().await?;
+        let mut importer = StateImporter::new(
+            db.clone(),
+            fuel_mock,
+            block_validator,
+            Compressor::no_compression(),
+            MAX_FRAGMENT_SIZE,
+            None,
+            100,
+        );
+
+        // when
+        importer.run().await.unwrap();
+
+        // then
+        assert_eq!(db.missing_state_heights(1, 5, 100).await?, vec![1, 2, 3, 4]);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn fills_gaps_left_by_downtime() -> Result<()> {
+        // given
+        let secret_key = given_secret_key();
+        let blocks: Vec<_> = (1..=4).map(|h| given_a_block(h, &secret_key)).collect();
+        let block_validator = BlockValidator::new(secret_key.public_key());
+
+        let process = PostgresProcess::shared().await.unwrap();
+        let db = process.create_random_db().await?;
+        let mut importer = StateImporter::new(
+            db.clone(),
+            given_fetcher(blocks.clone()),
+            block_validator,
+            Compressor::no_compression(),
+            MAX_FRAGMENT_SIZE,
+            Some(1),
+            100,
+        );
+        for block in [&blocks[0], &blocks[3]] {
+            let (submission, fragments) = importer.block_to_state_submission(block.clone())?;
+            db.insert_state(submission, fragments).await?;
+        }
+
+        // when
+        importer.run().await.unwrap();
+
+        // then
+        assert!(db.missing_state_heights(1, 4, 100).await?.is_empty());
+
+        let fragments = db.get_unsubmitted_fragments(usize::MAX).await?;
+        assert_eq!(fragments.len(), 4);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn imports_followed_blocks_once_caught_up() -> Result<()> {
+        // given
+        let secret_key = given_secret_key();
+        let blocks: Vec<_> = (1..=4).map(|h| given_a_block(h, &secret_key)).collect();
+        let block_validator = BlockValidator::new(secret_key.public_key());
+
+        let mut fuel_mock = ports::fuel::MockApi::new();
+        let tip = blocks[1].block.clone();
+        fuel_mock
+            .expect_latest_block()
+            .once()
+            .return_once(move || Ok(tip));
+        let polled_blocks = blocks[..2].to_vec();
+        fuel_mock
+            .expect_full_blocks_in_height_range()
+            .with(eq(1..=2))
+            .once()
+            .return_once(move |_| Ok(polled_blocks));
+        let followed_blocks = blocks[2..].to_vec();
+        fuel_mock
+            .expect_full_blocks_in_height_range()
+            .with(eq(3..=4))
+            .once()
+            .return_once(move |_| Ok(followed_blocks));
+        let streamed_blocks = blocks
+            .into_iter()
+            .skip(2)
+            .map(|full_block| Ok(full_block.block));
+        fuel_mock
+            .expect_poll_blocks_from()
+            .with(eq(3))
+            .once()
+            .return_once(move |_| {
+                Box::pin(futures::stream::iter(streamed_blocks).chain(futures::stream::pending()))
+            });
+
+        let process = PostgresProcess::shared().await.unwrap();
+        let db = process.create_random_db().await?;
+        let mut importer = StateImporter::new(
+            db.clone(),
+            fuel_mock,
+            block_validator,
+            Compressor::no_compression(),
+            MAX_FRAGMENT_SIZE,
+            Some(1),
+            100,
+        );
+        importer.run().await.unwrap();
+
+        // when
+        importer.run().await.unwrap();
+
+        // then
+        assert!(db.missing_state_heights(1, 4, 100).await?.is_empty());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn rejects_blocks_whose_transactions_dont_match_the_header() -> Result<()> {
+        // given
+        let secret_key = given_secret_key();
+        let block = given_a_block(1, &secret_key);
+        let block_validator = BlockValidator::new(secret_key.public_key());
+
+        let mut fuel_mock = ports::fuel::MockApi::new();
+        let tip = block.block.clone();
+        fuel_mock
+            .expect_latest_block()
+            .returning(move || Ok(tip.clone()));
+        let tampered = FullFuelBlock {
+            raw_transactions: vec![vec![3u8; 64]],
+            ..block
+        };
+        fuel_mock
+            .expect_full_blocks_in_height_range()
+            .returning(move |_| Ok(vec![tampered.clone()]));
+
+        let process = PostgresProcess::shared().await.unwrap();
+        let db = process.create_random_db().await?;
+        let mut importer = StateImporter::new(
+            db.clone(),
+            fuel_mock,
+            block_validator,
+            Compressor::no_compression(),
+            MAX_FRAGMENT_SIZE,
+            Some(1),
+            100,
+        );
+
+        // when
+        let result = importer.run().await;
+
+        // then
+        let Err(Error::BlockValidation(msg)) = result else {
+            panic!("expected the block to be rejected");
+        };
+        assert!(msg.starts_with("transactions mismatch"));
+        assert!(db.get_unsubmitted_fragments(usize::MAX).await?.is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn groups_heights_into_consecutive_ranges() {
+        // given
+        let heights = [1, 2, 3, 5, 8, 9];
+
+        // when
+        let ranges = consecutive_ranges(&heights);
+
+        // then
+        assert_eq!(ranges, vec![1..=3, 5..=5, 8..=9]);
+    }
 }
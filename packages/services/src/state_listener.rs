@@ -0,0 +1,301 @@
+use async_trait::async_trait;
+use metrics::{
+    prometheus::{core::Collector, IntGauge, Opts},
+    RegistersMetrics,
+};
+use ports::{
+    storage::Storage,
+    types::{SubmissionStatus, SubmissionTx, TransactionState},
+};
+use tracing::info;
+
+use super::Runner;
+
+pub struct StateListener<Da, Db> {
+    da: Da,
+    storage: Db,
+    num_blocks_to_finalize: u64,
+    metrics: Metrics,
+}
+
+impl<Da, Db> StateListener<Da, Db> {
+    pub fn new(da: Da, storage: Db, num_blocks_to_finalize: u64) -> Self {
+        Self {
+            da,
+            storage,
+            num_blocks_to_finalize,
+            metrics: Metrics::default(),
+        }
+    }
+}
+
+impl<Da, Db> StateListener<Da, Db>
+where
+    Da: ports::da::Api,
+    Db: Storage,
+{
+    async fn check_pending_txs(&mut self, pending_txs: Vec<SubmissionTx>) -> crate::Result<()> {
+        let current_block_number: u64 = self.da.height().await?.into();
+
+        for tx in pending_txs {
+            let tx_hash = tx.hash;
+            let tx_response = match self.da.confirm(tx_hash).await? {
+                SubmissionStatus::Included(tx_response) => tx_response,
+                SubmissionStatus::Pending => continue, // not committed
+                SubmissionStatus::Dropped => {
+                    self.storage
+                        .update_submission_tx_state(tx_hash, TransactionState::Failed)
+                        .await?;
+
+                    info!("blob tx {} dropped from the mempool", hex::encode(tx_hash));
+                    continue;
+                }
+            };
+
+            if !tx_response.succeeded() {
+                self.storage
+                    .update_submission_tx_state(tx_hash, TransactionState::Failed)
+                    .await?;
+
+                info!("failed blob tx {}", hex::encode(tx_hash));
+                continue;
+            }
+
+            if tx_response.confirmations(current_block_number) < self.num_blocks_to_finalize {
+                continue; // not finalized
+            }
+
+            self.storage
+                .update_submission_tx_state(tx_hash, TransactionState::Finalized)
+                .await?;
+
+            info!("finalized blob tx {}", hex::encode(tx_hash));
+
+            self.metrics
+                .last_eth_block_w_blob
+                .set(tx_response.block_number() as i64);
+        }
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl<Da, Db> Runner for StateListener<Da, Db>
+where
+    Da: ports::da::Api,
+    Db: Storage,
+{
+    async fn run(&mut self) -> crate::Result<()> {
+        let pending_txs = self.storage.get_pending_txs().await?;
+
+        if pending_txs.is_empty() {
+            return Ok(());
+        }
+
+        self.check_pending_txs(pending_txs).await?;
+
+        Ok(())
+    }
+}
+
+#[derive(Clone)]
+struct Metrics {
+    last_eth_block_w_blob: IntGauge,
+}
+
+impl<Da, Db> RegistersMetrics for StateListener<Da, Db> {
+    fn metrics(&self) -> Vec<Box<dyn Collector>> {
+        vec![Box::new(self.metrics.last_eth_block_w_blob.clone())]
+    }
+}
+
+impl Default for Metrics {
+    fn default() -> Self {
+        let last_eth_block_w_blob = IntGauge::with_opts(Opts::new(
+            "last_eth_block_w_blob",
+            "The height of the latest Ethereum block used for state submission.",
+        ))
+        .expect("last_eth_block_w_blob metric to be correctly configured");
+
+        Self {
+            last_eth_block_w_blob,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mockall::predicate;
+    use ports::types::{
+        Compression, DaSubmission, L1Height, StateFragment, StateSubmission, TransactionResponse,
+    };
+    use storage::{Postgres, PostgresProcess};
+
+    use super::*;
+
+    const TX_HASH: [u8; 32] = [1; 32];
+
+    fn given_da_with_status(current_height: u32, status: SubmissionStatus) -> ports::da::MockApi {
+        let mut da = ports::da::MockApi::new();
+
+        da.expect_height()
+            .returning(move || Ok(L1Height::from(current_height)));
+
+        da.expect_confirm()
+            .with(predicate::eq(TX_HASH))
+            .return_once(move |_| Ok(status));
+
+        da
+    }
+
+    async fn given_db_with_pending_tx(process: &PostgresProcess) -> Postgres {
+        let db = process.create_random_db().await.unwrap();
+
+        let state = StateSubmission {
+            block_hash: [0u8; 32],
+            block_height: 1,
+            completed: false,
+            compression: Compression::None,
+            num_fragments: 1,
+        };
+        let fragment = StateFragment {
+            block_hash: [0u8; 32],
+            transaction_hash: None,
+            fragment_index: 0,
+            raw_data: vec![1, 2, 3],
+            created_at: ports::types::Utc::now(),
+            completed: false,
+        };
+        let fragment_id = fragment.id();
+
+        db.insert_state(state, vec![fragment]).await.unwrap();
+        let submission = DaSubmission {
+            hash: TX_HASH,
+            details: vec![],
+        };
+        db.record_pending_tx(
+            SubmissionTx::pending(submission, 0u32.into()),
+            vec![fragment_id],
+        )
+        .await
+        .unwrap();
+
+        db
+    }
+
+    #[tokio::test]
+    async fn state_listener_will_update_tx_state_if_finalized() -> crate::Result<()> {
+        // given
+        let process = PostgresProcess::shared().await.unwrap();
+        let db = given_db_with_pending_tx(&process).await;
+
+        let tx_response = TransactionResponse::new(10, [10; 32], true, 21_000, 1);
+        let da_mock = given_da_with_status(12, SubmissionStatus::Included(tx_response));
+
+        let mut listener = StateListener::new(da_mock, db.clone(), 3);
+
+        // when
+        listener.run().await.unwrap();
+
+        // then
+        assert!(!db.has_pending_txs().await?);
+        assert!(db.get_unsubmitted_fragments(usize::MAX).await?.is_empty());
+        assert!(
+            db.state_submission_w_latest_block()
+                .await?
+                .unwrap()
+                .completed
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn state_listener_will_not_update_tx_state_if_not_finalized() -> crate::Result<()> {
+        // given
+        let process = PostgresProcess::shared().await.unwrap();
+        let db = given_db_with_pending_tx(&process).await;
+
+        let tx_response = TransactionResponse::new(10, [10; 32], true, 21_000, 1);
+        let da_mock = given_da_with_status(11, SubmissionStatus::Included(tx_response));
+
+        let mut listener = StateListener::new(da_mock, db.clone(), 3);
+
+        // when
+        listener.run().await.unwrap();
+
+        // then
+        assert!(db.has_pending_txs().await?);
+        assert!(
+            !db.state_submission_w_latest_block()
+                .await?
+                .unwrap()
+                .completed
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn state_listener_will_release_fragments_if_tx_failed() -> crate::Result<()> {
+        // given
+        let process = PostgresProcess::shared().await.unwrap();
+        let db = given_db_with_pending_tx(&process).await;
+
+        let tx_response = TransactionResponse::new(10, [10; 32], false, 21_000, 1);
+        let da_mock = given_da_with_status(12, SubmissionStatus::Included(tx_response));
+
+        let mut listener = StateListener::new(da_mock, db.clone(), 3);
+
+        // when
+        listener.run().await.unwrap();
+
+        // then
+        assert!(!db.has_pending_txs().await?);
+        let fragments = db.get_unsubmitted_fragments(usize::MAX).await?;
+        assert_eq!(fragments.len(), 1);
+        assert_eq!(fragments[0].transaction_hash, None);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn state_listener_waits_for_pending_txs() -> crate::Result<()> {
+        // given
+        let process = PostgresProcess::shared().await.unwrap();
+        let db = given_db_with_pending_tx(&process).await;
+
+        let da_mock = given_da_with_status(12, SubmissionStatus::Pending);
+
+        let mut listener = StateListener::new(da_mock, db.clone(), 3);
+
+        // when
+        listener.run().await.unwrap();
+
+        // then
+        assert!(db.has_pending_txs().await?);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn state_listener_will_release_fragments_if_tx_dropped() -> crate::Result<()> {
+        // given
+        let process = PostgresProcess::shared().await.unwrap();
+        let db = given_db_with_pending_tx(&process).await;
+
+        let da_mock = given_da_with_status(12, SubmissionStatus::Dropped);
+
+        let mut listener = StateListener::new(da_mock, db.clone(), 3);
+
+        // when
+        listener.run().await.unwrap();
+
+        // then
+        assert!(!db.has_pending_txs().await?);
+        assert_eq!(db.get_unsubmitted_fragments(usize::MAX).await?.len(), 1);
+
+        Ok(())
+    }
+}
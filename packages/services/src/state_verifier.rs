@@ -0,0 +1,270 @@
+use std::collections::{hash_map::Entry, BTreeMap, HashMap};
+
+use ports::types::{encode_block_state, StateFrame};
+
+use crate::{compression::Compressor, Result};
+
+/// Outcome of checking the state of a fuel block recovered from L1 against the fuel node.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StateVerification {
+    Verified,
+    Incomplete { missing_fragments: Vec<u32> },
+    Mismatch(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StateReport {
+    pub block_hash: [u8; 32],
+    pub block_height: u32,
+    pub verification: StateVerification,
+}
+
+/// Reassembles the state of fuel blocks from frames posted to L1 and checks it against what the
+/// fuel node has for the same height.
+pub struct StateVerifier<Fuel> {
+    fuel_adapter: Fuel,
+}
+
+impl<Fuel> StateVerifier<Fuel> {
+    pub fn new(fuel_adapter: Fuel) -> Self {
+        Self { fuel_adapter }
+    }
+}
+
+impl<Fuel> StateVerifier<Fuel>
+where
+    Fuel: ports::fuel::Api,
+{
+    /// One report per block found in `frames`, ordered by block height. Frames may come from any
+    /// number of txs and appear more than once.
+    pub async fn verify(&self, frames: Vec<StateFrame>) -> Result<Vec<StateReport>> {
+        let mut blocks: BTreeMap<(u32, [u8; 32]), Vec<StateFrame>> = BTreeMap::new();
+        for frame in frames {
+            blocks
+                .entry((frame.block_height, frame.block_hash))
+                .or_default()
+                .push(frame);
+        }
+
+        let mut reports = Vec::with_capacity(blocks.len());
+        for ((block_height, block_hash), frames) in blocks {
+            let verification = match reassemble(frames) {
+                Ok(state) => {
+                    self.compare_with_fuel(block_height, block_hash, state)
+                        .await?
+                }
+                Err(verification) => verification,
+            };
+
+            reports.push(StateReport {
+                block_hash,
+                block_height,
+                verification,
+            });
+        }
+
+        Ok(reports)
+    }
+
+    async fn compare_with_fuel(
+        &self,
+        block_height: u32,
+        block_hash: [u8; 32],
+        state: Vec<u8>,
+    ) -> Result<StateVerification> {
+        let Some(full_block) = self
+            .fuel_adapter
+            .full_blocks_in_height_range(block_height..=block_height)
+            .await?
+            .pop()
+        else {
+            return Ok(StateVerification::Mismatch(
+                "block not known to the fuel node".to_string(),
+            ));
+        };
+
+        let verification = if *full_block.block.id != block_hash {
+            StateVerification::Mismatch(format!(
+                "fuel node has block {} at this height",
+                hex::encode(*full_block.block.id)
+            ))
+        } else if encode_block_state(&full_block) != state {
+            StateVerification::Mismatch("posted state differs from the fuel block".to_string())
+        } else {
+            StateVerification::Verified
+        };
+
+        Ok(verification)
+    }
+}
+
+/// Puts the fragments of a single block back together and decompresses them.
+fn reassemble(frames: Vec<StateFrame>) -> std::result::Result<Vec<u8>, StateVerification> {
+    let first = &frames[0];
+    let (total_fragments, compression) = (first.total_fragments, first.compression);
+    if frames
+        .iter()
+        .any(|frame| frame.total_fragments != total_fragments || frame.compression != compression)
+    {
+        return Err(StateVerification::Mismatch(
+            "frames disagree on the fragment count or compression".to_string(),
+        ));
+    }
+
+    let mut fragments = HashMap::new();
+    for frame in frames {
+        match fragments.entry(frame.fragment_index) {
+            Entry::Vacant(entry) => {
+                entry.insert(frame.data);
+            }
+            Entry::Occupied(entry) if *entry.get() != frame.data => {
+                return Err(StateVerification::Mismatch(format!(
+                    "fragment {} was posted with differing data",
+                    frame.fragment_index
+                )));
+            }
+            Entry::Occupied(_) => {}
+        }
+    }
+
+    let missing_fragments: Vec<u32> = (0..total_fragments)
+        .filter(|index| !fragments.contains_key(index))
+        .collect();
+    if !missing_fragments.is_empty() {
+        return Err(StateVerification::Incomplete { missing_fragments });
+    }
+
+    let compressed: Vec<u8> = (0..total_fragments)
+        .flat_map(|index| fragments.remove(&index).expect("no fragment is missing"))
+        .collect();
+
+    Compressor::decompress(compression, &compressed)
+        .map_err(|e| StateVerification::Mismatch(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use ports::{
+        fuel::{FuelBlock, FuelBlockId, FuelConsensus, FuelHeader, FullFuelBlock},
+        types::Compression,
+    };
+
+    use super::*;
+
+    fn given_block() -> FullFuelBlock {
+        let header = FuelHeader {
+            id: Default::default(),
+            da_height: Default::default(),
+            consensus_parameters_version: Default::default(),
+            state_transition_bytecode_version: Default::default(),
+            transactions_count: 2,
+            message_receipt_count: Default::default(),
+            transactions_root: Default::default(),
+            message_outbox_root: Default::default(),
+            event_inbox_root: Default::default(),
+            height: 10,
+            prev_root: Default::default(),
+            time: tai64::Tai64(0),
+            application_hash: Default::default(),
+        };
+
+        let block = FuelBlock {
+            id: FuelBlockId::from([1; 32]),
+            header,
+            consensus: FuelConsensus::Unknown,
+            transactions: vec![[2u8; 32].into(), [3u8; 32].into()],
+            block_producer: None,
+        };
+
+        FullFuelBlock {
+            block,
+            raw_transactions: vec![vec![2u8; 40], vec![3u8; 90]],
+        }
+    }
+
+    fn given_frames(state: &[u8], compression: Compression) -> Vec<StateFrame> {
+        let compressed = Compressor::new(compression, 3).compress(state).unwrap();
+        let chunks: Vec<_> = compressed.chunks(compressed.len().div_ceil(2)).collect();
+
+        chunks
+            .iter()
+            .enumerate()
+            .map(|(index, chunk)| StateFrame {
+                block_hash: [1; 32],
+                block_height: 10,
+                fragment_index: index as u32,
+                total_fragments: chunks.len() as u32,
+                compression,
+                data: chunk.to_vec(),
+            })
+            .collect()
+    }
+
+    fn given_fuel_with(block: FullFuelBlock) -> ports::fuel::MockApi {
+        let mut fuel = ports::fuel::MockApi::new();
+        fuel.expect_full_blocks_in_height_range()
+            .with(mockall::predicate::eq(10..=10))
+            .return_once(move |_| Ok(vec![block]));
+
+        fuel
+    }
+
+    #[tokio::test]
+    async fn verifies_state_matching_the_fuel_block() {
+        // given
+        let block = given_block();
+        let mut frames = given_frames(&encode_block_state(&block), Compression::Zstd);
+        frames.reverse();
+        let verifier = StateVerifier::new(given_fuel_with(block));
+
+        // when
+        let reports = verifier.verify(frames).await.unwrap();
+
+        // then
+        assert_eq!(
+            reports,
+            vec![StateReport {
+                block_hash: [1; 32],
+                block_height: 10,
+                verification: StateVerification::Verified,
+            }]
+        );
+    }
+
+    #[tokio::test]
+    async fn reports_missing_fragments() {
+        // given
+        let block = given_block();
+        let mut frames = given_frames(&encode_block_state(&block), Compression::None);
+        frames.truncate(1);
+        let verifier = StateVerifier::new(ports::fuel::MockApi::new());
+
+        // when
+        let reports = verifier.verify(frames).await.unwrap();
+
+        // then
+        assert_eq!(
+            reports[0].verification,
+            StateVerification::Incomplete {
+                missing_fragments: vec![1]
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn reports_state_differing_from_the_fuel_block() {
+        // given
+        let block = given_block();
+        let frames = given_frames(&[2u8; 64], Compression::None);
+        let verifier = StateVerifier::new(given_fuel_with(block));
+
+        // when
+        let reports = verifier.verify(frames).await.unwrap();
+
+        // then
+        assert!(matches!(
+            reports[0].verification,
+            StateVerification::Mismatch(_)
+        ));
+    }
+}
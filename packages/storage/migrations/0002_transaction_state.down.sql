@@ -0,0 +1,11 @@
+BEGIN;
+
+ALTER TABLE l1_transactions
+DROP CONSTRAINT state_check;
+
+ALTER TABLE l1_transactions
+DROP COLUMN state;
+
+ALTER TABLE l1_transactions RENAME TO l1_pending_transaction;
+
+COMMIT;
@@ -0,0 +1,11 @@
+BEGIN;
+
+ALTER TABLE l1_pending_transaction RENAME TO l1_transactions;
+
+ALTER TABLE l1_transactions
+ADD COLUMN state SMALLINT NOT NULL DEFAULT 0;
+
+ALTER TABLE l1_transactions
+ADD CONSTRAINT state_check CHECK (state IN (0, 1, 2));
+
+COMMIT;
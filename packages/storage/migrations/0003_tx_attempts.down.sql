@@ -0,0 +1,10 @@
+BEGIN;
+
+DROP TABLE IF EXISTS l1_transaction_fragments;
+
+ALTER TABLE l1_transactions
+DROP COLUMN details,
+DROP COLUMN submitted_at,
+DROP COLUMN id;
+
+COMMIT;
@@ -0,0 +1,28 @@
+BEGIN;
+
+-- what the da layer needs to replace or read back a tx, kept as it hands it over. Txs sent before
+-- this migration get the details of a blob tx nothing is known about, see `eth::state_tx::Details`
+ALTER TABLE l1_transactions
+ADD COLUMN details      BYTEA NOT NULL DEFAULT convert_to('{"nonce":0,"max_fee_per_gas":0,"max_priority_fee_per_gas":0,"max_fee_per_blob_gas":0,"gas_limit":0,"blob_gas":0,"mode":"blob"}', 'UTF8'),
+ADD COLUMN submitted_at BIGINT NOT NULL DEFAULT 0 CHECK (submitted_at >= 0),
+-- orders the attempts sent at the same height
+ADD COLUMN id           BIGSERIAL NOT NULL;
+
+ALTER TABLE l1_transactions
+ALTER COLUMN details DROP DEFAULT;
+
+-- every attempt (original tx and its replacements) carries the same fragments
+CREATE TABLE IF NOT EXISTS l1_transaction_fragments (
+    transaction_hash BYTEA NOT NULL REFERENCES l1_transactions(transaction_hash) ON DELETE CASCADE,
+    fuel_block_hash  BYTEA NOT NULL,
+    fragment_index   BIGINT NOT NULL,
+    PRIMARY KEY (transaction_hash, fuel_block_hash, fragment_index),
+    FOREIGN KEY (fuel_block_hash, fragment_index) REFERENCES l1_state_fragment(fuel_block_hash, fragment_index) ON DELETE CASCADE
+);
+
+INSERT INTO l1_transaction_fragments (transaction_hash, fuel_block_hash, fragment_index)
+SELECT transaction_hash, fuel_block_hash, fragment_index
+FROM l1_state_fragment
+WHERE transaction_hash IS NOT NULL;
+
+COMMIT;
@@ -0,0 +1,10 @@
+BEGIN;
+
+-- next nonce to hand out per wallet, so that a restart doesn't reuse or skip any
+CREATE TABLE IF NOT EXISTS l1_nonces (
+    address    BYTEA PRIMARY KEY NOT NULL,
+    next_nonce BIGINT NOT NULL CHECK (next_nonce >= 0),
+    CHECK (octet_length(address) = 20)
+);
+
+COMMIT;
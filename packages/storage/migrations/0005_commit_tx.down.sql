@@ -0,0 +1,10 @@
+BEGIN;
+
+ALTER TABLE l1_fuel_block_submission
+DROP COLUMN tx_hash,
+DROP COLUMN tx_block_height,
+DROP COLUMN tx_succeeded,
+DROP COLUMN tx_gas_used,
+DROP COLUMN tx_effective_gas_price;
+
+COMMIT;
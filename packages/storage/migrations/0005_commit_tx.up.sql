@@ -0,0 +1,11 @@
+BEGIN;
+
+-- the commit tx and, once included, its receipt
+ALTER TABLE l1_fuel_block_submission
+ADD COLUMN tx_hash                BYTEA CHECK (octet_length(tx_hash) = 32),
+ADD COLUMN tx_block_height        BIGINT CHECK (tx_block_height >= 0),
+ADD COLUMN tx_succeeded           BOOLEAN,
+ADD COLUMN tx_gas_used            BIGINT CHECK (tx_gas_used >= 0),
+ADD COLUMN tx_effective_gas_price BIGINT CHECK (tx_effective_gas_price >= 0);
+
+COMMIT;
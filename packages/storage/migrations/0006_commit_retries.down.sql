@@ -0,0 +1,7 @@
+BEGIN;
+
+ALTER TABLE l1_fuel_block_submission
+DROP COLUMN attempts,
+DROP COLUMN retry_at;
+
+COMMIT;
@@ -0,0 +1,8 @@
+BEGIN;
+
+ALTER TABLE l1_fuel_block_submission
+ADD COLUMN attempts BIGINT NOT NULL DEFAULT 1 CHECK (attempts >= 1),
+-- L1 height after which an incomplete submission gets resent
+ADD COLUMN retry_at BIGINT NOT NULL DEFAULT 0 CHECK (retry_at >= 0);
+
+COMMIT;
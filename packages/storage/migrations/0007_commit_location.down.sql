@@ -0,0 +1,8 @@
+BEGIN;
+
+ALTER TABLE l1_fuel_block_submission
+DROP COLUMN commit_l1_height,
+DROP COLUMN commit_l1_block_hash,
+DROP COLUMN tx_block_hash;
+
+COMMIT;
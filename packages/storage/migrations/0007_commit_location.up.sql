@@ -0,0 +1,10 @@
+BEGIN;
+
+-- L1 block the commit event was seen in, needed to notice it getting reorged out
+ALTER TABLE l1_fuel_block_submission
+ADD COLUMN commit_l1_height     BIGINT CHECK (commit_l1_height >= 0),
+ADD COLUMN commit_l1_block_hash BYTEA CHECK (octet_length(commit_l1_block_hash) = 32),
+-- block the commit tx was included in, as told by its receipt
+ADD COLUMN tx_block_hash        BYTEA CHECK (octet_length(tx_block_hash) = 32);
+
+COMMIT;
@@ -0,0 +1,6 @@
+BEGIN;
+
+ALTER TABLE l1_state_submission
+DROP COLUMN compression;
+
+COMMIT;
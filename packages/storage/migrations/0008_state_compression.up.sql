@@ -0,0 +1,7 @@
+BEGIN;
+
+-- Codec the state was compressed with, see `ports::types::Compression::id`
+ALTER TABLE l1_state_submission
+ADD COLUMN compression SMALLINT NOT NULL DEFAULT 0 CHECK (compression >= 0);
+
+COMMIT;
@@ -0,0 +1,6 @@
+BEGIN;
+
+ALTER TABLE l1_state_submission
+DROP COLUMN num_fragments;
+
+COMMIT;
@@ -0,0 +1,10 @@
+BEGIN;
+
+-- Number of fragments the block state was split into, posted along with every fragment
+ALTER TABLE l1_state_submission
+ADD COLUMN num_fragments BIGINT NOT NULL DEFAULT 0 CHECK (num_fragments >= 0);
+
+UPDATE l1_state_submission s
+SET num_fragments = (SELECT COUNT(*) FROM l1_state_fragment f WHERE f.fuel_block_hash = s.fuel_block_hash);
+
+COMMIT;
@@ -0,0 +1,8 @@
+BEGIN;
+
+ALTER TABLE l1_fuel_block_submission
+DROP COLUMN tx_nonce,
+DROP COLUMN tx_max_fee_per_gas,
+DROP COLUMN tx_max_priority_fee_per_gas;
+
+COMMIT;
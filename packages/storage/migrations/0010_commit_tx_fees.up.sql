@@ -0,0 +1,9 @@
+BEGIN;
+
+-- needed to replace a stuck commit tx instead of sending a second one
+ALTER TABLE l1_fuel_block_submission
+ADD COLUMN tx_nonce                    BIGINT CHECK (tx_nonce >= 0),
+ADD COLUMN tx_max_fee_per_gas          BIGINT CHECK (tx_max_fee_per_gas >= 0),
+ADD COLUMN tx_max_priority_fee_per_gas BIGINT CHECK (tx_max_priority_fee_per_gas >= 0);
+
+COMMIT;
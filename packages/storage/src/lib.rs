@@ -10,7 +10,10 @@ mod postgres;
 use ports::types::BlockSubmission;
 pub use postgres::*;
 
-use ports::types::{StateFragment, StateFragmentId, StateSubmission};
+use ports::types::{
+    CommitLocation, CommitTx, L1Height, StateFragment, StateFragmentId, StateSubmission,
+    SubmissionTx, TransactionResponse, TransactionState,
+};
 
 #[async_trait::async_trait]
 impl ports::storage::Storage for postgres::Postgres {
@@ -29,6 +32,70 @@ impl ports::storage::Storage for postgres::Postgres {
         Ok(self._set_submission_completed(fuel_block_hash).await?)
     }
 
+    async fn record_resubmission(
+        &self,
+        fuel_block_hash: [u8; 32],
+        retry_at: L1Height,
+    ) -> ports::storage::Result<BlockSubmission> {
+        Ok(self._record_resubmission(fuel_block_hash, retry_at).await?)
+    }
+
+    async fn record_commit_event(
+        &self,
+        fuel_block_hash: [u8; 32],
+        location: CommitLocation,
+    ) -> ports::storage::Result<BlockSubmission> {
+        Ok(self._record_commit_event(fuel_block_hash, location).await?)
+    }
+
+    async fn revert_commit_event(
+        &self,
+        fuel_block_hash: [u8; 32],
+    ) -> ports::storage::Result<BlockSubmission> {
+        Ok(self._revert_commit_event(fuel_block_hash).await?)
+    }
+
+    async fn requeue_submission(
+        &self,
+        fuel_block_hash: [u8; 32],
+        retry_at: L1Height,
+    ) -> ports::storage::Result<BlockSubmission> {
+        Ok(self._requeue_submission(fuel_block_hash, retry_at).await?)
+    }
+
+    async fn submissions_committed_since(
+        &self,
+        l1_height: L1Height,
+    ) -> ports::storage::Result<Vec<BlockSubmission>> {
+        Ok(self._submissions_committed_since(l1_height).await?)
+    }
+
+    async fn set_submission_tx(
+        &self,
+        fuel_block_hash: [u8; 32],
+        tx: CommitTx,
+    ) -> ports::storage::Result<()> {
+        Ok(self._set_submission_tx(fuel_block_hash, tx).await?)
+    }
+
+    async fn submissions_awaiting_commit(&self) -> ports::storage::Result<Vec<BlockSubmission>> {
+        Ok(self._submissions_awaiting_commit().await?)
+    }
+
+    async fn submissions_awaiting_receipt(&self) -> ports::storage::Result<Vec<BlockSubmission>> {
+        Ok(self._submissions_awaiting_receipt().await?)
+    }
+
+    async fn set_submission_receipt(
+        &self,
+        fuel_block_hash: [u8; 32],
+        receipt: TransactionResponse,
+    ) -> ports::storage::Result<BlockSubmission> {
+        Ok(self
+            ._set_submission_receipt(fuel_block_hash, receipt)
+            .await?)
+    }
+
     async fn insert_state(
         &self,
         state: StateSubmission,
@@ -37,34 +104,98 @@ impl ports::storage::Storage for postgres::Postgres {
         Ok(self._insert_state(state, fragments).await?)
     }
 
-    async fn get_unsubmitted_fragments(&self) -> ports::storage::Result<Vec<StateFragment>> {
-        Ok(self._get_unsubmitted_fragments().await?)
+    async fn get_unsubmitted_fragments(
+        &self,
+        max_total_size: usize,
+    ) -> ports::storage::Result<Vec<StateFragment>> {
+        Ok(self._get_unsubmitted_fragments(max_total_size).await?)
     }
 
     async fn record_pending_tx(
         &self,
-        tx_hash: [u8; 32],
+        tx: SubmissionTx,
         fragment_ids: Vec<StateFragmentId>,
     ) -> ports::storage::Result<()> {
-        Ok(self._record_pending_tx(tx_hash, fragment_ids).await?)
+        Ok(self._record_pending_tx(tx, fragment_ids).await?)
+    }
+
+    async fn get_tx_fragments(
+        &self,
+        tx_hash: [u8; 32],
+    ) -> ports::storage::Result<Vec<StateFragment>> {
+        Ok(self._get_tx_fragments(tx_hash).await?)
     }
 
     async fn has_pending_txs(&self) -> ports::storage::Result<bool> {
         Ok(self._has_pending_txs().await?)
     }
 
+    async fn get_pending_txs(&self) -> ports::storage::Result<Vec<SubmissionTx>> {
+        Ok(self._get_pending_txs().await?)
+    }
+
+    async fn submission_tx(
+        &self,
+        tx_hash: [u8; 32],
+    ) -> ports::storage::Result<Option<SubmissionTx>> {
+        Ok(self._submission_tx(tx_hash).await?)
+    }
+
+    async fn update_submission_tx_state(
+        &self,
+        hash: [u8; 32],
+        state: TransactionState,
+    ) -> ports::storage::Result<()> {
+        Ok(self._update_submission_tx_state(hash, state).await?)
+    }
+
     async fn state_submission_w_latest_block(
         &self,
     ) -> ports::storage::Result<Option<StateSubmission>> {
         Ok(self._state_submission_w_latest_block().await?)
     }
+
+    async fn state_submission(
+        &self,
+        fuel_block_hash: [u8; 32],
+    ) -> ports::storage::Result<Option<StateSubmission>> {
+        Ok(self._state_submission(fuel_block_hash).await?)
+    }
+
+    async fn missing_state_heights(
+        &self,
+        from: u32,
+        to: u32,
+        limit: usize,
+    ) -> ports::storage::Result<Vec<u32>> {
+        Ok(self._missing_state_heights(from, to, limit).await?)
+    }
+
+    async fn reserve_nonce(
+        &self,
+        address: [u8; 20],
+        min_nonce: u64,
+    ) -> ports::storage::Result<u64> {
+        Ok(self._reserve_nonce(address, min_nonce).await?)
+    }
+
+    async fn next_nonce(&self, address: [u8; 20]) -> ports::storage::Result<Option<u64>> {
+        Ok(self._next_nonce(address).await?)
+    }
+
+    async fn set_next_nonce(&self, address: [u8; 20], nonce: u64) -> ports::storage::Result<()> {
+        Ok(self._set_next_nonce(address, nonce).await?)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use ports::{
         storage::{Error, Storage},
-        types::BlockSubmission,
+        types::{
+            BlockSubmission, BlockSubmissionState, CommitLocation, CommitTx, Compression,
+            StateSubmission, SubmissionTx, TransactionResponse, TransactionState, TxFees,
+        },
     };
     use rand::{thread_rng, Rng};
     use storage as _;
@@ -136,6 +267,175 @@ mod tests {
         assert_eq!(msg, format!("Cannot set submission to completed! Submission of block: `{block_hash}` not found in DB."));
     }
 
+    #[tokio::test]
+    async fn reserved_nonces_are_unique_and_respect_the_minimum() {
+        // given
+        let process = PostgresProcess::shared().await.unwrap();
+        let db = process.create_random_db().await.unwrap();
+        let address = [1; 20];
+
+        // when
+        let first = db.reserve_nonce(address, 5).await.unwrap();
+        let second = db.reserve_nonce(address, 5).await.unwrap();
+        let third = db.reserve_nonce(address, 10).await.unwrap();
+
+        // then
+        assert_eq!((first, second, third), (5, 6, 10));
+        assert_eq!(db.next_nonce(address).await.unwrap(), Some(11));
+        assert_eq!(db.next_nonce([2; 20]).await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn a_reverted_commit_tx_marks_the_submission_as_failed() {
+        // given
+        let process = PostgresProcess::shared().await.unwrap();
+        let db = process.create_random_db().await.unwrap();
+
+        let submission = BlockSubmission {
+            tx_hash: None,
+            ..given_incomplete_submission(random_non_zero_height())
+        };
+        let block_hash = submission.block_hash;
+        db.insert(submission).await.unwrap();
+        db.set_submission_tx(block_hash, given_commit_tx())
+            .await
+            .unwrap();
+        assert_eq!(db.submissions_awaiting_receipt().await.unwrap().len(), 1);
+
+        // when
+        let receipt = TransactionResponse::new(10, [10; 32], false, 21_000, 1_000_000_000);
+        let submission = db
+            .set_submission_receipt(block_hash, receipt)
+            .await
+            .unwrap();
+
+        // then
+        assert_eq!(submission.state(), BlockSubmissionState::Failed);
+        assert_eq!(submission.tx_hash, Some([1; 32]));
+        assert_eq!(submission.tx_receipt, Some(receipt));
+        assert!(db.submissions_awaiting_receipt().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn missing_state_heights_reports_gaps_in_imported_state() {
+        // given
+        let process = PostgresProcess::shared().await.unwrap();
+        let db = process.create_random_db().await.unwrap();
+        for height in [2, 3, 6] {
+            let state = StateSubmission {
+                block_hash: [height as u8; 32],
+                block_height: height,
+                completed: true,
+                compression: Compression::None,
+                num_fragments: 0,
+            };
+            db.insert_state(state, vec![]).await.unwrap();
+        }
+
+        // when
+        let missing = db.missing_state_heights(1, 8, 100).await.unwrap();
+        let limited = db.missing_state_heights(1, 8, 2).await.unwrap();
+        let within = db.missing_state_heights(3, 5, 100).await.unwrap();
+
+        // then
+        assert_eq!(missing, vec![1, 4, 5, 7, 8]);
+        assert_eq!(limited, vec![1, 4]);
+        assert_eq!(within, vec![4, 5]);
+    }
+
+    #[tokio::test]
+    async fn resubmission_keeps_the_commit_tx_to_be_replaced() {
+        // given
+        let process = PostgresProcess::shared().await.unwrap();
+        let db = process.create_random_db().await.unwrap();
+
+        let submission = given_incomplete_submission(random_non_zero_height());
+        let block_hash = submission.block_hash;
+        db.insert(submission).await.unwrap();
+        db.set_submission_tx(block_hash, given_commit_tx())
+            .await
+            .unwrap();
+
+        // when
+        let submission = db
+            .record_resubmission(block_hash, 100u32.into())
+            .await
+            .unwrap();
+
+        // then
+        let tx = given_commit_tx();
+        assert_eq!(submission.tx_hash, Some(tx.hash));
+        assert_eq!(submission.tx_nonce, Some(tx.nonce));
+        assert_eq!(submission.tx_fees, Some(tx.fees));
+    }
+
+    #[tokio::test]
+    async fn submissions_awaiting_commit_skip_completed_and_landed_ones() {
+        // given
+        let process = PostgresProcess::shared().await.unwrap();
+        let db = process.create_random_db().await.unwrap();
+
+        let [awaiting_a, awaiting_b, completed, landed] =
+            [4, 2, 3, 1].map(|height| BlockSubmission {
+                completed: false,
+                ..given_incomplete_submission(height)
+            });
+        for submission in [&awaiting_a, &awaiting_b, &completed, &landed] {
+            db.insert(submission.clone()).await.unwrap();
+        }
+        db.set_submission_completed(completed.block_hash)
+            .await
+            .unwrap();
+        let location = CommitLocation {
+            l1_height: 10u32.into(),
+            l1_block_hash: [3; 32],
+        };
+        db.record_commit_event(landed.block_hash, location)
+            .await
+            .unwrap();
+
+        // when
+        let awaiting = db.submissions_awaiting_commit().await.unwrap();
+
+        // then
+        assert_eq!(awaiting, vec![awaiting_b, awaiting_a]);
+    }
+
+    #[tokio::test]
+    async fn submission_tx_is_read_back_with_its_details() {
+        // given
+        let process = PostgresProcess::shared().await.unwrap();
+        let db = process.create_random_db().await.unwrap();
+
+        let tx = SubmissionTx {
+            hash: [1; 32],
+            details: vec![1, 2, 3],
+            submitted_at: 10u32.into(),
+            state: TransactionState::Pending,
+        };
+        db.record_pending_tx(tx.clone(), vec![]).await.unwrap();
+
+        // when
+        let stored = db.submission_tx(tx.hash).await.unwrap();
+        let unknown = db.submission_tx([9; 32]).await.unwrap();
+
+        // then
+        assert_eq!(stored, Some(tx));
+        assert_eq!(unknown, None);
+    }
+
+    fn given_commit_tx() -> CommitTx {
+        CommitTx {
+            hash: [1; 32],
+            nonce: 7,
+            fees: TxFees {
+                max_fee_per_gas: 2_000_000_000,
+                max_priority_fee_per_gas: 1_000_000_000,
+                max_fee_per_blob_gas: 0,
+            },
+        }
+    }
+
     fn given_incomplete_submission(fuel_block_height: u32) -> BlockSubmission {
         let mut submission = rand::thread_rng().gen::<BlockSubmission>();
         submission.block_height = fuel_block_height;
@@ -1,5 +1,8 @@
-use crate::tables::state_submission::{L1StateFragment, L1StateSubmission};
-use ports::types::{BlockSubmission, StateFragment, StateFragmentId, StateSubmission};
+use crate::tables::state_submission::{L1StateFragment, L1StateSubmission, L1SubmissionTx};
+use ports::types::{
+    BlockSubmission, CommitLocation, CommitTx, L1Height, StateFragment, StateFragmentId,
+    StateSubmission, SubmissionTx, TransactionResponse, TransactionState,
+};
 use sqlx::postgres::{PgConnectOptions, PgPoolOptions};
 
 use super::error::{Error, Result};
@@ -79,13 +82,26 @@ impl Postgres {
     }
 
     pub(crate) async fn _insert(&self, submission: BlockSubmission) -> crate::error::Result<()> {
-        let row = tables::L1FuelBlockSubmission::from(submission);
+        let row = tables::L1FuelBlockSubmission::try_from(submission)?;
         sqlx::query!(
-            "INSERT INTO l1_fuel_block_submission (fuel_block_hash, fuel_block_height, completed, submittal_height) VALUES ($1, $2, $3, $4)",
+            "INSERT INTO l1_fuel_block_submission (fuel_block_hash, fuel_block_height, completed, submittal_height, tx_hash, tx_block_height, tx_block_hash, tx_succeeded, tx_gas_used, tx_effective_gas_price, attempts, retry_at, commit_l1_height, commit_l1_block_hash, tx_nonce, tx_max_fee_per_gas, tx_max_priority_fee_per_gas) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17)",
             row.fuel_block_hash,
             row.fuel_block_height,
             row.completed,
-            row.submittal_height
+            row.submittal_height,
+            row.tx_hash,
+            row.tx_block_height,
+            row.tx_block_hash,
+            row.tx_succeeded,
+            row.tx_gas_used,
+            row.tx_effective_gas_price,
+            row.attempts,
+            row.retry_at,
+            row.commit_l1_height,
+            row.commit_l1_block_hash,
+            row.tx_nonce,
+            row.tx_max_fee_per_gas,
+            row.tx_max_priority_fee_per_gas,
         ).execute(&self.connection_pool).await?;
         Ok(())
     }
@@ -121,17 +137,220 @@ impl Postgres {
         }
     }
 
+    pub(crate) async fn _record_resubmission(
+        &self,
+        fuel_block_hash: [u8; 32],
+        retry_at: L1Height,
+    ) -> Result<BlockSubmission> {
+        // the previous tx is kept until its replacement was sent so that its receipt still gets
+        // picked up should it land in the meantime
+        let updated_row = sqlx::query_as!(
+            tables::L1FuelBlockSubmission,
+            "UPDATE l1_fuel_block_submission
+            SET attempts = attempts + 1, retry_at = $1, tx_block_height = NULL, tx_block_hash = NULL, tx_succeeded = NULL, tx_gas_used = NULL, tx_effective_gas_price = NULL
+            WHERE fuel_block_hash = $2 RETURNING *",
+            i64::from(retry_at),
+            fuel_block_hash.as_slice(),
+        )
+        .fetch_optional(&self.connection_pool)
+        .await?;
+
+        if let Some(row) = updated_row {
+            Ok(row.try_into()?)
+        } else {
+            let hash = hex::encode(fuel_block_hash);
+            Err(Error::Database(format!(
+                "Cannot record resubmission! Submission of block: `{hash}` not found in DB."
+            )))
+        }
+    }
+
+    pub(crate) async fn _record_commit_event(
+        &self,
+        fuel_block_hash: [u8; 32],
+        location: CommitLocation,
+    ) -> Result<BlockSubmission> {
+        let updated_row = sqlx::query_as!(
+            tables::L1FuelBlockSubmission,
+            "UPDATE l1_fuel_block_submission SET commit_l1_height = $1, commit_l1_block_hash = $2 WHERE fuel_block_hash = $3 RETURNING *",
+            i64::from(location.l1_height),
+            location.l1_block_hash.as_slice(),
+            fuel_block_hash.as_slice(),
+        )
+        .fetch_optional(&self.connection_pool)
+        .await?;
+
+        Self::updated_submission(updated_row, fuel_block_hash, "record commit event")
+    }
+
+    pub(crate) async fn _revert_commit_event(
+        &self,
+        fuel_block_hash: [u8; 32],
+    ) -> Result<BlockSubmission> {
+        let updated_row = sqlx::query_as!(
+            tables::L1FuelBlockSubmission,
+            "UPDATE l1_fuel_block_submission SET completed = false, commit_l1_height = NULL, commit_l1_block_hash = NULL WHERE fuel_block_hash = $1 RETURNING *",
+            fuel_block_hash.as_slice(),
+        )
+        .fetch_optional(&self.connection_pool)
+        .await?;
+
+        Self::updated_submission(updated_row, fuel_block_hash, "revert commit event")
+    }
+
+    pub(crate) async fn _requeue_submission(
+        &self,
+        fuel_block_hash: [u8; 32],
+        retry_at: L1Height,
+    ) -> Result<BlockSubmission> {
+        let updated_row = sqlx::query_as!(
+            tables::L1FuelBlockSubmission,
+            "UPDATE l1_fuel_block_submission
+            SET completed = false, commit_l1_height = NULL, commit_l1_block_hash = NULL, retry_at = $1,
+                tx_block_height = NULL, tx_block_hash = NULL, tx_succeeded = NULL, tx_gas_used = NULL, tx_effective_gas_price = NULL
+            WHERE fuel_block_hash = $2 RETURNING *",
+            i64::from(retry_at),
+            fuel_block_hash.as_slice(),
+        )
+        .fetch_optional(&self.connection_pool)
+        .await?;
+
+        Self::updated_submission(updated_row, fuel_block_hash, "requeue submission")
+    }
+
+    pub(crate) async fn _submissions_committed_since(
+        &self,
+        l1_height: L1Height,
+    ) -> Result<Vec<BlockSubmission>> {
+        sqlx::query_as!(
+            tables::L1FuelBlockSubmission,
+            "SELECT * FROM l1_fuel_block_submission WHERE commit_l1_height >= $1 ORDER BY commit_l1_height ASC",
+            i64::from(l1_height),
+        )
+        .fetch_all(&self.connection_pool)
+        .await?
+        .into_iter()
+        .map(BlockSubmission::try_from)
+        .collect()
+    }
+
+    fn updated_submission(
+        updated_row: Option<tables::L1FuelBlockSubmission>,
+        fuel_block_hash: [u8; 32],
+        action: &str,
+    ) -> Result<BlockSubmission> {
+        if let Some(row) = updated_row {
+            Ok(row.try_into()?)
+        } else {
+            let hash = hex::encode(fuel_block_hash);
+            Err(Error::Database(format!(
+                "Cannot {action}! Submission of block: `{hash}` not found in DB."
+            )))
+        }
+    }
+
+    pub(crate) async fn _set_submission_tx(
+        &self,
+        fuel_block_hash: [u8; 32],
+        tx: CommitTx,
+    ) -> Result<()> {
+        let to_i64 = |value: u128, column: &str| {
+            i64::try_from(value).map_err(|_| {
+                Error::Conversion(format!("`{column}` of {value} doesn't fit into an i64"))
+            })
+        };
+
+        let updated = sqlx::query!(
+            "UPDATE l1_fuel_block_submission
+            SET tx_hash = $1, tx_nonce = $2, tx_max_fee_per_gas = $3, tx_max_priority_fee_per_gas = $4
+            WHERE fuel_block_hash = $5",
+            tx.hash.as_slice(),
+            to_i64(tx.nonce.into(), "tx_nonce")?,
+            to_i64(tx.fees.max_fee_per_gas, "tx_max_fee_per_gas")?,
+            to_i64(
+                tx.fees.max_priority_fee_per_gas,
+                "tx_max_priority_fee_per_gas"
+            )?,
+            fuel_block_hash.as_slice(),
+        )
+        .execute(&self.connection_pool)
+        .await?;
+
+        if updated.rows_affected() == 0 {
+            let hash = hex::encode(fuel_block_hash);
+            return Err(Error::Database(format!(
+                "Cannot record commit tx! Submission of block: `{hash}` not found in DB."
+            )));
+        }
+
+        Ok(())
+    }
+
+    pub(crate) async fn _submissions_awaiting_commit(&self) -> Result<Vec<BlockSubmission>> {
+        sqlx::query_as!(
+            tables::L1FuelBlockSubmission,
+            "SELECT * FROM l1_fuel_block_submission WHERE completed = false AND commit_l1_height IS NULL ORDER BY fuel_block_height ASC"
+        )
+        .fetch_all(&self.connection_pool)
+        .await?
+        .into_iter()
+        .map(BlockSubmission::try_from)
+        .collect()
+    }
+
+    pub(crate) async fn _submissions_awaiting_receipt(&self) -> Result<Vec<BlockSubmission>> {
+        sqlx::query_as!(
+            tables::L1FuelBlockSubmission,
+            "SELECT * FROM l1_fuel_block_submission WHERE tx_hash IS NOT NULL AND tx_block_height IS NULL ORDER BY fuel_block_height ASC"
+        )
+        .fetch_all(&self.connection_pool)
+        .await?
+        .into_iter()
+        .map(BlockSubmission::try_from)
+        .collect()
+    }
+
+    pub(crate) async fn _set_submission_receipt(
+        &self,
+        fuel_block_hash: [u8; 32],
+        receipt: TransactionResponse,
+    ) -> Result<BlockSubmission> {
+        let to_i64 = |value: u128, column: &str| {
+            i64::try_from(value).map_err(|_| {
+                Error::Conversion(format!("`{column}` of {value} doesn't fit into an i64"))
+            })
+        };
+
+        let updated_row = sqlx::query_as!(
+            tables::L1FuelBlockSubmission,
+            "UPDATE l1_fuel_block_submission
+            SET tx_block_height = $1, tx_block_hash = $2, tx_succeeded = $3, tx_gas_used = $4, tx_effective_gas_price = $5
+            WHERE fuel_block_hash = $6 RETURNING *",
+            to_i64(receipt.block_number().into(), "tx_block_height")?,
+            receipt.block_hash().to_vec(),
+            receipt.succeeded(),
+            to_i64(receipt.gas_used().into(), "tx_gas_used")?,
+            to_i64(receipt.effective_gas_price(), "tx_effective_gas_price")?,
+            fuel_block_hash.as_slice(),
+        )
+        .fetch_optional(&self.connection_pool)
+        .await?;
+
+        if let Some(row) = updated_row {
+            Ok(row.try_into()?)
+        } else {
+            let hash = hex::encode(fuel_block_hash);
+            Err(Error::Database(format!(
+                "Cannot record commit receipt! Submission of block: `{hash}` not found in DB."
+            )))
+        }
+    }
+
     pub(crate) async fn _insert_state(
         &self,
         state: StateSubmission,
         fragments: Vec<StateFragment>,
     ) -> Result<()> {
-        if fragments.is_empty() {
-            return Err(Error::Database(
-                "Cannot insert state with no fragments".to_string(),
-            ));
-        }
-
         let state_row = L1StateSubmission::from(state);
         let fragment_rows = fragments
             .into_iter()
@@ -142,10 +361,12 @@ impl Postgres {
 
         // Insert the state submission
         sqlx::query!(
-            "INSERT INTO l1_state_submission (fuel_block_hash, fuel_block_height, completed) VALUES ($1, $2, $3)",
+            "INSERT INTO l1_state_submission (fuel_block_hash, fuel_block_height, completed, compression, num_fragments) VALUES ($1, $2, $3, $4, $5)",
             state_row.fuel_block_hash,
             state_row.fuel_block_height,
             state_row.completed,
+            state_row.compression,
+            state_row.num_fragments,
         )
         .execute(&mut *transaction)
         .await?;
@@ -169,11 +390,24 @@ impl Postgres {
         Ok(())
     }
 
-    pub(crate) async fn _get_unsubmitted_fragments(&self) -> Result<Vec<StateFragment>> {
-        // TODO use blob limit
+    pub(crate) async fn _get_unsubmitted_fragments(
+        &self,
+        max_total_size: usize,
+    ) -> Result<Vec<StateFragment>> {
+        let max_total_size = i64::try_from(max_total_size).unwrap_or(i64::MAX);
+
+        // oldest fragments first, as many as fit into `max_total_size` bytes of data
         let rows = sqlx::query_as!(
             L1StateFragment,
-            "SELECT * FROM l1_state_fragment WHERE completed = false ORDER BY created_at ASC LIMIT 6"
+            r#"SELECT fuel_block_hash AS "fuel_block_hash!", transaction_hash, raw_data AS "raw_data!", created_at AS "created_at!", fragment_index AS "fragment_index!", completed AS "completed!"
+            FROM (
+                SELECT *, SUM(octet_length(raw_data)) OVER (ORDER BY created_at ASC, fuel_block_hash ASC, fragment_index ASC) AS total_size
+                FROM l1_state_fragment
+                WHERE completed = false
+            ) f
+            WHERE total_size <= $1
+            ORDER BY created_at ASC, fuel_block_hash ASC, fragment_index ASC"#,
+            max_total_size
         )
         .fetch_all(&self.connection_pool)
         .await?
@@ -185,24 +419,39 @@ impl Postgres {
 
     pub(crate) async fn _record_pending_tx(
         &self,
-        tx_hash: [u8; 32],
+        tx: SubmissionTx,
         fragment_ids: Vec<StateFragmentId>,
     ) -> Result<()> {
+        let row = L1SubmissionTx::from(tx);
+
         let mut transaction = self.connection_pool.begin().await?;
 
         sqlx::query!(
-            "INSERT INTO l1_pending_transaction (transaction_hash) VALUES ($1)",
-            tx_hash.as_slice()
+            "INSERT INTO l1_transactions (transaction_hash, state, submitted_at, details) VALUES ($1, $2, $3, $4)",
+            row.transaction_hash,
+            row.state,
+            row.submitted_at,
+            row.details,
         )
         .execute(&mut *transaction)
         .await?;
 
         for (block_hash, fragment_idx) in fragment_ids {
+            sqlx::query!(
+                "INSERT INTO l1_transaction_fragments (transaction_hash, fuel_block_hash, fragment_index) VALUES ($1, $2, $3)",
+                row.transaction_hash,
+                block_hash.as_slice(),
+                i64::from(fragment_idx)
+            )
+            .execute(&mut *transaction)
+            .await?;
+
+            // the fragment always points to its latest submission attempt
             sqlx::query!(
                 "UPDATE l1_state_fragment SET transaction_hash = $1 WHERE fuel_block_hash = $2 AND fragment_index = $3",
-                tx_hash.as_slice(),
+                row.transaction_hash,
                 block_hash.as_slice(),
-                fragment_idx as i64
+                i64::from(fragment_idx)
             )
             .execute(&mut *transaction)
             .await?;
@@ -213,15 +462,133 @@ impl Postgres {
         Ok(())
     }
 
+    pub(crate) async fn _get_tx_fragments(&self, tx_hash: [u8; 32]) -> Result<Vec<StateFragment>> {
+        sqlx::query_as!(
+            L1StateFragment,
+            "SELECT f.* FROM l1_state_fragment f
+            JOIN l1_transaction_fragments tf ON tf.fuel_block_hash = f.fuel_block_hash AND tf.fragment_index = f.fragment_index
+            WHERE tf.transaction_hash = $1
+            ORDER BY f.created_at ASC, f.fragment_index ASC",
+            tx_hash.as_slice()
+        )
+        .fetch_all(&self.connection_pool)
+        .await?
+        .into_iter()
+        .map(StateFragment::try_from)
+        .collect::<Result<Vec<_>>>()
+    }
+
     pub(crate) async fn _has_pending_txs(&self) -> Result<bool> {
-        let resp =
-            sqlx::query!("SELECT EXISTS (SELECT 1 FROM l1_pending_transaction LIMIT 1) as exists")
-                .fetch_one(&self.connection_pool)
-                .await?;
+        let resp = sqlx::query!(
+            "SELECT EXISTS (SELECT 1 FROM l1_transactions WHERE state = $1) as exists",
+            L1SubmissionTx::db_state(TransactionState::Pending)
+        )
+        .fetch_one(&self.connection_pool)
+        .await?;
 
         Ok(resp.exists.expect("query will always return a row"))
     }
 
+    pub(crate) async fn _get_pending_txs(&self) -> Result<Vec<SubmissionTx>> {
+        sqlx::query_as!(
+            L1SubmissionTx,
+            "SELECT transaction_hash, state, submitted_at, details FROM l1_transactions WHERE state = $1 ORDER BY submitted_at ASC, id ASC",
+            L1SubmissionTx::db_state(TransactionState::Pending)
+        )
+        .fetch_all(&self.connection_pool)
+        .await?
+        .into_iter()
+        .map(SubmissionTx::try_from)
+        .collect::<Result<Vec<_>>>()
+    }
+
+    pub(crate) async fn _submission_tx(&self, tx_hash: [u8; 32]) -> Result<Option<SubmissionTx>> {
+        sqlx::query_as!(
+            L1SubmissionTx,
+            "SELECT transaction_hash, state, submitted_at, details FROM l1_transactions WHERE transaction_hash = $1",
+            tx_hash.as_slice()
+        )
+        .fetch_optional(&self.connection_pool)
+        .await?
+        .map(SubmissionTx::try_from)
+        .transpose()
+    }
+
+    pub(crate) async fn _update_submission_tx_state(
+        &self,
+        hash: [u8; 32],
+        state: TransactionState,
+    ) -> Result<()> {
+        let mut transaction = self.connection_pool.begin().await?;
+
+        let updated = sqlx::query!(
+            "UPDATE l1_transactions SET state = $1 WHERE transaction_hash = $2",
+            L1SubmissionTx::db_state(state),
+            hash.as_slice(),
+        )
+        .execute(&mut *transaction)
+        .await?;
+
+        if updated.rows_affected() == 0 {
+            let hash = hex::encode(hash);
+            return Err(Error::Database(format!(
+                "Cannot update transaction state! Transaction `{hash}` not found in DB."
+            )));
+        }
+
+        match state {
+            TransactionState::Finalized => {
+                // whichever attempt made it in, it carried the same fragments as the others
+                sqlx::query!(
+                    "UPDATE l1_state_fragment SET completed = true, transaction_hash = $1
+                    WHERE (fuel_block_hash, fragment_index) IN (
+                        SELECT fuel_block_hash, fragment_index FROM l1_transaction_fragments WHERE transaction_hash = $1
+                    )",
+                    hash.as_slice(),
+                )
+                .execute(&mut *transaction)
+                .await?;
+
+                // a submission is done once none of its fragments are left to be posted
+                sqlx::query!(
+                    "UPDATE l1_state_submission SET completed = true
+                    WHERE completed = false
+                    AND fuel_block_hash IN (SELECT fuel_block_hash FROM l1_transaction_fragments WHERE transaction_hash = $1)
+                    AND NOT EXISTS (
+                        SELECT 1 FROM l1_state_fragment f
+                        WHERE f.fuel_block_hash = l1_state_submission.fuel_block_hash AND f.completed = false
+                    )",
+                    hash.as_slice(),
+                )
+                .execute(&mut *transaction)
+                .await?;
+            }
+            TransactionState::Failed => {
+                // hand the fragments over to another attempt that is still pending, if any.
+                // Otherwise release them so that they get picked up by a new submission.
+                sqlx::query!(
+                    "UPDATE l1_state_fragment f SET transaction_hash = (
+                        SELECT t.transaction_hash FROM l1_transactions t
+                        JOIN l1_transaction_fragments tf ON tf.transaction_hash = t.transaction_hash
+                        WHERE tf.fuel_block_hash = f.fuel_block_hash AND tf.fragment_index = f.fragment_index AND t.state = $2
+                        ORDER BY t.submitted_at DESC
+                        LIMIT 1
+                    )
+                    WHERE f.transaction_hash = $1",
+                    hash.as_slice(),
+                    L1SubmissionTx::db_state(TransactionState::Pending),
+                )
+                .execute(&mut *transaction)
+                .await?;
+            }
+            TransactionState::Pending => {}
+        }
+
+        transaction.commit().await?;
+
+        Ok(())
+    }
+
     pub(crate) async fn _state_submission_w_latest_block(
         &self,
     ) -> crate::error::Result<Option<StateSubmission>> {
@@ -234,4 +601,114 @@ impl Postgres {
         .map(StateSubmission::try_from)
         .transpose()
     }
+
+    pub(crate) async fn _missing_state_heights(
+        &self,
+        from: u32,
+        to: u32,
+        limit: usize,
+    ) -> crate::error::Result<Vec<u32>> {
+        let limit = i64::try_from(limit).unwrap_or(i64::MAX);
+        // Finds the gaps between neighbouring imported heights instead of probing every height
+        // of the range. No more than `limit` heights are taken from each gap.
+        let heights = sqlx::query_scalar!(
+            r#"WITH bounds AS (
+                SELECT $1::BIGINT - 1 AS height
+                UNION ALL
+                SELECT fuel_block_height FROM l1_state_submission
+                WHERE fuel_block_height BETWEEN $1::BIGINT AND $2::BIGINT
+                UNION ALL
+                SELECT $2::BIGINT + 1
+            ),
+            gaps AS (
+                SELECT height + 1 AS gap_start, LEAD(height) OVER (ORDER BY height) - 1 AS gap_end
+                FROM bounds
+            )
+            SELECT height AS "height!"
+            FROM gaps, generate_series(gap_start, LEAST(gap_end, gap_start + $3::BIGINT - 1)) AS height
+            WHERE gap_end IS NOT NULL
+            ORDER BY height
+            LIMIT $3"#,
+            i64::from(from),
+            i64::from(to),
+            limit,
+        )
+        .fetch_all(&self.connection_pool)
+        .await?;
+
+        heights
+            .into_iter()
+            .map(|height| {
+                u32::try_from(height)
+                    .map_err(|_| Error::Conversion(format!("invalid block height: {height}")))
+            })
+            .collect()
+    }
+
+    pub(crate) async fn _state_submission(
+        &self,
+        fuel_block_hash: [u8; 32],
+    ) -> crate::error::Result<Option<StateSubmission>> {
+        sqlx::query_as!(
+            L1StateSubmission,
+            "SELECT * FROM l1_state_submission WHERE fuel_block_hash = $1",
+            fuel_block_hash.as_slice()
+        )
+        .fetch_optional(&self.connection_pool)
+        .await?
+        .map(StateSubmission::try_from)
+        .transpose()
+    }
+
+    pub(crate) async fn _reserve_nonce(&self, address: [u8; 20], min_nonce: u64) -> Result<u64> {
+        let min_nonce = i64::try_from(min_nonce)
+            .map_err(|_| Error::Conversion(format!("nonce {min_nonce} doesn't fit into an i64")))?;
+
+        // a single statement so that concurrent reservations never hand out the same nonce
+        let reserved = sqlx::query!(
+            "INSERT INTO l1_nonces (address, next_nonce) VALUES ($1, $2::BIGINT + 1)
+            ON CONFLICT (address) DO UPDATE SET next_nonce = GREATEST(l1_nonces.next_nonce, $2::BIGINT) + 1
+            RETURNING next_nonce - 1 AS reserved",
+            address.as_slice(),
+            min_nonce,
+        )
+        .fetch_one(&self.connection_pool)
+        .await?
+        .reserved
+        .expect("returning clause always produces a value");
+
+        u64::try_from(reserved)
+            .map_err(|_| Error::Conversion(format!("invalid nonce `{reserved}` stored in db")))
+    }
+
+    pub(crate) async fn _next_nonce(&self, address: [u8; 20]) -> Result<Option<u64>> {
+        sqlx::query!(
+            "SELECT next_nonce FROM l1_nonces WHERE address = $1",
+            address.as_slice()
+        )
+        .fetch_optional(&self.connection_pool)
+        .await?
+        .map(|row| {
+            u64::try_from(row.next_nonce).map_err(|_| {
+                Error::Conversion(format!("invalid nonce `{}` stored in db", row.next_nonce))
+            })
+        })
+        .transpose()
+    }
+
+    pub(crate) async fn _set_next_nonce(&self, address: [u8; 20], nonce: u64) -> Result<()> {
+        let nonce = i64::try_from(nonce)
+            .map_err(|_| Error::Conversion(format!("nonce {nonce} doesn't fit into an i64")))?;
+
+        sqlx::query!(
+            "INSERT INTO l1_nonces (address, next_nonce) VALUES ($1, $2)
+            ON CONFLICT (address) DO UPDATE SET next_nonce = $2",
+            address.as_slice(),
+            nonce,
+        )
+        .execute(&self.connection_pool)
+        .await?;
+
+        Ok(())
+    }
 }
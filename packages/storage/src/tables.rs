@@ -1,4 +1,4 @@
-use ports::types::BlockSubmission;
+use ports::types::{BlockSubmission, CommitLocation, TransactionResponse, TxFees};
 
 macro_rules! bail {
     ($msg: literal, $($args: expr),*) => {
@@ -6,12 +6,40 @@ macro_rules! bail {
     };
 }
 
+macro_rules! convert_column {
+    ($value: expr, $column: literal) => {
+        match $value.try_into() {
+            Ok(value) => value,
+            Err(_) => {
+                bail!(
+                    "`{}` cannot be converted between the db and the domain type. Got: {}",
+                    $column,
+                    $value
+                );
+            }
+        }
+    };
+}
+
 #[derive(sqlx::FromRow)]
 pub struct L1FuelBlockSubmission {
     pub fuel_block_hash: Vec<u8>,
     pub fuel_block_height: i64,
     pub completed: bool,
     pub submittal_height: i64,
+    pub tx_hash: Option<Vec<u8>>,
+    pub tx_block_height: Option<i64>,
+    pub tx_block_hash: Option<Vec<u8>>,
+    pub tx_succeeded: Option<bool>,
+    pub tx_gas_used: Option<i64>,
+    pub tx_effective_gas_price: Option<i64>,
+    pub attempts: i64,
+    pub retry_at: i64,
+    pub commit_l1_height: Option<i64>,
+    pub commit_l1_block_hash: Option<Vec<u8>>,
+    pub tx_nonce: Option<i64>,
+    pub tx_max_fee_per_gas: Option<i64>,
+    pub tx_max_priority_fee_per_gas: Option<i64>,
 }
 
 impl TryFrom<L1FuelBlockSubmission> for BlockSubmission {
@@ -35,28 +63,169 @@ impl TryFrom<L1FuelBlockSubmission> for BlockSubmission {
             bail!("`submittal_height` as read from the db cannot fit in a `u64` as expected. Got: {} from db", value.submittal_height);
         };
 
+        let tx_hash = match value.tx_hash {
+            Some(tx_hash) => {
+                let Ok(tx_hash) = tx_hash.as_slice().try_into() else {
+                    bail!("Expected 32 bytes for `tx_hash`, but got: {tx_hash:?} from db",);
+                };
+                Some(tx_hash)
+            }
+            None => None,
+        };
+
+        let tx_receipt = match (
+            value.tx_block_height,
+            value.tx_block_hash,
+            value.tx_succeeded,
+            value.tx_gas_used,
+            value.tx_effective_gas_price,
+        ) {
+            (
+                Some(block_height),
+                Some(block_hash),
+                Some(succeeded),
+                Some(gas_used),
+                Some(effective_gas_price),
+            ) => {
+                let Ok(tx_block_hash) = block_hash.as_slice().try_into() else {
+                    bail!("Expected 32 bytes for `tx_block_hash`, but got: {block_hash:?} from db",);
+                };
+                Some(TransactionResponse::new(
+                    convert_column!(block_height, "tx_block_height"),
+                    tx_block_hash,
+                    succeeded,
+                    convert_column!(gas_used, "tx_gas_used"),
+                    convert_column!(effective_gas_price, "tx_effective_gas_price"),
+                ))
+            }
+            (None, None, None, None, None) => None,
+            _ => {
+                bail!(
+                    "Receipt of the commit tx of block {:?} is only partially stored in the db",
+                    block_hash
+                );
+            }
+        };
+
+        let tx_nonce = match value.tx_nonce {
+            Some(nonce) => Some(convert_column!(nonce, "tx_nonce")),
+            None => None,
+        };
+
+        let tx_fees = match (value.tx_max_fee_per_gas, value.tx_max_priority_fee_per_gas) {
+            (Some(max_fee_per_gas), Some(max_priority_fee_per_gas)) => Some(TxFees {
+                max_fee_per_gas: convert_column!(max_fee_per_gas, "tx_max_fee_per_gas"),
+                max_priority_fee_per_gas: convert_column!(
+                    max_priority_fee_per_gas,
+                    "tx_max_priority_fee_per_gas"
+                ),
+                max_fee_per_blob_gas: 0,
+            }),
+            (None, None) => None,
+            _ => {
+                bail!(
+                    "Fees of the commit tx of block {:?} are only partially stored in the db",
+                    block_hash
+                );
+            }
+        };
+
+        let commit_location = match (value.commit_l1_height, value.commit_l1_block_hash) {
+            (Some(l1_height), Some(l1_block_hash)) => {
+                let Ok(l1_block_hash) = l1_block_hash.as_slice().try_into() else {
+                    bail!("Expected 32 bytes for `commit_l1_block_hash`, but got: {l1_block_hash:?} from db",);
+                };
+                Some(CommitLocation {
+                    l1_height: convert_column!(l1_height, "commit_l1_height"),
+                    l1_block_hash,
+                })
+            }
+            (None, None) => None,
+            _ => {
+                bail!(
+                    "Commit location of block {:?} is only partially stored in the db",
+                    block_hash
+                );
+            }
+        };
+
         Ok(Self {
             block_hash,
             block_height,
             completed: value.completed,
             submittal_height,
+            commit_location,
+            tx_hash,
+            tx_nonce,
+            tx_fees,
+            tx_receipt,
+            attempts: convert_column!(value.attempts, "attempts"),
+            retry_at: convert_column!(value.retry_at, "retry_at"),
         })
     }
 }
 
-impl From<BlockSubmission> for L1FuelBlockSubmission {
-    fn from(value: BlockSubmission) -> Self {
-        Self {
+impl TryFrom<BlockSubmission> for L1FuelBlockSubmission {
+    type Error = crate::error::Error;
+
+    fn try_from(value: BlockSubmission) -> Result<Self, Self::Error> {
+        let (tx_block_height, tx_block_hash, tx_succeeded, tx_gas_used, tx_effective_gas_price) =
+            match value.tx_receipt {
+                Some(receipt) => (
+                    Some(convert_column!(receipt.block_number(), "tx_block_height")),
+                    Some(receipt.block_hash().to_vec()),
+                    Some(receipt.succeeded()),
+                    Some(convert_column!(receipt.gas_used(), "tx_gas_used")),
+                    Some(convert_column!(
+                        receipt.effective_gas_price(),
+                        "tx_effective_gas_price"
+                    )),
+                ),
+                None => (None, None, None, None, None),
+            };
+
+        Ok(Self {
             fuel_block_hash: value.block_hash.to_vec(),
             fuel_block_height: i64::from(value.block_height),
             completed: value.completed,
             submittal_height: value.submittal_height.into(),
-        }
+            tx_hash: value.tx_hash.map(|hash| hash.to_vec()),
+            tx_nonce: match value.tx_nonce {
+                Some(nonce) => Some(convert_column!(nonce, "tx_nonce")),
+                None => None,
+            },
+            tx_max_fee_per_gas: match value.tx_fees {
+                Some(fees) => Some(convert_column!(fees.max_fee_per_gas, "tx_max_fee_per_gas")),
+                None => None,
+            },
+            tx_max_priority_fee_per_gas: match value.tx_fees {
+                Some(fees) => Some(convert_column!(
+                    fees.max_priority_fee_per_gas,
+                    "tx_max_priority_fee_per_gas"
+                )),
+                None => None,
+            },
+            tx_block_height,
+            tx_block_hash,
+            tx_succeeded,
+            tx_gas_used,
+            tx_effective_gas_price,
+            attempts: i64::from(value.attempts),
+            retry_at: value.retry_at.into(),
+            commit_l1_height: value
+                .commit_location
+                .map(|location| location.l1_height.into()),
+            commit_l1_block_hash: value
+                .commit_location
+                .map(|location| location.l1_block_hash.to_vec()),
+        })
     }
 }
 
 pub mod state_submission {
-    use ports::types::{StateFragment, StateSubmission};
+    use ports::types::{
+        Compression, StateFragment, StateSubmission, SubmissionTx, TransactionState,
+    };
     use sqlx::types::chrono;
 
     #[derive(sqlx::FromRow)]
@@ -64,6 +233,8 @@ pub mod state_submission {
         pub fuel_block_hash: Vec<u8>,
         pub fuel_block_height: i64,
         pub completed: bool,
+        pub compression: i16,
+        pub num_fragments: i64,
     }
 
     #[derive(sqlx::FromRow)]
@@ -93,10 +264,29 @@ pub mod state_submission {
             );
             };
 
+            let compression = u8::try_from(value.compression)
+                .ok()
+                .and_then(|id| Compression::try_from(id).ok());
+            let Some(compression) = compression else {
+                bail!(
+                    "`compression` as read from the db is not a known codec id. Got: {:?} from db",
+                    value.compression
+                );
+            };
+
+            let Ok(num_fragments) = value.num_fragments.try_into() else {
+                bail!(
+                    "`num_fragments` as read from the db cannot fit in a `u32` as expected. Got: {:?} from db",
+                    value.num_fragments
+                );
+            };
+
             Ok(Self {
                 block_height,
                 block_hash,
                 completed: value.completed,
+                compression,
+                num_fragments,
             })
         }
     }
@@ -107,6 +297,8 @@ pub mod state_submission {
                 fuel_block_height: i64::from(value.block_height),
                 completed: value.completed,
                 fuel_block_hash: value.block_hash.to_vec(),
+                compression: i16::from(value.compression.id()),
+                num_fragments: i64::from(value.num_fragments),
             }
         }
     }
@@ -166,29 +358,62 @@ pub mod state_submission {
     }
 
     #[derive(sqlx::FromRow)]
-    pub struct L1PendingTransaction {
+    pub struct L1SubmissionTx {
         pub transaction_hash: Vec<u8>,
+        pub state: i16,
+        pub submitted_at: i64,
+        pub details: Vec<u8>,
+    }
+
+    impl L1SubmissionTx {
+        pub fn db_state(state: TransactionState) -> i16 {
+            match state {
+                TransactionState::Pending => 0,
+                TransactionState::Finalized => 1,
+                TransactionState::Failed => 2,
+            }
+        }
     }
 
-    impl TryFrom<L1PendingTransaction> for [u8; 32] {
+    impl TryFrom<L1SubmissionTx> for SubmissionTx {
         type Error = crate::error::Error;
 
-        fn try_from(value: L1PendingTransaction) -> Result<Self, Self::Error> {
+        fn try_from(value: L1SubmissionTx) -> Result<Self, Self::Error> {
             let transaction_hash = value.transaction_hash.as_slice();
-            let Ok(transaction_hash) = transaction_hash.try_into() else {
+            let Ok(hash) = transaction_hash.try_into() else {
                 bail!(
                 "Expected 32 bytes for `transaction_hash`, but got: {transaction_hash:?} from db",
             );
             };
 
-            Ok(transaction_hash)
+            let state = match value.state {
+                0 => TransactionState::Pending,
+                1 => TransactionState::Finalized,
+                2 => TransactionState::Failed,
+                _ => {
+                    bail!(
+                        "`state` as read from the db is not a known transaction state. Got: {} from db",
+                        value.state
+                    );
+                }
+            };
+
+            Ok(Self {
+                hash,
+                details: value.details,
+                submitted_at: convert_column!(value.submitted_at, "submitted_at"),
+                state,
+            })
         }
     }
 
-    impl From<[u8; 32]> for L1PendingTransaction {
-        fn from(value: [u8; 32]) -> Self {
+    impl From<SubmissionTx> for L1SubmissionTx {
+        fn from(value: SubmissionTx) -> Self {
             Self {
-                transaction_hash: value.to_vec(),
+                transaction_hash: value.hash.to_vec(),
+                state: Self::db_state(value.state),
+                submitted_at: i64::from(value.submitted_at),
+                details: value.details,
             }
         }
     }
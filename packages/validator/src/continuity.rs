@@ -0,0 +1,173 @@
+use std::sync::{Arc, Mutex};
+
+use crate::{block::ValidatedFuelBlock, Error, Result};
+
+/// Checks that the fuel node keeps serving the chain we committed to, by walking the blocks it
+/// serves from the committed one up to the one about to be committed. Only the committed block and
+/// the heights of the walked blocks are checked. A block isn't linked to its parent: `prev_root` is
+/// the merkle root of every preceding block id rather than the id of the parent, so it can't be
+/// recomputed from the blocks walked here. A fork starting right after the committed block goes
+/// unnoticed. Once a discontinuity is seen every later check fails as well, halting submissions
+/// until the committer is restarted.
+#[derive(Debug, Clone, Default)]
+pub struct ChainContinuity {
+    discontinuity: Arc<Mutex<Option<String>>>,
+}
+
+impl ChainContinuity {
+    pub fn is_intact(&self) -> bool {
+        self.discontinuity
+            .lock()
+            .expect("lock not poisoned")
+            .is_none()
+    }
+
+    /// Fails if a discontinuity was seen before.
+    pub fn ensure_intact(&self) -> Result<()> {
+        match self
+            .discontinuity
+            .lock()
+            .expect("lock not poisoned")
+            .as_ref()
+        {
+            Some(reason) => Err(Error::ChainDiscontinuity(reason.clone())),
+            None => Ok(()),
+        }
+    }
+
+    /// `chain` holds the blocks the node now serves from `committed_height` on, lowest first. It
+    /// has to start with the committed block, continue without gaps and, if given, end with
+    /// `next`, the block about to be committed on top of it.
+    pub fn validate(
+        &self,
+        committed_height: u32,
+        committed_hash: [u8; 32],
+        chain: &[ValidatedFuelBlock],
+        next: Option<&ValidatedFuelBlock>,
+    ) -> Result<()> {
+        self.ensure_intact()?;
+
+        let Some(served) = chain.first() else {
+            return self.broken(format!(
+                "fuel node serves no block at the committed height {committed_height}"
+            ));
+        };
+
+        if served.height() != committed_height || served.hash() != committed_hash {
+            return self.broken(format!(
+                "fuel node serves block `{}` at height {} where `{}` was committed at height \
+                 {committed_height}",
+                hex(served.hash()),
+                served.height(),
+                hex(committed_hash),
+            ));
+        }
+
+        if let Some((parent, child)) = chain
+            .iter()
+            .zip(chain.iter().skip(1))
+            .find(|(parent, child)| Some(child.height()) != parent.height().checked_add(1))
+        {
+            return self.broken(format!(
+                "block `{}` at height {} doesn't follow block `{}` at height {}",
+                hex(child.hash()),
+                child.height(),
+                hex(parent.hash()),
+                parent.height(),
+            ));
+        }
+
+        if let Some(next) = next.filter(|next| chain.last() != Some(*next)) {
+            return self.broken(format!(
+                "block `{}` at height {} isn't part of the chain served on top of the block \
+                 committed at height {committed_height}",
+                hex(next.hash()),
+                next.height(),
+            ));
+        }
+
+        Ok(())
+    }
+
+    fn broken(&self, reason: String) -> Result<()> {
+        *self.discontinuity.lock().expect("lock not poisoned") = Some(reason.clone());
+
+        Err(Error::ChainDiscontinuity(reason))
+    }
+}
+
+fn hex(hash: [u8; 32]) -> String {
+    hash.map(|byte| format!("{byte:02x}")).join("")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn accepts_blocks_extending_the_committed_chain() {
+        // given
+        let continuity = ChainContinuity::default();
+        let chain = given_chain(2, &[[1; 32], [5; 32], [2; 32]]);
+
+        // when
+        let result = continuity.validate(2, [1; 32], &chain, chain.last());
+
+        // then
+        assert!(result.is_ok());
+        assert!(continuity.is_intact());
+    }
+
+    #[test]
+    fn a_fork_halts_every_later_check() {
+        // given
+        let continuity = ChainContinuity::default();
+        let forked = given_chain(2, &[[9; 32], [2; 32]]);
+
+        // when
+        let result = continuity.validate(2, [1; 32], &forked, forked.last());
+
+        // then
+        assert!(matches!(result, Err(Error::ChainDiscontinuity(_))));
+        assert!(!continuity.is_intact());
+        assert!(continuity.ensure_intact().is_err());
+    }
+
+    #[test]
+    fn rejects_a_next_block_missing_from_the_served_chain() {
+        // given
+        let continuity = ChainContinuity::default();
+        let chain = given_chain(2, &[[1; 32], [5; 32]]);
+        let next_on_a_fork = ValidatedFuelBlock::new([6; 32], 3);
+
+        // when
+        let result = continuity.validate(2, [1; 32], &chain, Some(&next_on_a_fork));
+
+        // then
+        assert!(matches!(result, Err(Error::ChainDiscontinuity(_))));
+    }
+
+    #[test]
+    fn rejects_gaps_in_the_served_chain() {
+        // given
+        let continuity = ChainContinuity::default();
+        let chain = vec![
+            ValidatedFuelBlock::new([1; 32], 2),
+            ValidatedFuelBlock::new([2; 32], 4),
+        ];
+
+        // when
+        let result = continuity.validate(2, [1; 32], &chain, chain.last());
+
+        // then
+        assert!(matches!(result, Err(Error::ChainDiscontinuity(_))));
+    }
+
+    fn given_chain(from_height: u32, hashes: &[[u8; 32]]) -> Vec<ValidatedFuelBlock> {
+        hashes
+            .iter()
+            .zip(from_height..)
+            .map(|(hash, height)| ValidatedFuelBlock::new(*hash, height))
+            .collect()
+    }
+}
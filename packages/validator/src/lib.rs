@@ -1,7 +1,9 @@
 pub mod block;
+mod continuity;
 #[cfg(feature = "validator")]
 mod validator;
 
+pub use continuity::ChainContinuity;
 use fuel_core_client::client::types::block::Block as FuelBlock;
 #[cfg(feature = "validator")]
 pub use validator::*;
@@ -12,6 +14,10 @@ use crate::block::ValidatedFuelBlock;
 pub enum Error {
     #[error("{0}")]
     BlockValidation(String),
+    #[error("chain discontinuity: {0}")]
+    ChainDiscontinuity(String),
+    #[error("transactions mismatch: {0}")]
+    TransactionsMismatch(String),
 }
 
 pub type Result<T> = std::result::Result<T, Error>;
@@ -19,4 +25,8 @@ pub type Result<T> = std::result::Result<T, Error>;
 #[cfg_attr(feature = "test-helpers", mockall::automock)]
 pub trait Validator: Send + Sync {
     fn validate(&self, fuel_block: &FuelBlock) -> Result<ValidatedFuelBlock>;
+    /// Checks the canonically encoded `transactions` against the count and merkle root committed
+    /// to in the block header.
+    fn validate_transactions(&self, fuel_block: &FuelBlock, transactions: &[Vec<u8>])
+        -> Result<()>;
 }
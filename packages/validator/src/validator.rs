@@ -6,29 +6,61 @@ use fuel_core_client::client::types::{
     primitives::{BlockId as FuelBlockId, Bytes32 as FuelBytes32, PublicKey as FuelPublicKey},
 };
 use fuel_crypto::{Hasher, Message};
+use fuel_merkle::binary::root_calculator::MerkleRootCalculator;
 
 use crate::{block::ValidatedFuelBlock, Error, Result, Validator};
 
 #[derive(Debug)]
 pub struct BlockValidator {
-    producer_pub_key: FuelPublicKey,
+    /// Producer keys by the height they take effect at, ordered by height.
+    producer_keys: Vec<ProducerKey>,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct ProducerKey {
+    activation_height: u32,
+    public_key: FuelPublicKey,
 }
 
 impl Validator for BlockValidator {
     fn validate(&self, fuel_block: &FuelBlock) -> Result<ValidatedFuelBlock> {
         self._validate(fuel_block)
     }
+
+    fn validate_transactions(
+        &self,
+        fuel_block: &FuelBlock,
+        transactions: &[Vec<u8>],
+    ) -> Result<()> {
+        Self::_validate_transactions(fuel_block, transactions)
+    }
 }
 
 impl BlockValidator {
     pub fn new(producer_pub_key: FuelPublicKey) -> Self {
-        Self { producer_pub_key }
+        Self::with_key_schedule([(0, producer_pub_key)])
+    }
+
+    /// Validates each block against the key of the latest `(activation height, public key)`
+    /// pair at or below its height. Blocks below the first activation height are rejected.
+    pub fn with_key_schedule(schedule: impl IntoIterator<Item = (u32, FuelPublicKey)>) -> Self {
+        let mut producer_keys: Vec<_> = schedule
+            .into_iter()
+            .map(|(activation_height, public_key)| ProducerKey {
+                activation_height,
+                public_key,
+            })
+            .collect();
+        producer_keys.sort_by_key(|key| key.activation_height);
+
+        Self { producer_keys }
     }
 
     fn _validate(&self, fuel_block: &FuelBlock) -> Result<ValidatedFuelBlock> {
-        self.validate_public_key(fuel_block)?;
+        let producer_key = self.producer_key_at(fuel_block.header.height)?;
+        Self::validate_public_key(fuel_block, producer_key)?;
         Self::validate_block_id(fuel_block)?;
-        self.validate_block_signature(fuel_block)?;
+        Self::validate_block_signature(fuel_block, producer_key)?;
 
         Ok(ValidatedFuelBlock {
             hash: *fuel_block.id,
@@ -36,18 +68,71 @@ impl BlockValidator {
         })
     }
 
-    fn validate_public_key(&self, fuel_block: &FuelBlock) -> Result<()> {
+    fn _validate_transactions(fuel_block: &FuelBlock, transactions: &[Vec<u8>]) -> Result<()> {
+        let FuelHeader {
+            transactions_count,
+            transactions_root,
+            ..
+        } = &fuel_block.header;
+
+        if transactions.len() != usize::from(*transactions_count) {
+            return Err(Error::TransactionsMismatch(format!(
+                "fuel block with id `{:x}` has {transactions_count} transactions \
+                 but {} were provided.",
+                fuel_block.id,
+                transactions.len()
+            )));
+        }
+
+        let calculated_root = Self::transactions_root(transactions);
+        if *transactions_root != calculated_root {
+            return Err(Error::TransactionsMismatch(format!(
+                "transactions root `{transactions_root:x}` of fuel block with id `{:x}` does not \
+                 match calculated transactions root `{calculated_root:x}`.",
+                fuel_block.id
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Sets the transaction count, transactions root and application hash of `header` the way the
+    /// block producer does, so that tests can build blocks holding `transactions`.
+    #[cfg(feature = "test-helpers")]
+    pub fn commit_to_transactions(header: &mut FuelHeader, transactions: &[Vec<u8>]) {
+        header.transactions_count =
+            u16::try_from(transactions.len()).expect("a block holds at most u16::MAX transactions");
+        header.transactions_root = Self::transactions_root(transactions);
+        header.application_hash = Self::application_hash(header);
+    }
+
+    fn transactions_root(transactions: &[Vec<u8>]) -> FuelBytes32 {
+        FuelBytes32::from(MerkleRootCalculator::new().root_from_iterator(transactions.iter()))
+    }
+
+    fn producer_key_at(&self, height: u32) -> Result<ProducerKey> {
+        self.producer_keys
+            .iter()
+            .rev()
+            .find(|key| key.activation_height <= height)
+            .copied()
+            .ok_or_else(|| {
+                Error::BlockValidation(format!("no producer key is active at height {height}"))
+            })
+    }
+
+    fn validate_public_key(fuel_block: &FuelBlock, expected: ProducerKey) -> Result<()> {
         let Some(producer_pub_key) = fuel_block.block_producer() else {
             return Err(Error::BlockValidation(
                 "producer public key not found in fuel block".to_string(),
             ));
         };
 
-        if *producer_pub_key != self.producer_pub_key {
+        if *producer_pub_key != expected.public_key {
             return Err(Error::BlockValidation(format!(
                 "producer public key `{producer_pub_key:x}` does not match \
-                 expected public key `{:x}`.",
-                self.producer_pub_key
+                 expected public key `{:x}` active since height {}.",
+                expected.public_key, expected.activation_height
             )));
         }
 
@@ -67,7 +152,7 @@ impl BlockValidator {
         Ok(())
     }
 
-    fn validate_block_signature(&self, fuel_block: &FuelBlock) -> Result<()> {
+    fn validate_block_signature(fuel_block: &FuelBlock, expected: ProducerKey) -> Result<()> {
         let FuelConsensus::PoAConsensus(FuelPoAConsensus { signature }) = fuel_block.consensus
         else {
             return Err(Error::BlockValidation(
@@ -78,11 +163,11 @@ impl BlockValidator {
         let block_id_message = Message::from_bytes(*fuel_block.id);
 
         signature
-            .verify(&self.producer_pub_key, &block_id_message)
+            .verify(&expected.public_key, &block_id_message)
             .map_err(|_| {
                 Error::BlockValidation(format!(
-                    "signature validation failed for fuel block with id: `{:x}` and pub key: `{:x}`",
-                    fuel_block.id, &self.producer_pub_key
+                    "signature validation failed for fuel block with id: `{:x}` and pub key: `{:x}` active since height {}",
+                    fuel_block.id, &expected.public_key, expected.activation_height
                 ))
             })?;
 
@@ -205,6 +290,74 @@ mod tests {
         validator.validate(&fuel_block).unwrap();
     }
 
+    #[test]
+    fn validates_blocks_against_the_key_active_at_their_height() {
+        // given
+        let secret_key = given_secret_key();
+        let fuel_block = given_a_block(Some(secret_key));
+        let rotated_key = SecretKey::random(&mut StdRng::seed_from_u64(7)).public_key();
+
+        let key_active =
+            BlockValidator::with_key_schedule([(0, secret_key.public_key()), (1, rotated_key)]);
+        let key_not_yet_active =
+            BlockValidator::with_key_schedule([(0, rotated_key), (1, secret_key.public_key())]);
+
+        // when
+        let valid = key_active.validate(&fuel_block);
+        let invalid = key_not_yet_active.validate(&fuel_block);
+
+        // then
+        assert!(valid.is_ok());
+        let Err(Error::BlockValidation(msg)) = invalid else {
+            panic!("expected the block to be rejected");
+        };
+        assert!(msg.contains(&format!("`{rotated_key:x}` active since height 0")));
+    }
+
+    #[test]
+    #[should_panic(expected = "no producer key is active at height 0")]
+    fn rejects_blocks_below_the_first_activation_height() {
+        let secret_key = given_secret_key();
+        let fuel_block = given_a_block(Some(secret_key));
+        let validator = BlockValidator::with_key_schedule([(10, secret_key.public_key())]);
+
+        validator.validate(&fuel_block).unwrap();
+    }
+
+    #[test]
+    fn validates_transactions_against_the_header() {
+        // given
+        let transactions = vec![vec![1, 2, 3], vec![4, 5, 6]];
+        let fuel_block = given_a_block_with_transactions(&transactions);
+        let validator = BlockValidator::new(given_secret_key().public_key());
+
+        let mut tampered = transactions.clone();
+        tampered[1][0] = 0;
+
+        // when
+        let valid = validator.validate_transactions(&fuel_block, &transactions);
+        let invalid = validator.validate_transactions(&fuel_block, &tampered);
+
+        // then
+        assert!(valid.is_ok());
+        let Err(Error::TransactionsMismatch(msg)) = invalid else {
+            panic!("expected the transactions to be rejected");
+        };
+        assert!(msg.contains("does not match calculated transactions root"));
+    }
+
+    #[test]
+    #[should_panic(expected = "has 2 transactions but 1 were provided")]
+    fn rejects_missing_transactions() {
+        let transactions = vec![vec![1, 2, 3], vec![4, 5, 6]];
+        let fuel_block = given_a_block_with_transactions(&transactions);
+        let validator = BlockValidator::new(given_secret_key().public_key());
+
+        validator
+            .validate_transactions(&fuel_block, &transactions[..1])
+            .unwrap();
+    }
+
     fn given_secret_key() -> SecretKey {
         let mut rng = StdRng::seed_from_u64(42);
 
@@ -239,6 +392,19 @@ mod tests {
         }
     }
 
+    fn given_a_block_with_transactions(transactions: &[Vec<u8>]) -> FuelBlock {
+        let mut calculator = MerkleRootCalculator::new();
+        for transaction in transactions {
+            calculator.push(transaction);
+        }
+
+        let mut fuel_block = given_a_block(None);
+        fuel_block.header.transactions_count = transactions.len() as u16;
+        fuel_block.header.transactions_root = calculator.root().into();
+
+        fuel_block
+    }
+
     fn given_header() -> FuelHeader {
         let application_hash = "0x017ab4b70ea129c29e932d44baddc185ad136bf719c4ada63a10b5bf796af91e"
             .parse()